@@ -36,7 +36,7 @@ fn main() {
     // Write the vec! macro call with all the rules
     writeln!(f, "vec![").unwrap();
     for module in &rule_modules {
-        writeln!(f, "    {module}::get_rule(),").unwrap();
+        writeln!(f, "    Rc::new({module}::get_rule()) as Rc<dyn Rule>,").unwrap();
     }
     writeln!(f, "]").unwrap();
 }
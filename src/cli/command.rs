@@ -1,22 +1,37 @@
 use shlex::{split, Shlex};
-use std::process::{Command, Stdio};
-use std::{fmt, str};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::rc::Rc;
+use std::thread;
 
+use crate::cli::output::CappedOutput;
+use crate::context::Context;
 use crate::shell::Shell;
 
-#[derive(Debug)]
+/// Callback run when a corrected command is selected, receiving the original command and
+/// the selected script.
+pub type SideEffect = Rc<dyn Fn(CrabCommand, Option<&str>, &dyn Context)>;
+
 pub struct CorrectedCommand {
     pub script: String,
-    pub side_effect: Option<fn(CrabCommand, Option<&str>)>,
+    pub side_effect: Option<SideEffect>,
     pub priority: u16,
 }
 
+impl fmt::Debug for CorrectedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CorrectedCommand")
+            .field("script", &self.script)
+            .field("side_effect", &self.side_effect.is_some())
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
 impl CorrectedCommand {
-    pub fn new(
-        script: String,
-        side_effect: Option<fn(CrabCommand, Option<&str>)>,
-        priority: u16,
-    ) -> Self {
+    pub fn new(script: String, side_effect: Option<SideEffect>, priority: u16) -> Self {
         Self {
             script,
             side_effect,
@@ -26,10 +41,16 @@ impl CorrectedCommand {
     pub fn get_script(&self) -> &String {
         &self.script
     }
-    pub fn run(&self, old_command: CrabCommand) {
-        if let Some(side_effect) = self.side_effect {
-            (side_effect)(old_command, Some(&self.script));
+    /// Runs the side effect of the rule, if any, and prints the script for the shell to run.
+    pub fn run(&self, old_command: CrabCommand, context: &dyn Context) {
+        if let Some(side_effect) = &self.side_effect {
+            (side_effect)(old_command, Some(&self.script), context);
         }
+        self.print();
+    }
+    /// Prints the script without running the side effect, for a command line that the user
+    /// may still edit or drop.
+    pub fn print(&self) {
         println!("{}", self.get_script());
     }
 }
@@ -37,7 +58,16 @@ impl CorrectedCommand {
 #[derive(Debug)]
 pub struct CrabCommand {
     pub script: String,
+    /// Combined view of `stdout` and `stderr`, which is what most rules match against.
     pub output: Option<String>,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+    /// Exit code of the command, `None` if unknown or if it was terminated by a signal.
+    pub exit_code: Option<i32>,
+    /// Signal that terminated the command, if any.
+    pub signal: Option<i32>,
+    /// `true` if the output was not valid UTF-8 and some bytes had to be replaced.
+    pub lossy_output: bool,
     pub script_parts: Vec<String>,
 }
 
@@ -52,19 +82,19 @@ impl fmt::Display for CrabCommand {
     }
 }
 
-fn concat_stdout_stderr(stdout: Option<String>, stderr: Option<String>) -> Option<String> {
+fn concat_stdout_stderr(stdout: Option<&str>, stderr: Option<&str>) -> Option<String> {
     match (stdout, stderr) {
         (Some(stdout), Some(stderr)) => Some({
             if !stderr.is_empty() && !stdout.is_empty() {
-                format!("{}\n{}", stdout, &stderr)
+                format!("{stdout}\n{stderr}")
             } else if !stderr.is_empty() {
-                stderr
+                stderr.to_owned()
             } else {
-                stdout
+                stdout.to_owned()
             }
         }),
-        (Some(stdout), None) => Some(stdout),
-        (None, Some(stderr)) => Some(stderr),
+        (Some(stdout), None) => Some(stdout.to_owned()),
+        (None, Some(stderr)) => Some(stderr.to_owned()),
         (None, None) => None,
     }
 }
@@ -72,28 +102,74 @@ fn concat_stdout_stderr(stdout: Option<String>, stderr: Option<String>) -> Optio
 impl CrabCommand {
     pub fn new(script: String, stdout: Option<String>, stderr: Option<String>) -> Self {
         let split_parts = CrabCommand::split_command(&script);
-        let output = concat_stdout_stderr(stdout, stderr);
+        let output = concat_stdout_stderr(stdout.as_deref(), stderr.as_deref());
 
         CrabCommand {
             script,
             output,
+            stdout,
+            stderr,
+            exit_code: None,
+            signal: None,
+            lossy_output: false,
             script_parts: split_parts,
         }
     }
 
+    /// Creates the command the shell could not find, as reported by its "command not
+    /// found" hook, without running it again.
+    pub fn not_found(raw_command: Vec<String>, system_shell: &dyn Shell) -> Self {
+        let parts = raw_command.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let script = system_shell.join(&parts);
+        let program = raw_command.first().cloned().unwrap_or_default();
+        let output = format!("{}: {program}: command not found", system_shell.get_shell());
+        CrabCommand::new(script, Some("".to_owned()), Some(output))
+            .with_exit_status(Some(127), None)
+    }
+
+    /// Creates a command from a command line that was not run, e.g. the one being typed.
+    ///
+    /// It has no output, so only the rules that do not require it can correct it.
+    pub fn from_buffer(raw_command: Vec<String>) -> Self {
+        CrabCommand::new(prepare_command(raw_command), None, None)
+    }
+
+    /// Sets how the command terminated: its exit code, or the signal that killed it.
+    pub fn with_exit_status(mut self, exit_code: Option<i32>, signal: Option<i32>) -> Self {
+        self.exit_code = exit_code;
+        self.signal = signal;
+        self
+    }
+
+    pub fn with_lossy_output(mut self, lossy_output: bool) -> Self {
+        self.lossy_output = lossy_output;
+        self
+    }
+
+    /// Returns `true` only if the command is known to have exited with code zero.
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0) && self.signal.is_none()
+    }
+
+    /// Returns a copy of the command with a new script and/or new output streams.
+    ///
+    /// Streams are replaced together: if either `stdout` or `stderr` is given, both
+    /// are taken from the arguments. The exit status is always kept.
     pub fn update(
         &self,
         script: Option<String>,
         stdout: Option<String>,
         stderr: Option<String>,
     ) -> CrabCommand {
-        let output = concat_stdout_stderr(stdout, stderr);
+        let (stdout, stderr) = if stdout.is_some() || stderr.is_some() {
+            (stdout, stderr)
+        } else {
+            (self.stdout.to_owned(), self.stderr.to_owned())
+        };
 
-        CrabCommand::new(
-            script.unwrap_or(self.script.to_owned()),
-            output.map_or(self.output.to_owned(), Some),
-            None,
-        )
+        CrabCommand::new(script.unwrap_or(self.script.to_owned()), stdout, stderr)
+            .with_exit_status(self.exit_code, self.signal)
+            .with_lossy_output(self.lossy_output)
     }
 
     fn split_command(command: &str) -> Vec<String> {
@@ -109,22 +185,79 @@ pub fn shlex_split(in_str: &str) -> Vec<String> {
     shl.by_ref().collect()
 }
 
-pub fn run_command(raw_command: Vec<String>, system_shell: &dyn Shell) -> CrabCommand {
+/// Environment variables that make programs print untranslated messages, which is what
+/// the rules match against.
+const C_LOCALE_ENV: [(&str, &str); 2] = [("LC_ALL", "C"), ("LANG", "C")];
+
+/// Makes `cmd` run with the C locale, regardless of the language of the user.
+pub fn force_c_locale(cmd: &mut Command) -> &mut Command {
+    cmd.envs(C_LOCALE_ENV).env_remove("LANGUAGE")
+}
+
+/// Runs the command and captures its output, keeping at most `max_output_bytes` bytes of
+/// each stream.
+///
+/// Both streams are read while the command runs, so a command printing a lot does not
+/// fill the memory: only their head and tail are kept.
+///
+/// Unless `keep_locale` is set, the command runs with the C locale so its messages can
+/// be matched by the rules.
+pub fn run_command(
+    raw_command: Vec<String>,
+    system_shell: &dyn Shell,
+    max_output_bytes: usize,
+    keep_locale: bool,
+) -> Result<CrabCommand, String> {
     let command = prepare_command(raw_command);
-    let mut output = shell_command(&system_shell.get_shell())
+    let shell = system_shell.get_shell();
+    let mut cmd = shell_command(&shell);
+    if !keep_locale {
+        force_c_locale(&mut cmd);
+    }
+    let mut child = cmd
         .arg(&command)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .expect("Command failed to start")
-        .wait_with_output()
-        .expect("failed to wait on child");
-
-    let exit_status = output.status;
-    // output.status.success();
-    let stdout = str::from_utf8(&output.stdout).map(|s| s.to_owned()).ok();
-    let stderr = str::from_utf8(&output.stderr).map(|s| s.to_owned()).ok();
-    CrabCommand::new(command, stdout, stderr)
+        .map_err(|error| format!("Could not run `{command}` with {shell}: {error}"))?;
+
+    let child_stdout = child.stdout.take().unwrap();
+    let child_stderr = child.stderr.take().unwrap();
+    let stderr_reader = thread::spawn(move || {
+        let mut stderr = CappedOutput::new(max_output_bytes);
+        stderr.read_from(child_stderr).map(|_| stderr)
+    });
+    let mut stdout = CappedOutput::new(max_output_bytes);
+    let read_error =
+        |error: io::Error| format!("Could not read the output of `{command}`: {error}");
+    stdout.read_from(child_stdout).map_err(read_error)?;
+    let stderr = stderr_reader
+        .join()
+        .expect("the stderr reader panicked")
+        .map_err(read_error)?;
+    let status = child
+        .wait()
+        .map_err(|error| format!("Could not wait for `{command}`: {error}"))?;
+
+    let stdout = stdout.decode();
+    let stderr = stderr.decode();
+    let lossy_output = stdout.lossy || stderr.lossy;
+    Ok(
+        CrabCommand::new(command, Some(stdout.text), Some(stderr.text))
+            .with_exit_status(status.code(), exit_signal(&status))
+            .with_lossy_output(lossy_output),
+    )
+}
+
+#[cfg(target_family = "unix")]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(target_family = "unix"))]
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    None
 }
 
 fn prepare_command(raw_command: Vec<String>) -> String {
@@ -141,7 +274,15 @@ pub fn shell_command(words_str: &str) -> Command {
     } else {
         "-c"
     };
-    let mut cmd = Command::new(first_cmd);
+    let mut cmd = Command::new(&first_cmd);
+    // Unlike other shells, (t)csh reads its startup files when running a command, which
+    // could print to the terminal and would slow down every run
+    let shell_name = Path::new(first_cmd.as_str())
+        .file_name()
+        .and_then(|name| name.to_str());
+    if matches!(shell_name, Some("tcsh") | Some("csh")) {
+        cmd.arg("-f");
+    }
     cmd.args(words);
     cmd.arg(dash_c);
     cmd
@@ -150,11 +291,17 @@ pub fn shell_command(words_str: &str) -> Command {
 #[cfg(test)]
 mod tests {
     use crate::{
-        cli::command::shell_command,
+        cli::command::{force_c_locale, shell_command},
         shell::{Bash, Shell},
     };
+    use std::ffi::OsStr;
 
-    use super::run_command;
+    use super::{run_command, CorrectedCommand, CrabCommand, SideEffect};
+    use crate::cli::output::DEFAULT_MAX_OUTPUT_BYTES;
+    use crate::context::{Context, FakeContext};
+    use rstest::rstest;
+    use std::path::Path;
+    use std::rc::Rc;
 
     #[test]
     fn test_shell_command() {
@@ -164,6 +311,21 @@ mod tests {
         assert_eq!(cmd.get_program().to_str().unwrap(), shell_name);
     }
 
+    #[rstest]
+    #[case("tcsh", vec!["-f", "-c"])]
+    #[case("/bin/csh", vec!["-f", "-c"])]
+    #[case("zsh", vec!["-c"])]
+    fn test_shell_command_args(#[case] shell_name: &str, #[case] expected: Vec<&str>) {
+        let cmd = shell_command(shell_name);
+        assert_eq!(cmd.get_program(), shell_name);
+        assert_eq!(
+            cmd.get_args()
+                .map(|arg| arg.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn test_run_command() {
@@ -177,9 +339,14 @@ mod tests {
         let command_vec = vec![terminal_command.to_owned(), "Hello!".to_owned()];
         let command = command_vec.join(" ").trim().to_owned();
         let system_shell: Box<dyn Shell> = Box::new(Bash {});
-        let crab_command = run_command(command_vec, &*system_shell);
+        let crab_command =
+            run_command(command_vec, &*system_shell, DEFAULT_MAX_OUTPUT_BYTES, false).unwrap();
         assert_eq!(crab_command.script, command);
         assert_eq!(crab_command.output.unwrap(), "Hello!\n");
+        assert_eq!(crab_command.stdout.unwrap(), "Hello!\n");
+        assert_eq!(crab_command.stderr.unwrap(), "");
+        assert_eq!(crab_command.exit_code, Some(0));
+        assert_eq!(crab_command.signal, None);
     }
 
     #[cfg(target_family = "unix")]
@@ -188,11 +355,176 @@ mod tests {
         let command_vec = vec!["ls".to_owned(), "non_existent_directory".to_owned()];
         let command = command_vec.join(" ").trim().to_owned();
         let system_shell: Box<dyn Shell> = Box::new(Bash {});
-        let crab_command = run_command(command_vec, &*system_shell);
+        let crab_command =
+            run_command(command_vec, &*system_shell, DEFAULT_MAX_OUTPUT_BYTES, false).unwrap();
         assert_eq!(crab_command.script, command);
         assert!(crab_command
             .output
+            .as_ref()
             .unwrap()
             .contains("No such file or directory"));
+        assert_eq!(crab_command.stdout.as_deref(), Some(""));
+        assert!(crab_command
+            .stderr
+            .as_ref()
+            .unwrap()
+            .contains("No such file or directory"));
+        assert_ne!(crab_command.exit_code, Some(0));
+        assert!(!crab_command.succeeded());
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_run_command_killed_by_signal() {
+        let command_vec = vec!["kill".to_owned(), "-TERM".to_owned(), "$$".to_owned()];
+        let system_shell: Box<dyn Shell> = Box::new(Bash {});
+        let crab_command =
+            run_command(command_vec, &*system_shell, DEFAULT_MAX_OUTPUT_BYTES, false).unwrap();
+        assert_eq!(crab_command.exit_code, None);
+        assert_eq!(crab_command.signal, Some(15));
+        assert!(!crab_command.succeeded());
+    }
+
+    #[test]
+    fn test_force_c_locale() {
+        let mut cmd = shell_command("bash");
+        cmd.env("LC_ALL", "de_DE.UTF-8")
+            .env("LANG", "pt_BR.UTF-8")
+            .env("LANGUAGE", "de:pt");
+        force_c_locale(&mut cmd);
+
+        let mut envs = cmd.get_envs().collect::<Vec<_>>();
+        envs.sort();
+        assert_eq!(
+            envs,
+            vec![
+                (OsStr::new("LANG"), Some(OsStr::new("C"))),
+                (OsStr::new("LANGUAGE"), None),
+                (OsStr::new("LC_ALL"), Some(OsStr::new("C"))),
+            ]
+        );
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_run_command_with_invalid_utf8() {
+        let command_vec = vec![
+            "printf".to_owned(),
+            "'\\033[31mcaf\\351\\033[0m'".to_owned(),
+        ];
+        let system_shell: Box<dyn Shell> = Box::new(Bash {});
+        let crab_command =
+            run_command(command_vec, &*system_shell, DEFAULT_MAX_OUTPUT_BYTES, false).unwrap();
+        assert_eq!(crab_command.output.unwrap(), "caf\u{FFFD}");
+        assert!(crab_command.lossy_output);
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_run_command_with_huge_output() {
+        let command_vec = vec!["echo start; seq 100000; echo end".to_owned()];
+        let system_shell: Box<dyn Shell> = Box::new(Bash {});
+        let crab_command = run_command(command_vec, &*system_shell, 100, false).unwrap();
+        let output = crab_command.output.unwrap();
+        assert!(output.starts_with("start\n1\n2\n"));
+        assert!(output.contains("bytes omitted"));
+        assert!(output.ends_with("99999\n100000\nend\n"));
+        assert!(output.len() < 200);
+    }
+
+    #[rstest]
+    #[case(Some("out"), Some("err"), Some("out\nerr"))]
+    #[case(Some("out"), Some(""), Some("out"))]
+    #[case(Some(""), Some("err"), Some("err"))]
+    #[case(None, Some("err"), Some("err"))]
+    #[case(None, None, None)]
+    fn test_new_keeps_streams(
+        #[case] stdout: Option<&str>,
+        #[case] stderr: Option<&str>,
+        #[case] output: Option<&str>,
+    ) {
+        let command = CrabCommand::new(
+            "ls".to_owned(),
+            stdout.map(str::to_owned),
+            stderr.map(str::to_owned),
+        );
+        assert_eq!(command.stdout.as_deref(), stdout);
+        assert_eq!(command.stderr.as_deref(), stderr);
+        assert_eq!(command.output.as_deref(), output);
+    }
+
+    #[rstest]
+    #[case(Some(0), None, true)]
+    #[case(Some(1), None, false)]
+    #[case(None, Some(9), false)]
+    #[case(None, None, false)]
+    fn test_succeeded(
+        #[case] exit_code: Option<i32>,
+        #[case] signal: Option<i32>,
+        #[case] succeeded: bool,
+    ) {
+        let command =
+            CrabCommand::new("ls".to_owned(), None, None).with_exit_status(exit_code, signal);
+        assert_eq!(command.succeeded(), succeeded);
+    }
+
+    #[test]
+    fn test_print_skips_side_effect() {
+        let side_effect: SideEffect = Rc::new(|_, _, context: &dyn Context| {
+            context.remove_file(Path::new("notes.txt")).unwrap();
+        });
+        let corrected_command =
+            CorrectedCommand::new("mkdir -p notes".to_owned(), Some(side_effect), 1000);
+        let context = FakeContext::new().with_file("notes.txt", b"");
+        let old_command = CrabCommand::new("tar xf notes.tar".to_owned(), None, None);
+
+        corrected_command.print();
+        assert!(context.is_file(Path::new("notes.txt")));
+        corrected_command.run(old_command, &context);
+        assert!(!context.is_file(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn test_not_found() {
+        let command_vec = vec![
+            "gti".to_owned(),
+            "commit".to_owned(),
+            "-m".to_owned(),
+            "a message".to_owned(),
+        ];
+        let command = CrabCommand::not_found(command_vec, &Bash {});
+        assert_eq!(command.script, "gti commit -m 'a message'");
+        assert_eq!(
+            command.script_parts,
+            vec!["gti", "commit", "-m", "a message"]
+        );
+        assert_eq!(
+            command.output.as_deref(),
+            Some("bash: gti: command not found")
+        );
+        assert_eq!(command.exit_code, Some(127));
+    }
+
+    #[test]
+    fn test_update_keeps_streams_and_exit_status() {
+        let command = CrabCommand::new(
+            "sudo ls".to_owned(),
+            Some("out".to_owned()),
+            Some("err".to_owned()),
+        )
+        .with_exit_status(Some(2), None);
+
+        let updated = command.update(Some("ls".to_owned()), None, None);
+        assert_eq!(updated.script_parts, vec!["ls"]);
+        assert_eq!(updated.stdout.as_deref(), Some("out"));
+        assert_eq!(updated.stderr.as_deref(), Some("err"));
+        assert_eq!(updated.output.as_deref(), Some("out\nerr"));
+        assert_eq!(updated.exit_code, Some(2));
+
+        let updated = command.update(None, None, Some("new err".to_owned()));
+        assert_eq!(updated.script, "sudo ls");
+        assert_eq!(updated.stdout, None);
+        assert_eq!(updated.output.as_deref(), Some("new err"));
+        assert_eq!(updated.exit_code, Some(2));
     }
 }
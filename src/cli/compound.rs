@@ -0,0 +1,270 @@
+use regex::Regex;
+use std::ops::Range;
+
+use crate::cli::command::shlex_split;
+
+/// A simple command inside of a compound command line, e.g. `git status` in
+/// `cd repo && git status | less`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub script: String,
+    /// Position of the segment inside of the full command line.
+    pub range: Range<usize>,
+}
+
+impl Segment {
+    /// Replaces this segment of `full_script` with `new_segment`.
+    pub fn splice(&self, full_script: &str, new_segment: &str) -> String {
+        format!(
+            "{}{}{}",
+            &full_script[..self.range.start],
+            new_segment,
+            &full_script[self.range.end..]
+        )
+    }
+
+    /// Name of the program run by the segment, skipping variable assignments.
+    fn program(&self) -> Option<String> {
+        shlex_split(&self.script)
+            .into_iter()
+            .find(|word| !is_assignment(word))
+    }
+}
+
+/// Returns whether `word` assigns a variable for the command that follows, e.g.
+/// `LANG=C`. Variable names cannot start with a digit.
+pub fn is_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// Splits a command line into the simple commands that form its pipelines and lists.
+///
+/// Segments are separated by `|`, `|&`, `&&`, `||`, `;`, `&` and new lines, and the
+/// contents of subshells and groups (`( ... )`, `{ ...; }`) are split as well. Operators
+/// inside of quotes, command substitutions or redirections such as `2>&1` are ignored.
+pub fn split_segments(script: &str) -> Vec<Segment> {
+    let bytes = script.as_bytes();
+    let mut boundaries: Vec<Range<usize>> = vec![];
+    let mut start = 0;
+    let mut substitution_depth = 0;
+    let mut in_single_quotes = false;
+    let mut in_double_quotes = false;
+    let mut in_backticks = false;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if in_single_quotes {
+            in_single_quotes = byte != b'\'';
+            i += 1;
+            continue;
+        }
+        match byte {
+            b'\\' => i += 1,
+            b'"' => in_double_quotes = !in_double_quotes,
+            b'\'' if !in_double_quotes => in_single_quotes = true,
+            b'`' => in_backticks = !in_backticks,
+            b'$' if bytes.get(i + 1) == Some(&b'(') => {
+                substitution_depth += 1;
+                i += 1;
+            }
+            b'(' if substitution_depth > 0 => substitution_depth += 1,
+            b')' if substitution_depth > 0 => substitution_depth -= 1,
+            _ if in_double_quotes || in_backticks || substitution_depth > 0 => {}
+            b'(' if script[start..i].trim().is_empty() => {
+                boundaries.push(start..i);
+                start = i + 1;
+            }
+            b')' | b';' | b'\n' => {
+                boundaries.push(start..i);
+                start = i + 1;
+            }
+            b'|' => {
+                let len = match bytes.get(i + 1) {
+                    Some(b'|') | Some(b'&') => 2,
+                    _ => 1,
+                };
+                boundaries.push(start..i);
+                start = i + len;
+                i += len - 1;
+            }
+            b'&' => {
+                let is_redirection = matches!(bytes.get(i + 1), Some(b'>'))
+                    || (i > 0 && matches!(bytes[i - 1], b'>' | b'<'));
+                if !is_redirection {
+                    let len = if bytes.get(i + 1) == Some(&b'&') {
+                        2
+                    } else {
+                        1
+                    };
+                    boundaries.push(start..i);
+                    start = i + len;
+                    i += len - 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    boundaries.push(start..script.len().max(start));
+
+    boundaries
+        .into_iter()
+        .filter_map(|range| trim_segment(script, range))
+        .collect()
+}
+
+/// Removes the surrounding whitespace and group braces from a segment.
+fn trim_segment(script: &str, range: Range<usize>) -> Option<Segment> {
+    let mut text = &script[range.clone()];
+    let mut start = range.start;
+
+    let trimmed = text.trim_start();
+    start += text.len() - trimmed.len();
+    text = trimmed.trim_end();
+    if let Some(rest) = text.strip_prefix('{') {
+        if rest.starts_with(char::is_whitespace) {
+            let rest = rest.trim_start();
+            start += text.len() - rest.len();
+            text = rest;
+        }
+    }
+
+    if text.is_empty() || text == "}" {
+        return None;
+    }
+    Some(Segment {
+        script: text.to_owned(),
+        range: start..start + text.len(),
+    })
+}
+
+/// Returns the segment that most likely made the command fail.
+///
+/// Shells and most programs prefix their error messages with the name of the program,
+/// e.g. `bash: gti: command not found` or `grep: foo: No such file or directory`, so the
+/// last segment whose program is mentioned in that way is chosen.
+pub fn find_failed_segment<'a>(segments: &'a [Segment], output: &str) -> Option<&'a Segment> {
+    segments.iter().rev().find(|segment| {
+        segment
+            .program()
+            .is_some_and(|program| mentions_program(output, &program))
+    })
+}
+
+fn mentions_program(output: &str, program: &str) -> bool {
+    let re = Regex::new(&format!(
+        r#"(?m)(^|[\s:'"`]){}([:'"`]|$)"#,
+        regex::escape(program)
+    ))
+    .unwrap();
+    re.is_match(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_failed_segment, is_assignment, split_segments};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("git status", vec!["git status"])]
+    #[case("cd foo && gti status", vec!["cd foo", "gti status"])]
+    #[case("cat x | grpe y", vec!["cat x", "grpe y"])]
+    #[case("make || echo failed; ls", vec!["make", "echo failed", "ls"])]
+    #[case("cargo build |& less", vec!["cargo build", "less"])]
+    #[case("sleep 10 & gti log", vec!["sleep 10", "gti log"])]
+    #[case("(cd foo && gti status) | less", vec!["cd foo", "gti status", "less"])]
+    #[case("{ ls; gti status; }", vec!["ls", "gti status"])]
+    #[case("ls\ngti status", vec!["ls", "gti status"])]
+    #[case("git commit -m \"a; b && c\"", vec!["git commit -m \"a; b && c\""])]
+    #[case("echo 'a | b' | grep a", vec!["echo 'a | b'", "grep a"])]
+    #[case("echo $(ls | wc -l) && ls", vec!["echo $(ls | wc -l)", "ls"])]
+    #[case("echo `ls | wc -l`", vec!["echo `ls | wc -l`"])]
+    #[case("make 2>&1 | less", vec!["make 2>&1", "less"])]
+    #[case("make &> log.txt", vec!["make &> log.txt"])]
+    #[case("echo a\\;b", vec!["echo a\\;b"])]
+    #[case("", vec![])]
+    fn test_split_segments(#[case] script: &str, #[case] expected: Vec<&str>) {
+        let segments = split_segments(script);
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| segment.script.as_str())
+                .collect::<Vec<_>>(),
+            expected
+        );
+        for segment in segments {
+            assert_eq!(&script[segment.range.clone()], segment.script);
+        }
+    }
+
+    #[rstest]
+    #[case(
+        "cd foo && gti status",
+        "bash: gti: command not found",
+        Some("gti status")
+    )]
+    #[case("cat x | grpe y", "zsh: command not found: grpe", Some("grpe y"))]
+    #[case(
+        "cd foo && ls",
+        "bash: cd: foo: No such file or directory",
+        Some("cd foo")
+    )]
+    #[case(
+        "LANG=C make && ./run.sh",
+        "./run.sh: Permission denied",
+        Some("./run.sh")
+    )]
+    #[case("git pul | less", "git: 'pul' is not a git command.", Some("git pul"))]
+    #[case("cd foo && ls", "Something went wrong", None)]
+    #[case("cat x | catty", "catty", Some("catty"))]
+    fn test_find_failed_segment(
+        #[case] script: &str,
+        #[case] output: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let segments = split_segments(script);
+        assert_eq!(
+            find_failed_segment(&segments, output).map(|segment| segment.script.as_str()),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("cd foo && gti status", 1, "git status", "cd foo && git status")]
+    #[case(
+        "(cd foo && gti status) | less",
+        1,
+        "git status",
+        "(cd foo && git status) | less"
+    )]
+    #[case("{ ls; gti status; }", 1, "git status", "{ ls; git status; }")]
+    fn test_splice(
+        #[case] script: &str,
+        #[case] index: usize,
+        #[case] new_segment: &str,
+        #[case] expected: &str,
+    ) {
+        let segments = split_segments(script);
+        assert_eq!(segments[index].splice(script, new_segment), expected);
+    }
+
+    #[rstest]
+    #[case("LANG=C", true)]
+    #[case("_OPTS=-v", true)]
+    #[case("FOO=", true)]
+    #[case("1FOO=bar", false)]
+    #[case("=bar", false)]
+    #[case("--color=auto", false)]
+    #[case("make", false)]
+    fn test_is_assignment(#[case] word: &str, #[case] expected: bool) {
+        assert_eq!(is_assignment(word), expected);
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::shell::Shell;
+
+/// Comment written before the snippet added to the startup file of the shell, used to
+/// know whether ohcrab was already installed.
+const INSTALL_MARKER: &str = "# Added by `ohcrab init --install`";
+
+/// Key used by `--keybinding` when no key is given: Esc twice, in the notation understood
+/// by both readline and `bindkey`.
+pub const DEFAULT_KEYBINDING: &str = "\\e\\e";
+
+/// Returns the script that integrates ohcrab with the shell: the alias and, if requested,
+/// the function called when a command is not found and the key binding.
+pub fn get_integration_script(
+    system_shell: &dyn Shell,
+    alias_name: &str,
+    hook: bool,
+    keybinding: Option<&str>,
+) -> String {
+    let mut script = system_shell.app_alias(alias_name);
+    if hook {
+        if let Some(command_not_found_hook) = system_shell.command_not_found_hook() {
+            script.push_str(&command_not_found_hook);
+        }
+    }
+    if let Some(key) = keybinding {
+        if let Some(keybinding) = system_shell.keybinding(key) {
+            script.push_str(&keybinding);
+        }
+    }
+    script
+}
+
+/// Appends `snippet` to the startup file `rc_file`, unless it was already added.
+///
+/// Returns `true` if the file was changed. A missing file is created, but a file that
+/// cannot be read is left alone, since whether the snippet is already there is unknown.
+pub fn install_snippet(rc_file: &Path, snippet: &str) -> io::Result<bool> {
+    let content = match fs::read_to_string(rc_file) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error),
+    };
+    if content.lines().any(|line| line.trim() == INSTALL_MARKER) {
+        return Ok(false);
+    }
+
+    if let Some(parent) = rc_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(rc_file)?;
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    write!(file, "{separator}\n{INSTALL_MARKER}\n{snippet}\n")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_integration_script, install_snippet, INSTALL_MARKER};
+    use crate::shell::{Bash, Zsh};
+    use std::fs;
+
+    #[test]
+    fn test_get_integration_script() {
+        let script = get_integration_script(&Bash {}, "shinycrab", false, None);
+        assert!(script.contains("function shinycrab ()"));
+        assert!(!script.contains("command_not_found_handle"));
+        assert!(!script.contains("bind -x"));
+
+        let script = get_integration_script(&Zsh {}, "crab", true, Some("\\e\\e"));
+        assert!(script.contains("crab ()"));
+        assert!(script.contains("command_not_found_handler ()"));
+        assert!(script.contains("bindkey '\\e\\e' __ohcrab_keybinding"));
+    }
+
+    #[test]
+    fn test_install_snippet() {
+        let dir = tempfile::tempdir().unwrap();
+        let rc_file = dir.path().join(".bashrc");
+        fs::write(&rc_file, "export EDITOR=vim").unwrap();
+
+        assert!(install_snippet(&rc_file, "eval \"$(ohcrab init bash)\"").unwrap());
+        assert!(!install_snippet(&rc_file, "eval \"$(ohcrab init bash)\"").unwrap());
+        assert_eq!(
+            fs::read_to_string(&rc_file).unwrap(),
+            format!("export EDITOR=vim\n\n{INSTALL_MARKER}\neval \"$(ohcrab init bash)\"\n")
+        );
+    }
+
+    #[test]
+    fn test_install_snippet_creates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let rc_file = dir.path().join("zsh").join(".zshrc");
+
+        assert!(install_snippet(&rc_file, "eval \"$(ohcrab init zsh)\"").unwrap());
+        assert_eq!(
+            fs::read_to_string(&rc_file).unwrap(),
+            format!("\n{INSTALL_MARKER}\neval \"$(ohcrab init zsh)\"\n")
+        );
+    }
+
+    #[test]
+    fn test_install_snippet_unreadable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let rc_file = dir.path().join(".bashrc");
+        fs::write(&rc_file, b"export EDITOR=\xff").unwrap();
+
+        assert!(install_snippet(&rc_file, "eval \"$(ohcrab init bash)\"").is_err());
+        assert_eq!(fs::read(&rc_file).unwrap(), b"export EDITOR=\xff");
+    }
+}
@@ -1,2 +1,5 @@
 pub mod command;
+pub mod compound;
+pub mod init;
+pub mod output;
 pub mod parser;
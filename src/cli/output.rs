@@ -0,0 +1,223 @@
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::sync::OnceLock;
+
+/// Default maximum number of bytes captured from each output stream of a command.
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Text captured from an output stream of a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedOutput {
+    pub text: String,
+    /// `true` if invalid UTF-8 sequences were replaced while decoding.
+    pub lossy: bool,
+}
+
+/// Output stream of a command captured as it is read, keeping at most `max_bytes` bytes.
+///
+/// The first half of the budget is filled with the head of the stream and the rest keeps
+/// a rolling tail, since that is where error messages usually are. Everything in between
+/// is dropped and only counted.
+#[derive(Debug, Clone)]
+pub struct CappedOutput {
+    head: Vec<u8>,
+    tail: VecDeque<u8>,
+    head_capacity: usize,
+    tail_capacity: usize,
+    omitted: usize,
+}
+
+impl CappedOutput {
+    pub fn new(max_bytes: usize) -> Self {
+        CappedOutput {
+            head: Vec::new(),
+            tail: VecDeque::new(),
+            head_capacity: max_bytes / 2,
+            tail_capacity: max_bytes - max_bytes / 2,
+            omitted: 0,
+        }
+    }
+
+    /// Appends bytes read from the stream.
+    pub fn push(&mut self, mut bytes: &[u8]) {
+        let head_room = self.head_capacity - self.head.len();
+        if head_room > 0 {
+            let (head, rest) = bytes.split_at(head_room.min(bytes.len()));
+            self.head.extend_from_slice(head);
+            bytes = rest;
+        }
+        self.tail.extend(bytes);
+        if self.tail.len() > self.tail_capacity {
+            let excess = self.tail.len() - self.tail_capacity;
+            self.tail.drain(..excess);
+            self.omitted += excess;
+        }
+    }
+
+    /// Reads the stream until its end.
+    pub fn read_from<R: Read>(&mut self, mut reader: R) -> io::Result<()> {
+        let mut buffer = [0; 8192];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(read) => self.push(&buffer[..read]),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Decodes the captured output so that rules can match against it.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD` instead of discarding the whole
+    /// output. If bytes were dropped, a marker with their number is put between the head
+    /// and the tail, which are cut at character boundaries. ANSI escape sequences are
+    /// removed.
+    pub fn decode(self) -> DecodedOutput {
+        let mut tail = Vec::from(self.tail);
+        let (text, lossy) = if self.omitted > 0 {
+            let head_end = complete_prefix_len(&self.head);
+            let tail_start = tail
+                .iter()
+                .take_while(|&&byte| is_continuation(byte))
+                .count();
+            let omitted = self.omitted + self.head.len() - head_end + tail_start;
+            let head = String::from_utf8_lossy(&self.head[..head_end]);
+            let tail = String::from_utf8_lossy(&tail[tail_start..]);
+            let lossy = matches!(head, Cow::Owned(_)) || matches!(tail, Cow::Owned(_));
+            (
+                format!("{head}\n[... {omitted} bytes omitted ...]\n{tail}"),
+                lossy,
+            )
+        } else {
+            let mut bytes = self.head;
+            bytes.append(&mut tail);
+            let text = String::from_utf8_lossy(&bytes);
+            let lossy = matches!(text, Cow::Owned(_));
+            (text.into_owned(), lossy)
+        };
+
+        DecodedOutput {
+            text: strip_ansi(&text).into_owned(),
+            lossy,
+        }
+    }
+}
+
+/// Decodes the raw output of a command so that rules can match against it, keeping at
+/// most `max_bytes` bytes as `CappedOutput` does.
+pub fn decode_output(bytes: &[u8], max_bytes: usize) -> DecodedOutput {
+    let mut output = CappedOutput::new(max_bytes);
+    output.push(bytes);
+    output.decode()
+}
+
+fn is_continuation(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// Returns the length of `bytes` without the UTF-8 character cut in half at its end, if
+/// any.
+fn complete_prefix_len(bytes: &[u8]) -> usize {
+    let Some(start) = bytes.iter().rposition(|&byte| !is_continuation(byte)) else {
+        return bytes.len();
+    };
+    let char_len = match bytes[start] {
+        byte if byte >= 0b1111_0000 => 4,
+        byte if byte >= 0b1110_0000 => 3,
+        byte if byte >= 0b1100_0000 => 2,
+        _ => 1,
+    };
+    if start + char_len > bytes.len() {
+        start
+    } else {
+        bytes.len()
+    }
+}
+
+/// Removes ANSI escape sequences (colors, cursor movements, hyperlinks...) from `text`.
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    static ANSI_RE: OnceLock<Regex> = OnceLock::new();
+    let re = ANSI_RE.get_or_init(|| {
+        Regex::new(
+            r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]|\x9b[0-?]*[ -/]*[@-~]",
+        )
+        .unwrap()
+    });
+    re.replace_all(text, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_output, strip_ansi, CappedOutput, DecodedOutput};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("plain text", "plain text")]
+    #[case("\x1b[31merror\x1b[0m: oops", "error: oops")]
+    #[case("\x1b[1;38;5;196mbold\x1b[m", "bold")]
+    #[case("\x1b[2K\x1b[1Gprogress", "progress")]
+    #[case("\x1b]8;;https://crab.rs\x07link\x1b]8;;\x07", "link")]
+    #[case("\x1b]0;title\x1b\\text", "text")]
+    fn test_strip_ansi(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(strip_ansi(text), expected);
+    }
+
+    #[rstest]
+    #[case(b"hello", "hello", false)]
+    #[case(b"caf\xc3\xa9", "café", false)]
+    #[case(b"caf\xe9: erreur", "caf\u{FFFD}: erreur", true)]
+    #[case(b"\x1b[31m\xff\x1b[0m", "\u{FFFD}", true)]
+    fn test_decode_output(#[case] bytes: &[u8], #[case] text: &str, #[case] lossy: bool) {
+        assert_eq!(
+            decode_output(bytes, 1024),
+            DecodedOutput {
+                text: text.to_owned(),
+                lossy
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_output_keeps_head_and_tail() {
+        let bytes = b"first line\nnoise noise noise noise\nlast line";
+        let output = decode_output(bytes, 20);
+        assert_eq!(
+            output.text,
+            "first line\n[... 24 bytes omitted ...]\n\nlast line"
+        );
+        assert!(!output.lossy);
+    }
+
+    #[test]
+    fn test_decode_output_does_not_split_characters() {
+        let bytes = "ééééé".as_bytes();
+        let output = decode_output(bytes, 5);
+        assert_eq!(output.text, "é\n[... 6 bytes omitted ...]\né");
+        assert!(!output.lossy);
+    }
+
+    #[test]
+    fn test_capped_output_keeps_head_and_rolling_tail() {
+        let mut output = CappedOutput::new(10);
+        for line in ["start\n", "noise\n", "noise\n", "noise\n", "end\n"] {
+            output.push(line.as_bytes());
+        }
+        // Only the head and the tail are kept while reading
+        assert_eq!(output.head.len() + output.tail.len(), 10);
+        assert_eq!(output.omitted, 18);
+        assert_eq!(
+            output.decode().text,
+            "start\n[... 18 bytes omitted ...]\n\nend\n"
+        );
+    }
+
+    #[test]
+    fn test_capped_output_reads_stream() {
+        let mut output = CappedOutput::new(1024);
+        output.read_from("line\n".repeat(3).as_bytes()).unwrap();
+        assert_eq!(output.decode().text, "line\nline\nline\n");
+    }
+}
@@ -1,8 +1,12 @@
 // FILE: ./src/cli/parser.rs
-use clap::{command, Arg, ArgAction};
+use clap::{builder::FalseyValueParser, command, Arg, ArgAction};
 use std::env;
 
-use crate::{ARGUMENT_PLACEHOLDER, ENV_VAR_NAME_ALIAS, ENV_VAR_NAME_HISTORY, ENV_VAR_NAME_SHELL};
+use crate::cli::init::DEFAULT_KEYBINDING;
+use crate::{
+    ARGUMENT_PLACEHOLDER, ENV_VAR_NAME_ALIAS, ENV_VAR_NAME_HISTORY, ENV_VAR_NAME_KEEP_LOCALE,
+    ENV_VAR_NAME_MAX_OUTPUT_BYTES, ENV_VAR_NAME_OFFER_FIX, ENV_VAR_NAME_SHELL,
+};
 
 /// Prepares arguments by:
 /// - Removing placeholder and moving arguments after it to beginning, we need this
@@ -21,7 +25,7 @@ pub fn prepare_arguments(mut argv: Vec<String>) -> Vec<String> {
         processed_args.append(&mut argv);
         processed_args
     } else {
-        if !argv.is_empty() && !argv[0].starts_with('-') && argv[0] != "--" {
+        if !argv.is_empty() && !argv[0].starts_with('-') && argv[0] != "--" && argv[0] != "init" {
             argv.insert(0, "--".to_owned());
         }
         argv
@@ -35,6 +39,7 @@ pub fn get_parser() -> clap::Command {
         .arg(
             Arg::new("alias")
                 .long("alias")
+                .global(true)
                 .short('a')
                 .help("Prints the shell function using the given alias")
                 .required(false)
@@ -66,6 +71,86 @@ pub fn get_parser() -> clap::Command {
                 .action(ArgAction::SetTrue)
                 .required(false),
         )
+        .arg(
+            Arg::new("max-output-bytes")
+                .long("max-output-bytes")
+                .help("Maximum number of bytes captured from each output stream of the command [default: 1 MiB]")
+                .env(ENV_VAR_NAME_MAX_OUTPUT_BYTES)
+                .value_parser(clap::value_parser!(usize))
+                .required(false),
+        )
+        .arg(
+            Arg::new("keep-locale")
+                .long("keep-locale")
+                .help("Run the command with the user's locale instead of forcing the C locale")
+                .env(ENV_VAR_NAME_KEEP_LOCALE)
+                .value_parser(FalseyValueParser::new())
+                .action(ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("buffer")
+                .long("buffer")
+                .help("Correct a command line that was not run yet, e.g. the one being typed, without running it")
+                .action(ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("command-not-found")
+                .long("command-not-found")
+                .help("Suggest corrections for a command the shell could not find, without running it")
+                .action(ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("offer-fix")
+                .long("offer-fix")
+                .help("Offer to run the correction when a command is not found")
+                .env(ENV_VAR_NAME_OFFER_FIX)
+                .value_parser(FalseyValueParser::new())
+                .action(ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("hook")
+                .long("hook")
+                .help("Also print the shell hook called when a command is not found")
+                .action(ArgAction::SetTrue)
+                .required(false),
+        )
+        .arg(
+            Arg::new("keybinding")
+                .long("keybinding")
+                .global(true)
+                .value_name("KEY")
+                .help("Also print a key binding that puts the correction of the last command, or of the one being typed, in the command line")
+                .num_args(0..=1)
+                .default_missing_value(DEFAULT_KEYBINDING)
+                .required(false),
+        )
+        .subcommand(
+            clap::Command::new("init")
+                .about("Prints the shell integration script, detecting the shell if not given")
+                .arg(
+                    Arg::new("shell")
+                        .help("Shell to integrate with (detected from the parent processes or $SHELL by default)")
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("hook")
+                        .long("hook")
+                        .help("Include the hook called when a command is not found")
+                        .action(ArgAction::SetTrue)
+                        .required(false),
+                )
+                .arg(
+                    Arg::new("install")
+                        .long("install")
+                        .help("Add the integration to the startup file of the shell instead of printing it")
+                        .action(ArgAction::SetTrue)
+                        .required(false),
+                ),
+        )
         .arg(
             Arg::new("command")
                 .help("Command that should be fixed")
@@ -159,6 +244,28 @@ mod tests {
             Some(&"pws".to_string())
         );
 
+        // Test maximum output size
+        assert_eq!(
+            get_parser()
+                .get_matches_from(Vec::<String>::new())
+                .get_one::<usize>("max-output-bytes"),
+            None
+        );
+        assert_eq!(
+            get_parser()
+                .get_matches_from(vec!["--max-output-bytes", "4096"])
+                .get_one::<usize>("max-output-bytes"),
+            Some(&4096)
+        );
+
+        // Test keep locale flag
+        assert!(!get_parser()
+            .get_matches_from(Vec::<String>::new())
+            .get_flag("keep-locale"));
+        assert!(get_parser()
+            .get_matches_from(vec!["--keep-locale"])
+            .get_flag("keep-locale"));
+
         // Test debug flag
         assert!(get_parser()
             .get_matches_from(vec!["-d", "--", "anything"])
@@ -190,6 +297,60 @@ mod tests {
         env::remove_var(ENV_VAR_NAME_HISTORY);
     }
 
+    #[test]
+    fn test_parser_init_subcommand() {
+        let matches = get_parser().get_matches_from(prepare_arguments(vec![
+            "init".to_owned(),
+            "zsh".to_owned(),
+            "--hook".to_owned(),
+            "--alias".to_owned(),
+            "shinycrab".to_owned(),
+        ]));
+        let (name, init_matches) = matches.subcommand().unwrap();
+        assert_eq!(name, "init");
+        assert_eq!(
+            init_matches.get_one::<String>("shell"),
+            Some(&"zsh".to_owned())
+        );
+        assert!(init_matches.get_flag("hook"));
+        assert!(!init_matches.get_flag("install"));
+        assert_eq!(
+            init_matches.get_one::<String>("alias"),
+            Some(&"shinycrab".to_owned())
+        );
+
+        let matches = get_parser().get_matches_from(prepare_arguments(vec![
+            "init".to_owned(),
+            "bash".to_owned(),
+            "--keybinding".to_owned(),
+        ]));
+        let (_, init_matches) = matches.subcommand().unwrap();
+        assert_eq!(
+            init_matches.get_one::<String>("keybinding"),
+            Some(&"\\e\\e".to_owned())
+        );
+
+        let matches = get_parser().get_matches_from(prepare_arguments(vec![
+            "init".to_owned(),
+            "--keybinding".to_owned(),
+            "\\C-x\\C-x".to_owned(),
+        ]));
+        let (_, init_matches) = matches.subcommand().unwrap();
+        assert_eq!(init_matches.get_one::<String>("shell"), None);
+        assert_eq!(
+            init_matches.get_one::<String>("keybinding"),
+            Some(&"\\C-x\\C-x".to_owned())
+        );
+
+        let matches = get_parser().get_matches_from(prepare_arguments(vec!["init".to_owned()]));
+        let (_, init_matches) = matches.subcommand().unwrap();
+        assert_eq!(init_matches.get_one::<String>("shell"), None);
+
+        // A command to fix is never taken as the subcommand
+        let matches = get_parser().get_matches_from(vec!["--", "init"]);
+        assert!(matches.subcommand().is_none());
+    }
+
     #[test]
     fn test_prepare_arguments() {
         for (input, exp_output) in [
@@ -0,0 +1,432 @@
+use is_executable::IsExecutable;
+use std::env;
+use std::fs;
+use std::io;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::command::force_c_locale;
+use crate::utils::get_all_executable;
+
+/// Output of a helper process spawned by a rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub success: bool,
+}
+
+/// Access to the host system used by the rules.
+///
+/// Rules never spawn processes, look up executables, read environment variables or touch
+/// the filesystem directly. They go through this trait instead, so tests can provide an
+/// in-memory implementation rather than relying on the state of the machine.
+pub trait Context {
+    /// Runs `program` with the given arguments and returns its output, or `None` if the
+    /// process could not be started.
+    fn run(&self, program: &str, args: &[&str]) -> Option<ProcessOutput>;
+
+    /// Returns the path of `program` if it can be found in the `PATH`.
+    fn which(&self, program: &str) -> Option<PathBuf>;
+
+    /// Returns the names of all executables available in the `PATH`.
+    fn executables(&self) -> Vec<String>;
+
+    fn env_var(&self, key: &str) -> Option<String>;
+
+    fn current_dir(&self) -> PathBuf;
+
+    fn home_dir(&self) -> Option<PathBuf>;
+
+    /// Returns the directory where applications keep their configuration, e.g.
+    /// `~/.config` on Linux.
+    fn config_dir(&self) -> Option<PathBuf>;
+
+    fn exists(&self, path: &Path) -> bool;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+
+    fn is_executable(&self, path: &Path) -> bool;
+
+    /// Returns the paths of the entries inside of the directory `path`.
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf>;
+
+    fn read(&self, path: &Path) -> Option<Vec<u8>>;
+
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    /// Returns whether the TCP `port` can be bound, i.e. nothing is listening on it yet.
+    fn is_port_available(&self, port: u16) -> bool;
+
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        self.read(path)
+            .map(|content| String::from_utf8_lossy(&content).into_owned())
+    }
+}
+
+/// `Context` backed by the real system.
+///
+/// Helper processes run with the C locale, like the command being corrected, unless
+/// `keep_locale` is set.
+#[derive(Default)]
+pub struct SystemContext {
+    pub keep_locale: bool,
+}
+
+impl Context for SystemContext {
+    fn run(&self, program: &str, args: &[&str]) -> Option<ProcessOutput> {
+        let mut cmd = Command::new(program);
+        if !self.keep_locale {
+            force_c_locale(&mut cmd);
+        }
+        cmd.args(args).output().ok().map(|output| ProcessOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            success: output.status.success(),
+        })
+    }
+
+    fn which(&self, program: &str) -> Option<PathBuf> {
+        which::which(program).ok()
+    }
+
+    fn executables(&self) -> Vec<String> {
+        get_all_executable()
+    }
+
+    fn env_var(&self, key: &str) -> Option<String> {
+        env::var(key).ok()
+    }
+
+    fn current_dir(&self) -> PathBuf {
+        env::current_dir().unwrap_or_default()
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        dirs::home_dir()
+    }
+
+    fn config_dir(&self) -> Option<PathBuf> {
+        dirs::config_dir()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn is_executable(&self, path: &Path) -> bool {
+        path.is_executable()
+    }
+
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        match fs::read_dir(path) {
+            Ok(entries) => entries.flatten().map(|entry| entry.path()).collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        fs::read(path).ok()
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn is_port_available(&self, port: u16) -> bool {
+        TcpListener::bind(("0.0.0.0", port)).is_ok()
+    }
+}
+
+#[cfg(test)]
+pub use fake::FakeContext;
+
+#[cfg(test)]
+mod fake {
+    use super::{Context, ProcessOutput};
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::io;
+    use std::path::{Component, Path, PathBuf};
+
+    /// In-memory `Context` used by the tests.
+    ///
+    /// Relative paths are resolved against the fake current directory, which defaults to
+    /// `/home/crab`, the fake home directory.
+    pub struct FakeContext {
+        current_dir: PathBuf,
+        programs: BTreeMap<String, PathBuf>,
+        commands: HashMap<String, ProcessOutput>,
+        env: HashMap<String, String>,
+        files: RefCell<BTreeMap<PathBuf, Vec<u8>>>,
+        dirs: BTreeSet<PathBuf>,
+        executable_files: BTreeSet<PathBuf>,
+        used_ports: BTreeSet<u16>,
+    }
+
+    impl Default for FakeContext {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl FakeContext {
+        pub fn new() -> Self {
+            let context = Self {
+                current_dir: PathBuf::new(),
+                programs: BTreeMap::new(),
+                commands: HashMap::new(),
+                env: HashMap::new(),
+                files: RefCell::new(BTreeMap::new()),
+                dirs: BTreeSet::new(),
+                executable_files: BTreeSet::new(),
+                used_ports: BTreeSet::new(),
+            };
+            context.with_current_dir("/home/crab")
+        }
+
+        pub fn with_current_dir(mut self, path: &str) -> Self {
+            self.current_dir = PathBuf::from(path);
+            self.with_dir(path)
+        }
+
+        /// Registers a program available in the `PATH`.
+        pub fn with_program(mut self, name: &str) -> Self {
+            self.programs
+                .insert(name.to_owned(), Path::new("/usr/bin").join(name));
+            self
+        }
+
+        /// Registers the stdout returned when `command_line` (program and arguments
+        /// separated by spaces) is run.
+        pub fn with_command_output(mut self, command_line: &str, stdout: &str) -> Self {
+            self.commands.insert(
+                command_line.to_owned(),
+                ProcessOutput {
+                    stdout: stdout.to_owned(),
+                    stderr: "".to_owned(),
+                    success: true,
+                },
+            );
+            self
+        }
+
+        pub fn with_env(mut self, key: &str, value: &str) -> Self {
+            self.env.insert(key.to_owned(), value.to_owned());
+            self
+        }
+
+        pub fn with_dir(mut self, path: &str) -> Self {
+            let path = self.resolve(Path::new(path));
+            for ancestor in path.ancestors() {
+                self.dirs.insert(ancestor.to_path_buf());
+            }
+            self
+        }
+
+        pub fn with_file(mut self, path: &str, content: &[u8]) -> Self {
+            let path = self.resolve(Path::new(path));
+            if let Some(parent) = path.parent() {
+                for ancestor in parent.ancestors() {
+                    self.dirs.insert(ancestor.to_path_buf());
+                }
+            }
+            self.files.borrow_mut().insert(path, content.to_vec());
+            self
+        }
+
+        pub fn with_executable_file(mut self, path: &str) -> Self {
+            self = self.with_file(path, b"");
+            let path = self.resolve(Path::new(path));
+            self.executable_files.insert(path);
+            self
+        }
+
+        /// Registers a TCP port something is already listening on.
+        pub fn with_used_port(mut self, port: u16) -> Self {
+            self.used_ports.insert(port);
+            self
+        }
+
+        /// Returns the paths of all files, relative to the current directory when possible.
+        pub fn files(&self) -> Vec<PathBuf> {
+            self.files
+                .borrow()
+                .keys()
+                .map(|path| {
+                    path.strip_prefix(&self.current_dir)
+                        .map(|p| p.to_path_buf())
+                        .unwrap_or(path.to_path_buf())
+                })
+                .collect()
+        }
+
+        fn resolve(&self, path: &Path) -> PathBuf {
+            let mut resolved = PathBuf::new();
+            for component in self.current_dir.join(path).components() {
+                match component {
+                    Component::ParentDir => {
+                        resolved.pop();
+                    }
+                    Component::CurDir => {}
+                    other => resolved.push(other),
+                }
+            }
+            resolved
+        }
+    }
+
+    impl Context for FakeContext {
+        fn run(&self, program: &str, args: &[&str]) -> Option<ProcessOutput> {
+            let mut command_line = vec![program];
+            command_line.extend(args);
+            self.commands.get(&command_line.join(" ")).cloned()
+        }
+
+        fn which(&self, program: &str) -> Option<PathBuf> {
+            self.programs.get(program).cloned()
+        }
+
+        fn executables(&self) -> Vec<String> {
+            self.programs.keys().cloned().collect()
+        }
+
+        fn env_var(&self, key: &str) -> Option<String> {
+            self.env.get(key).cloned()
+        }
+
+        fn current_dir(&self) -> PathBuf {
+            self.current_dir.clone()
+        }
+
+        fn home_dir(&self) -> Option<PathBuf> {
+            Some(PathBuf::from("/home/crab"))
+        }
+
+        fn config_dir(&self) -> Option<PathBuf> {
+            self.home_dir().map(|home| home.join(".config"))
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.is_file(path) || self.is_dir(path)
+        }
+
+        fn is_file(&self, path: &Path) -> bool {
+            self.files.borrow().contains_key(&self.resolve(path))
+        }
+
+        fn is_dir(&self, path: &Path) -> bool {
+            self.dirs.contains(&self.resolve(path))
+        }
+
+        fn is_executable(&self, path: &Path) -> bool {
+            self.executable_files.contains(&self.resolve(path))
+        }
+
+        fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+            let dir = self.resolve(path);
+            let files = self.files.borrow();
+            self.dirs
+                .iter()
+                .chain(files.keys())
+                .filter(|entry| entry.parent() == Some(dir.as_path()))
+                .cloned()
+                .collect()
+        }
+
+        fn read(&self, path: &Path) -> Option<Vec<u8>> {
+            self.files.borrow().get(&self.resolve(path)).cloned()
+        }
+
+        fn remove_file(&self, path: &Path) -> io::Result<()> {
+            match self.files.borrow_mut().remove(&self.resolve(path)) {
+                Some(_) => Ok(()),
+                None => Err(io::Error::from(io::ErrorKind::NotFound)),
+            }
+        }
+
+        fn is_port_available(&self, port: u16) -> bool {
+            !self.used_ports.contains(&port)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Context, FakeContext, SystemContext};
+    use std::path::{Path, PathBuf};
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_system_context_forces_c_locale() {
+        let context = SystemContext::default();
+        let output = context.run("sh", &["-c", "echo $LC_ALL $LANG"]).unwrap();
+        assert_eq!(output.stdout, "C C\n");
+    }
+
+    #[test]
+    fn test_fake_context_filesystem() {
+        let context = FakeContext::new()
+            .with_current_dir("/tmp/project")
+            .with_file("src/main.rs", b"fn main() {}")
+            .with_dir("target");
+
+        assert!(context.is_dir(Path::new("src")));
+        assert!(context.is_dir(Path::new("/tmp")));
+        assert!(context.is_file(Path::new("./src/main.rs")));
+        assert!(context.exists(Path::new("/tmp/project/target")));
+        assert!(!context.exists(Path::new("README.md")));
+        assert_eq!(
+            context.read_to_string(Path::new("src/main.rs")),
+            Some("fn main() {}".to_owned())
+        );
+
+        let mut entries = context.read_dir(Path::new("."));
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from("/tmp/project/src"),
+                PathBuf::from("/tmp/project/target")
+            ]
+        );
+
+        context.remove_file(Path::new("src/main.rs")).unwrap();
+        assert!(!context.exists(Path::new("src/main.rs")));
+        assert!(context.remove_file(Path::new("src/main.rs")).is_err());
+    }
+
+    #[test]
+    fn test_fake_context_processes() {
+        let context = FakeContext::new()
+            .with_program("git")
+            .with_command_output("git branch", "* main")
+            .with_env("EDITOR", "vim");
+
+        assert_eq!(context.which("git"), Some(PathBuf::from("/usr/bin/git")));
+        assert_eq!(context.which("npm"), None);
+        assert_eq!(context.executables(), vec!["git"]);
+        assert_eq!(context.run("git", &["branch"]).unwrap().stdout, "* main");
+        assert!(context.run("git", &["status"]).is_none());
+        assert_eq!(context.env_var("EDITOR"), Some("vim".to_owned()));
+    }
+
+    #[test]
+    fn test_fake_context_ports() {
+        let context = FakeContext::new().with_used_port(8080);
+
+        assert!(!context.is_port_available(8080));
+        assert!(context.is_port_available(8081));
+    }
+}
@@ -5,18 +5,24 @@
 #![allow(clippy::type_complexity)]
 
 pub mod cli;
+pub mod context;
 pub mod rules;
 pub mod shell;
 mod ui;
 mod utils;
 
+use clap::{parser::ValueSource, ArgMatches};
 use cli::{
-    command::run_command,
+    command::{run_command, CrabCommand},
+    init::{get_integration_script, install_snippet},
+    output::DEFAULT_MAX_OUTPUT_BYTES,
     parser::{get_parser, prepare_arguments},
 };
-use rules::get_corrected_commands;
-use shell::{get_bash_type, Shell};
-use std::env;
+use context::SystemContext;
+use rules::{get_buffer_corrections, get_command_not_found_corrections, get_corrected_commands};
+use shell::{detect::detect_shell, get_bash_type, Shell};
+use std::path::Path;
+use std::{env, process};
 
 use crate::{ui::interactive_menu, utils::debug_log};
 
@@ -24,20 +30,42 @@ const ARGUMENT_PLACEHOLDER: &str = "OHCRAB_ARGUMENT_PLACEHOLDER";
 const ENV_VAR_NAME_HISTORY: &str = "OHCRAB_COMMAND_HISTORY";
 const ENV_VAR_NAME_ALIAS: &str = "OHCRAB_ALIAS";
 const ENV_VAR_NAME_SHELL: &str = "OHCRAB_SHELL";
+const ENV_VAR_NAME_MAX_OUTPUT_BYTES: &str = "OHCRAB_MAX_OUTPUT_BYTES";
+const ENV_VAR_NAME_KEEP_LOCALE: &str = "OHCRAB_KEEP_LOCALE";
+const ENV_VAR_NAME_PRIVILEGE_COMMAND: &str = "OHCRAB_PRIVILEGE_COMMAND";
+const ENV_VAR_NAME_WRAPPERS: &str = "OHCRAB_WRAPPERS";
+const ENV_VAR_NAME_OFFER_FIX: &str = "OHCRAB_OFFER_FIX";
 
 /// Handles the command correction logic.
+///
+/// If `buffer` is set, the command is not run, only the rules that can decide from the
+/// script alone are used and the selected correction is printed without running its side
+/// effect.
 fn handle_command_correction(
     command_values: clap::parser::Values<String>,
     system_shell: &dyn Shell,
     select_first: bool,
+    buffer: bool,
+    max_output_bytes: usize,
+    keep_locale: bool,
 ) {
+    let context = SystemContext { keep_locale };
     let command_vec: Vec<String> = command_values.collect();
     debug_log(&format!("Retrieved command(s): {command_vec:?}"));
 
-    let mut crab_command = run_command(command_vec, system_shell);
+    let mut crab_command = if buffer {
+        CrabCommand::from_buffer(command_vec)
+    } else {
+        run_command(command_vec, system_shell, max_output_bytes, keep_locale)
+            .unwrap_or_else(|error| exit_with_error(&error))
+    };
     debug_log(&format!("Crab command: {crab_command:?}"));
 
-    let corrected_commands = get_corrected_commands(&mut crab_command, system_shell);
+    let corrected_commands = if buffer {
+        get_buffer_corrections(&mut crab_command, system_shell, &context)
+    } else {
+        get_corrected_commands(&mut crab_command, system_shell, &context)
+    };
     debug_log(&format!(
         "Candidate command(s): {:?}",
         corrected_commands
@@ -58,8 +86,111 @@ fn handle_command_correction(
             eprintln!();
         }
         debug_log(&format!("Command selected: {valid_command:?}"));
-        valid_command.run(crab_command);
+        if buffer {
+            // The correction only replaces the line being edited, which the user may still
+            // change or drop, so the side effects of the rule are not run
+            valid_command.print();
+        } else {
+            valid_command.run(crab_command, &context);
+        }
+    }
+}
+
+/// Handles a command reported by the shell "command not found" hook.
+///
+/// Suggestions are printed to stderr. If `offer_fix` is set, the user can select one of
+/// them, which is printed to stdout for the hook to run.
+fn handle_command_not_found(
+    command_values: clap::parser::Values<String>,
+    system_shell: &dyn Shell,
+    select_first: bool,
+    offer_fix: bool,
+    keep_locale: bool,
+) {
+    let context = SystemContext { keep_locale };
+    let mut crab_command = CrabCommand::not_found(command_values.collect(), system_shell);
+    debug_log(&format!("Crab command: {crab_command:?}"));
+
+    let corrected_commands =
+        get_command_not_found_corrections(&mut crab_command, system_shell, &context);
+    if corrected_commands.is_empty() {
+        return;
+    }
+
+    if !offer_fix {
+        if let [corrected_command] = corrected_commands.as_slice() {
+            eprintln!("Did you mean `{}`?", corrected_command.script);
+        } else {
+            eprintln!("Did you mean one of these?");
+            for corrected_command in &corrected_commands {
+                eprintln!("    {}", corrected_command.script);
+            }
+        }
+        return;
+    }
+
+    let selected_command = if select_first {
+        corrected_commands.first()
+    } else {
+        interactive_menu(&corrected_commands)
+    };
+    if let Some(valid_command) = selected_command {
+        if !select_first {
+            eprintln!();
+        }
+        valid_command.run(crab_command, &context);
+    }
+}
+
+/// Handles `ohcrab init`, printing or installing the shell integration script.
+fn handle_init(init_matches: &ArgMatches) -> Result<(), String> {
+    let context = SystemContext::default();
+    let shell_name = match init_matches.get_one::<String>("shell") {
+        Some(shell_name) => shell_name.to_owned(),
+        None => detect_shell(&context, process::id()).ok_or(
+            "Could not detect your shell, please pass it explicitly, e.g. `ohcrab init bash`",
+        )?,
+    };
+    let system_shell = get_bash_type(&shell_name)?;
+    let alias_name = init_matches.get_one::<String>("alias").unwrap();
+    let hook = init_matches.get_flag("hook");
+    let keybinding = init_matches.get_one::<String>("keybinding");
+
+    let script = get_integration_script(
+        &*system_shell,
+        alias_name,
+        hook,
+        keybinding.map(|key| key.as_str()),
+    );
+    if !init_matches.get_flag("install") {
+        println!("{script}");
+        return Ok(());
     }
+
+    let mut init_arguments = String::new();
+    if init_matches.value_source("alias") == Some(ValueSource::CommandLine) {
+        init_arguments.push_str(&format!(" --alias {alias_name}"));
+    }
+    if hook {
+        init_arguments.push_str(" --hook");
+    }
+    if let Some(key) = keybinding {
+        init_arguments.push_str(&format!(" --keybinding {}", system_shell.quote(key)));
+    }
+    let rc_file = system_shell.get_rc_file_name(&context);
+    let snippet = system_shell.init_snippet(&init_arguments, &script);
+    match install_snippet(Path::new(&rc_file), &snippet) {
+        Ok(true) => eprintln!("ohcrab was added to {rc_file}, restart your shell to use it"),
+        Ok(false) => eprintln!("ohcrab is already set up in {rc_file}"),
+        Err(error) => return Err(format!("Could not write to {rc_file}: {error}")),
+    }
+    Ok(())
+}
+
+/// Prints the error and exits with a non-zero status.
+fn exit_with_error(error: &str) -> ! {
+    eprintln!("ohcrab: {error}");
+    process::exit(1);
 }
 
 fn main() {
@@ -67,13 +198,53 @@ fn main() {
     let args: Vec<String> = env::args().skip(1).collect();
     let args = prepare_arguments(args);
     let mut arg_matches = get_parser().get_matches_from(args);
-    let system_shell = get_bash_type(&arg_matches.remove_one::<String>("shell").unwrap());
+    if let Some(("init", init_matches)) = arg_matches.subcommand() {
+        if let Err(error) = handle_init(init_matches) {
+            exit_with_error(&error);
+        }
+        return;
+    }
+
+    let system_shell = get_bash_type(&arg_matches.remove_one::<String>("shell").unwrap())
+        .unwrap_or_else(|error| exit_with_error(&error));
     let select_first = arg_matches.get_flag("select-first");
+    let max_output_bytes = arg_matches
+        .get_one::<usize>("max-output-bytes")
+        .copied()
+        .unwrap_or(DEFAULT_MAX_OUTPUT_BYTES);
+    let keep_locale = arg_matches.get_flag("keep-locale");
 
-    if let Some(command) = arg_matches.remove_many::<String>("command") {
-        handle_command_correction(command, &*system_shell, select_first);
+    if arg_matches.get_flag("command-not-found") {
+        if let Some(command) = arg_matches.remove_many::<String>("command") {
+            handle_command_not_found(
+                command,
+                &*system_shell,
+                select_first,
+                arg_matches.get_flag("offer-fix"),
+                keep_locale,
+            );
+        }
+    } else if let Some(command) = arg_matches.remove_many::<String>("command") {
+        handle_command_correction(
+            command,
+            &*system_shell,
+            select_first,
+            arg_matches.get_flag("buffer"),
+            max_output_bytes,
+            keep_locale,
+        );
     } else {
         let alias_name = arg_matches.get_one::<String>("alias").unwrap();
-        println!("{}", system_shell.app_alias(alias_name));
+        println!(
+            "{}",
+            get_integration_script(
+                &*system_shell,
+                alias_name,
+                arg_matches.get_flag("hook"),
+                arg_matches
+                    .get_one::<String>("keybinding")
+                    .map(|key| key.as_str())
+            )
+        );
     }
 }
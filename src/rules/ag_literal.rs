@@ -1,6 +1,6 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 
-use super::Rule;
+use super::BuiltinRule;
 
 pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
     if let Some(output) = &command.output {
@@ -14,8 +14,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     vec![command.script.replacen("ag", "ag -Q", 1)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "ag_literal".to_owned(),
         None,
         None,
@@ -0,0 +1,108 @@
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+/// Returns the packages of the command that do not exist, listed after
+/// `unable to select packages:` as `<name> (no such package):`.
+///
+/// Missing dependencies of other packages are listed too, but cannot be typos.
+fn get_missing_packages(command: &CrabCommand, output: &str) -> Vec<String> {
+    let re = Regex::new(r"(?m)^\s+(\S+) \(no such package\):").unwrap();
+    re.captures_iter(output)
+        .map(|caps| caps[1].to_owned())
+        .filter(|package| command.script_parts.contains(package))
+        .collect()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("unable to select packages")
+            && !get_missing_packages(command, output).is_empty()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["apk"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(output) = &command.output else {
+        return vec![];
+    };
+    let Some(package) = get_missing_packages(command, output).into_iter().next() else {
+        return vec![];
+    };
+    let packages = context
+        .run("apk", &["search", "-q"])
+        .map(|output| output.stdout)
+        .unwrap_or_default();
+    replace_command(command, &package, packages.lines().collect())
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "apk_unable_to_select".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NO_SUCH_PACKAGE: &str = "ERROR: unable to select packages:
+  curll (no such package):
+    required by: world[curll]";
+    const CONFLICT: &str = "ERROR: unable to select packages:
+  so:libcrypto.so.3 (no such package):
+    required by: openssl-3.1.4-r5[so:libcrypto.so.3]";
+
+    #[rstest]
+    #[case("apk add curll", NO_SUCH_PACKAGE, true)]
+    #[case("sudo apk add --no-cache curll", NO_SUCH_PACKAGE, true)]
+    #[case("apk add curl", "OK: 12 MiB in 25 packages", false)]
+    #[case("apt add curll", NO_SUCH_PACKAGE, false)]
+    #[case("apk add openssl", CONFLICT, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("apk add curll", NO_SUCH_PACKAGE, vec!["apk add curl"])]
+    #[case("sudo apk add --no-cache curll jq", NO_SUCH_PACKAGE, vec!["sudo apk add --no-cache curl jq"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = FakeContext::new().with_command_output("apk search -q", "curl\njq\nvim\n");
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
@@ -0,0 +1,103 @@
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{
+    cli::command::CrabCommand,
+    context::Context,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+use regex::Regex;
+
+const APPLETS: [&str; 19] = [
+    "add", "audit", "cache", "del", "dot", "fetch", "fix", "index", "info", "list", "manifest",
+    "policy", "search", "stats", "update", "upgrade", "verify", "version", "help",
+];
+
+fn get_unknown_applet(output: &str) -> Option<String> {
+    let re = Regex::new(r"'([^']+)' is not an apk command").unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_applet(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["apk"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    match command.output.as_deref().and_then(get_unknown_applet) {
+        Some(applet) => get_close_matches(&applet, &APPLETS, None, None)
+            .iter()
+            .map(|new| replace_argument(&command.script, &applet, new))
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "apk_unknown_applet".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNKNOWN_ADD: &str = "ERROR: 'ad' is not an apk command. See 'apk --help'.";
+    const UNKNOWN_UPGRADE: &str = "ERROR: 'upgarde' is not an apk command. See 'apk --help'.";
+
+    #[rstest]
+    #[case("apk ad vim", UNKNOWN_ADD, true)]
+    #[case("sudo apk upgarde", UNKNOWN_UPGRADE, true)]
+    #[case("apk add vim", "", false)]
+    #[case("apt ad vim", UNKNOWN_ADD, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("apk ad vim", UNKNOWN_ADD, vec!["apk add vim"])]
+    #[case("sudo apk upgarde", UNKNOWN_UPGRADE, vec!["sudo apk upgrade", "sudo apk update"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
+    }
+}
@@ -1,8 +1,6 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
-use which::which;
-
-use super::Rule;
+use super::BuiltinRule;
 
 fn get_executable(command: &CrabCommand) -> &str {
     if command.script_parts[0] == "sudo" {
@@ -12,18 +10,14 @@ fn get_executable(command: &CrabCommand) -> &str {
     }
 }
 
-fn _match_rule(
+pub fn match_rule(
     command: &mut CrabCommand,
     system_shell: Option<&dyn Shell>,
-    mock_which: Option<bool>,
+    context: &dyn Context,
 ) -> bool {
     if let Some(stdout) = &command.output {
         if stdout.contains("not found") || stdout.contains("not installed") {
-            if let Some(which_return) = mock_which {
-                !which_return
-            } else {
-                which(get_executable(command)).is_err()
-            }
+            context.which(get_executable(command)).is_none()
         } else {
             false
         }
@@ -32,11 +26,11 @@ fn _match_rule(
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    _match_rule(command, system_shell, None)
-}
-
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     let executable = get_executable(command);
     vec![system_shell.unwrap().and(vec![
         &("sudo apt-get install ".to_owned() + executable),
@@ -44,8 +38,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     ])]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "apt_get".to_owned(),
         None,
         Some(4000),
@@ -58,29 +52,32 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{_match_rule, get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
     #[rstest]
-    #[case("vim", "vim: command not found", None, true)]
-    #[case("sudo vim", "vim: command not found", None, true)]
-    #[case("vim", "The program \"vim\" is currently not installed. You can install it by typing: sudo apt install vim", None, true)]
-    #[case("", "", Some(false), false)]
-    #[case("vim", "", Some(false), false)]
-    #[case("vim", "vim: command not found", Some(true), false)]
-    #[case("sudo vim", "vim: command not found", Some(true), false)]
+    #[case("vim", "vim: command not found", false, true)]
+    #[case("sudo vim", "vim: command not found", false, true)]
+    #[case("vim", "The program \"vim\" is currently not installed. You can install it by typing: sudo apt install vim", false, true)]
+    #[case("", "", false, false)]
+    #[case("vim", "", false, false)]
+    #[case("vim", "vim: command not found", true, false)]
+    #[case("sudo vim", "vim: command not found", true, false)]
     fn test_match_rule(
         #[case] script: &str,
         #[case] stdout: &str,
-        #[case] mock_which: Option<bool>,
+        #[case] is_installed: bool,
         #[case] expected: bool,
     ) {
         let mut command = CrabCommand::new(script.to_owned(), Some(stdout.to_owned()), None);
-        // For matching cases, we need to mock `which` to return an error.
-        let mock_which_result = if expected { Some(false) } else { mock_which };
-        assert_eq!(_match_rule(&mut command, None, mock_which_result), expected);
+        let mut context = FakeContext::new();
+        if is_installed {
+            context = context.with_program("vim");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), expected);
     }
 
     #[rstest]
@@ -92,7 +89,7 @@ mod tests {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(script.to_owned(), Some(stdout.to_owned()), None);
         assert_eq!(
-            get_new_command(&mut command, Some(&system_shell))[0],
+            get_new_command(&mut command, Some(&system_shell), &FakeContext::new())[0],
             expected
         );
     }
@@ -1,6 +1,6 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 
-use super::Rule;
+use super::BuiltinRule;
 
 pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
     command.script.starts_with("apt-get search")
@@ -10,8 +10,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     vec!["apt-cache".to_owned() + &command.script[7..]]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "apt_get_search".to_owned(),
         None,
         None,
@@ -1,6 +1,6 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
-use super::{match_rule_without_sudo, Rule};
+use super::{match_rule_without_sudo, BuiltinRule};
 
 fn _match_rule(command: &CrabCommand) -> bool {
     (command.script == "apt list --upgradable") & {
@@ -12,16 +12,24 @@ fn _match_rule(command: &CrabCommand) -> bool {
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    match_rule_without_sudo(_match_rule, command)
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(_match_rule, command, context)
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     vec!["apt upgrade".to_owned()]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "apt_upgrade".to_owned(),
         None,
         None,
@@ -36,6 +44,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{_match_rule, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
 
     #[test]
     fn test_match_rule() {
@@ -55,7 +64,8 @@ mod tests {
                 Some("multiple\nlines".to_owned()),
                 None
             ),
-            None
+            None,
+            &FakeContext::new()
         ));
     }
 }
@@ -1,4 +1,4 @@
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{utils::match_rule_with_is_app, BuiltinRule};
 use crate::{cli::command::CrabCommand, shell::Shell};
 use regex::Regex;
 
@@ -32,8 +32,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
         .collect()
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "az_cli".to_owned(),
         None,
         None,
@@ -1,7 +1,7 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 use regex::Regex;
 
-use super::Rule;
+use super::BuiltinRule;
 
 fn get_suggestions(str: String) -> Vec<String> {
     str.replace(" or ", ", ")
@@ -34,8 +34,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
         .collect()
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "brew_install".to_owned(),
         None,
         None,
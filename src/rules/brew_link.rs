@@ -1,4 +1,7 @@
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
 use crate::{cli::command::CrabCommand, shell::Shell};
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
@@ -22,11 +25,11 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     "link".clone_into(&mut command_parts[1]);
     command_parts.insert(2, "--overwrite".to_owned());
     command_parts.insert(3, "--dry-run".to_owned());
-    vec![command_parts.join(" ")]
+    vec![join_script(system_shell, &command_parts)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "brew_link".to_owned(),
         None,
         None,
@@ -1,4 +1,7 @@
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
 use crate::{cli::command::CrabCommand, shell::Shell};
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
@@ -21,11 +24,11 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     let mut command_parts = command.script_parts.clone();
     "uninstall".clone_into(&mut command_parts[1]);
     command_parts.insert(2, "--force".to_owned());
-    vec![command_parts.join(" ")]
+    vec![join_script(system_shell, &command_parts)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "brew_uninstall".to_owned(),
         None,
         None,
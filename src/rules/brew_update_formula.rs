@@ -1,6 +1,6 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 
-use super::Rule;
+use super::BuiltinRule;
 
 pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
     if let Some(stdout) = &command.output {
@@ -16,8 +16,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     vec![command.script.replace("update", "upgrade")]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "brew_update_formula".to_owned(),
         None,
         None,
@@ -1,18 +1,11 @@
-use super::{utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use std::path::Path;
 
-fn is_dir(path: &str) -> bool {
-    Path::new(path).is_dir()
-}
-
-fn auxiliary_match_rule<F>(command: &CrabCommand, fn_is_dir: F) -> bool
-where
-    F: Fn(&str) -> bool,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         if command.script_parts.len() > 1 {
-            output.starts_with("cat: ") && fn_is_dir(&command.script_parts[1])
+            output.starts_with("cat: ") && context.is_dir(Path::new(&command.script_parts[1]))
         } else {
             false
         }
@@ -21,32 +14,29 @@ where
     }
 }
 
-fn mockable_match_rule<F>(
+pub fn match_rule(
     command: &mut CrabCommand,
     system_shell: Option<&dyn Shell>,
-    fn_is_dir: F,
-) -> bool
-where
-    F: Fn(&str) -> bool,
-{
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(
-        |command| auxiliary_match_rule(command, &fn_is_dir),
+        |command| auxiliary_match_rule(command, context),
         command,
         vec!["cat"],
         Some(1),
     )
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    mockable_match_rule(command, system_shell, is_dir)
-}
-
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     vec![command.script.replacen("cat", "ls", 1)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "cat_dir".to_owned(),
         None,
         None,
@@ -59,15 +49,16 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_new_command, mockable_match_rule};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
-    fn is_dir_true(path: &str) -> bool {
-        true
-    }
-    fn is_dir_false(path: &str) -> bool {
-        false
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_dir("foo")
+            .with_dir("/foo/bar")
+            .with_dir("cat")
     }
 
     #[rstest]
@@ -79,17 +70,7 @@ mod tests {
     #[case("notcat foo bar", "some output", false)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            mockable_match_rule(
-                &mut command,
-                None,
-                match is_match {
-                    true => is_dir_true,
-                    false => is_dir_false,
-                }
-            ),
-            is_match
-        );
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
     }
 
     #[rstest]
@@ -102,6 +83,6 @@ mod tests {
         #[case] expected: Vec<&str>,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
     }
 }
@@ -1,25 +1,21 @@
 use crate::rules::cd_mkdir::auxiliary_get_new_command;
 use crate::utils::get_close_matches;
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
-use std::env;
-use std::fs;
-use std::path::{Path, MAIN_SEPARATOR};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
 
-use super::{get_new_command_without_sudo, match_rule_without_sudo, Rule};
+use super::{get_new_command_without_sudo, match_rule_without_sudo, utils::quote, BuiltinRule};
 
-fn get_sub_dirs(parent: &str) -> Vec<String> {
-    let mut sub_dirs = Vec::new();
-    if let Ok(entries) = fs::read_dir(parent) {
-        let sub_dirs: Vec<String> = entries
-            .into_iter()
-            .flatten()
-            .filter(|entry| entry.metadata().is_ok_and(|m| m.is_dir()))
-            .map(|entry| entry.path().to_str().unwrap().to_string())
-            .collect();
-    }
-    sub_dirs
+fn get_sub_dirs(parent: &Path, context: &dyn Context) -> Vec<String> {
+    context
+        .read_dir(parent)
+        .iter()
+        .filter(|entry| context.is_dir(entry))
+        .filter_map(|entry| entry.file_name().and_then(|name| name.to_str()))
+        .map(|name| name.to_owned())
+        .collect()
 }
+
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     command.script.starts_with("cd ")
         && (if let Some(output) = &command.output {
@@ -31,60 +27,70 @@ fn auxiliary_match_rule(command: &CrabCommand) -> bool {
         })
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    match_rule_without_sudo(auxiliary_match_rule, command)
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(auxiliary_match_rule, command, context)
 }
 
-fn _get_new_command(command: &CrabCommand) -> Vec<String> {
+fn auxiliary_get_new_command_with_context(
+    command: &CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     if command.script_parts.len() > 1 {
         let mut dest: Vec<&str> = command.script_parts[1].split(MAIN_SEPARATOR).collect();
-        let mut cwd;
+        let mut cwd: PathBuf;
         if dest.last() == Some(&"") {
             dest.pop();
         }
         if dest[0].is_empty() {
-            cwd = MAIN_SEPARATOR.to_string();
+            cwd = PathBuf::from(MAIN_SEPARATOR.to_string());
             dest.remove(0);
         } else {
-            cwd = env::current_dir().unwrap().to_str().unwrap().to_string();
+            cwd = context.current_dir();
         }
 
         for directory in dest {
             if directory == "." {
                 continue;
             } else if directory == ".." {
-                cwd = Path::new(&cwd)
-                    .parent()
-                    .unwrap()
-                    .to_str()
-                    .unwrap()
-                    .to_string();
+                cwd.pop();
                 continue;
             }
-            let sub_dirs = get_sub_dirs(&cwd);
+            let sub_dirs = get_sub_dirs(&cwd, context);
             let sub_dirs = sub_dirs.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
             let best_matches = get_close_matches(directory, &sub_dirs, None, None);
             if !best_matches.is_empty() {
-                cwd = Path::new(&cwd)
-                    .join(best_matches[0])
-                    .to_str()
-                    .unwrap()
-                    .to_string();
+                cwd.push(best_matches[0]);
             } else {
                 return auxiliary_get_new_command(command);
             }
         }
-        return vec![format!("cd \"{}\"", cwd)];
+        return vec![format!(
+            "cd {}",
+            quote(system_shell, &cwd.to_string_lossy())
+        )];
     }
     vec![]
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_without_sudo(_get_new_command, command)
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(
+        |command| auxiliary_get_new_command_with_context(command, system_shell, context),
+        command,
+        context,
+    )
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "cd_correction".to_owned(),
         None,
         None,
@@ -97,8 +103,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::auxiliary_match_rule;
+    use super::{auxiliary_match_rule, get_new_command};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
     #[rstest]
@@ -112,4 +119,26 @@ mod tests {
         let mut command = CrabCommand::new(command.to_owned(), None, Some(stderr.to_owned()));
         assert_eq!(auxiliary_match_rule(&command), is_match);
     }
+
+    #[rstest]
+    #[case("cd foo", vec!["cd /home/crab/foo"])]
+    #[case("cd fooo/bar/", vec!["cd /home/crab/foo/bar"])]
+    #[case("cd ./fo/baz", vec!["cd /home/crab/foo/baz"])]
+    #[case("cd ../crb/fo", vec!["cd /home/crab/foo"])]
+    #[case("cd /hme/crab/fo", vec!["cd /home/crab/foo"])]
+    #[case("sudo cd fo", vec!["sudo cd /home/crab/foo"])]
+    #[case("cd qux", vec!["mkdir -p qux && cd qux"])]
+    #[case("cd fo/qux", vec!["mkdir -p fo/qux && cd fo/qux"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let context = FakeContext::new()
+            .with_dir("foo/bar")
+            .with_dir("foo/baz")
+            .with_file("foo/bat", b"");
+        let mut command = CrabCommand::new(
+            command.to_owned(),
+            None,
+            Some("cd: no such file or directory".to_owned()),
+        );
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
 }
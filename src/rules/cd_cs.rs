@@ -1,6 +1,6 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 
-use super::Rule;
+use super::BuiltinRule;
 
 pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
     command.script_parts[0] == "cs"
@@ -17,8 +17,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
 /// cs: command not found
 /// $ crab
 /// cd /etc/ [enter/↑/↓/ctrl+c]
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "cd_cs".to_owned(),
         None,
         Some(900),
@@ -1,8 +1,9 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use regex::Regex;
 
 use super::{
-    get_new_command_without_sudo, match_rule_without_sudo, utils::match_rule_with_is_app, Rule,
+    get_new_command_without_sudo, match_rule_without_sudo, utils::match_rule_with_is_app,
+    BuiltinRule,
 };
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
@@ -17,10 +18,15 @@ fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_without_sudo(
         |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["cd"], None),
         command,
+        context,
     )
 }
 
@@ -30,12 +36,16 @@ pub fn auxiliary_get_new_command(command: &CrabCommand) -> Vec<String> {
     vec![re.replace(&command.script, repl).to_string()]
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_without_sudo(auxiliary_get_new_command, command)
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(auxiliary_get_new_command, command, context)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "cd_mkdir".to_owned(),
         None,
         None,
@@ -50,6 +60,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
     #[rstest]
@@ -61,7 +72,10 @@ mod tests {
     #[case("", "", false)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
@@ -73,6 +87,9 @@ mod tests {
         #[case] expected: Vec<&str>,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
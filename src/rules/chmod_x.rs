@@ -1,51 +1,37 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
-use is_executable::IsExecutable;
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
 use std::path::Path;
 
-use super::Rule;
+use super::BuiltinRule;
 
-fn _match_rule(
+pub fn match_rule(
     command: &mut CrabCommand,
-    mock_file_exists: Option<bool>,
-    mock_file_access: Option<bool>,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
 ) -> bool {
     if let Some(stdout) = &command.output {
-        command.script.starts_with("./")
-            && stdout.to_lowercase().contains("permission denied")
-            && {
-                if let Some(file_exists) = mock_file_exists {
-                    file_exists
-                } else {
-                    Path::new(command.script_parts[0].as_str()).exists()
-                }
-            }
-            && {
-                if let Some(file_access) = mock_file_access {
-                    !file_access
-                } else {
-                    let path = Path::new(&command.script_parts[0]);
-                    !path.is_executable()
-                }
-            }
+        command.script.starts_with("./") && stdout.to_lowercase().contains("permission denied") && {
+            let path = Path::new(&command.script_parts[0]);
+            context.exists(path) && !context.is_executable(path)
+        }
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    _match_rule(command, None, None)
-}
-
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     vec![system_shell.unwrap().and(vec![
         format!("chmod +x {}", &command.script_parts[0][2..]).as_str(),
         &command.script,
     ])]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "chmod_x".to_owned(),
         None,
         None,
@@ -58,10 +44,20 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{_match_rule, get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
 
+    fn context(script: &str, file_exists: bool, file_access: bool) -> FakeContext {
+        let path = script.split_whitespace().next().unwrap();
+        match (file_exists, file_access) {
+            (true, true) => FakeContext::new().with_executable_file(path),
+            (true, false) => FakeContext::new().with_file(path, b""),
+            _ => FakeContext::new(),
+        }
+    }
+
     macro_rules! parameterized_match_rule_tests {
         ($($name:ident: $value:expr,)*) => {
             $(
@@ -73,7 +69,8 @@ mod tests {
                                 Some(stdout.to_owned()),
                                 None
                             );
-                    assert!(_match_rule(&mut command, file_exists, file_access));
+                    let context = context(script, file_exists, file_access);
+                    assert!(match_rule(&mut command, None, &context));
                 }
             )*
         }
@@ -90,7 +87,8 @@ mod tests {
                                 Some(stdout.to_owned()),
                                 None
                             );
-                    assert!(!_match_rule(&mut command, file_exists, file_access));
+                    let context = context(script, file_exists, file_access);
+                    assert!(!match_rule(&mut command, None, &context));
                 }
             )*
         }
@@ -108,22 +106,22 @@ mod tests {
                                 Some(stdout.to_owned()),
                                 None
                             );
-                    assert_eq!(get_new_command(&mut command, Some(&system_shell))[0], expected);
+                    assert_eq!(get_new_command(&mut command, Some(&system_shell), &FakeContext::new())[0], expected);
                 }
             )*
         }
     }
 
     parameterized_match_rule_tests! {
-        match_rule_1: ("./gradlew build", "gradlew: Permission denied", Some(true), Some(false)),
-        match_rule_2: ("./install.sh --help", "install.sh: permission denied", Some(true), Some(false)),
+        match_rule_1: ("./gradlew build", "gradlew: Permission denied", true, false),
+        match_rule_2: ("./install.sh --help", "install.sh: permission denied", true, false),
     }
 
     parameterized_unmatch_rule_tests! {
-        unmatch_rule_1: ("./gradlew build", "gradlew: Permission denied", Some(true), Some(true)),
-        unmatch_rule_2: ("./gradlew build", "gradlew: Permission denied", Some(false), Some(false)),
-        unmatch_rule_3: ("./gradlew build", "gradlew: error", Some(true), Some(false)),
-        unmatch_rule_4: ("gradlew build", "gradlew: Permission denied", Some(true), Some(false)),
+        unmatch_rule_1: ("./gradlew build", "gradlew: Permission denied", true, true),
+        unmatch_rule_2: ("./gradlew build", "gradlew: Permission denied", false, false),
+        unmatch_rule_3: ("./gradlew build", "gradlew: error", true, false),
+        unmatch_rule_4: ("gradlew build", "gradlew: Permission denied", true, false),
     }
 
     parameterized_get_new_command_tests! {
@@ -1,7 +1,10 @@
-use super::{utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
-use shlex::Quoter;
-use std::fs;
+use super::{
+    utils::{match_rule_with_is_app, quote},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+use std::io::Cursor;
+use std::path::{Component, Path};
 use tar::Archive;
 
 const TAR_EXTENSIONS: [&str; 15] = [
@@ -47,15 +50,22 @@ fn auxiliary_match_rule(command: &CrabCommand) -> bool {
         && tar_file(&command.script_parts).is_some()
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(auxiliary_match_rule, command, vec!["tar"], None)
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    let shlex_quoter = Quoter::new();
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     match tar_file(&command.script_parts) {
         Some((_, filepath_no_ext)) => {
-            let dir = shlex_quoter.quote(&filepath_no_ext).unwrap();
+            let dir = quote(system_shell, &filepath_no_ext);
             vec![system_shell.unwrap().and(vec![
                 &format!("mkdir -p {dir}"),
                 &format!("{cmd} -C {dir}", dir = dir, cmd = command.script),
@@ -65,35 +75,42 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn side_effect(old_cmd: CrabCommand, command: Option<&str>) {
+pub fn side_effect(old_cmd: CrabCommand, command: Option<&str>, context: &dyn Context) {
     if let Some((filepath, _)) = tar_file(&old_cmd.script_parts) {
-        let mut archive = Archive::new(std::fs::File::open(filepath).unwrap());
-
-        for file in archive.entries().unwrap() {
-            let file = file.unwrap();
-            let path = file.path().unwrap().to_path_buf();
+        let Some(content) = context.read(Path::new(&filepath)) else {
+            return;
+        };
+        let mut archive = Archive::new(Cursor::new(content));
+        let Ok(entries) = archive.entries() else {
+            return;
+        };
+
+        for file in entries.flatten() {
+            let Ok(path) = file.path() else {
+                continue;
+            };
+            let path = path.to_path_buf();
 
             let filename = path.to_string_lossy();
             if !filename.starts_with("._") {
-                if !path
-                    .canonicalize()
-                    .unwrap()
-                    .starts_with(std::env::current_dir().unwrap())
+                if path
+                    .components()
+                    .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
                 {
                     // it's unsafe to overwrite files outside of the current directory
                     continue;
                 }
 
-                if path.is_file() {
-                    fs::remove_file(path).unwrap_or(());
+                if context.is_file(&path) {
+                    context.remove_file(&path).unwrap_or(());
                 }
             }
         }
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "tar".to_owned(),
         None,
         None,
@@ -108,68 +125,29 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule, side_effect, TAR_EXTENSIONS};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
-    use std::env;
-    use std::fs;
-    use std::fs::File;
-    use std::io::Write;
-    use std::path::Path;
     use std::path::PathBuf;
-    use tar::Archive;
-    use tar::Builder;
-    use tempfile::TempDir;
-
-    pub fn tar_error(filename: &str, tmp_dir: &TempDir) {
-        let filename = format!("./{filename}");
-        let path = tmp_dir.path().join(&filename);
-
-        let _ = env::set_current_dir(tmp_dir.path());
-        reset(&path);
-
-        let entries = fs::read_dir(".").unwrap();
-        let mut files = entries
-            .map(|res| res.map(|e| e.path()))
-            .collect::<Result<Vec<_>, std::io::Error>>()
-            .unwrap();
-        let mut expected_files = vec![
-            Path::new(&filename),
-            Path::new("./a"),
-            Path::new("./b"),
-            Path::new("./c"),
-            Path::new("./d"),
-        ];
-        expected_files.sort();
-        files.sort();
-        assert_eq!(files, expected_files);
-
-        let entries = fs::read_dir("./d").unwrap();
-        let mut files = entries
-            .map(|res| res.map(|e| e.path()))
-            .collect::<Result<Vec<_>, std::io::Error>>()
-            .unwrap();
-        files.sort();
-        assert_eq!(files, vec![Path::new("./d/e")]);
-    }
-
-    fn reset(path: &Path) {
-        fs::create_dir("d").unwrap();
-        let files = vec!["a", "b", "c", "d/e"];
-
-        let tar_gz = File::create(path).unwrap();
-        let mut tar = Builder::new(tar_gz);
-
-        for file in files {
-            let file_path = PathBuf::from(file);
-
-            let mut f = File::create(&file_path).unwrap();
-            f.write_all(b"*").unwrap();
-            tar.append_path(&file_path).unwrap();
-            fs::remove_file(&file_path).unwrap();
+    use tar::{Builder, Header};
+
+    const ARCHIVED_FILES: [&str; 4] = ["a", "b", "c", "d/e"];
+
+    /// Returns a context containing the archive and the files extracted from it.
+    fn tar_error(filename: &str) -> FakeContext {
+        let mut tar = Builder::new(Vec::new());
+        for file in ARCHIVED_FILES {
+            let mut header = Header::new_gnu();
+            header.set_size(1);
+            header.set_cksum();
+            tar.append_data(&mut header, file, &b"*"[..]).unwrap();
         }
+        let archive = tar.into_inner().unwrap();
 
-        let tar_gz = File::open(path).unwrap();
-        let mut tar = Archive::new(tar_gz);
-        tar.unpack(".").unwrap();
+        let mut context = FakeContext::new().with_file(filename, &archive);
+        for file in ARCHIVED_FILES {
+            context = context.with_file(file, b"*");
+        }
+        context
     }
 
     fn get_filename() -> Vec<(
@@ -218,40 +196,49 @@ mod tests {
     }
 
     #[test]
-    // The unit tests were split into test_match, test_side_effect and test_get_new_command.
-    // However, there was an issue with tempfile raising errors when the tests were running in
-    // parallel. Hence, we moved them to the same function.
-    fn test_dirty_unrar() {
+    fn test_dirty_untar() {
         for (filename, unquoted, quoted) in get_filename() {
             for (script, fixed) in get_script() {
                 for ext in TAR_EXTENSIONS {
-                    let tmp_dir = TempDir::new().unwrap();
-                    tar_error(&unquoted(ext), &tmp_dir);
+                    let context = tar_error(&unquoted(ext));
                     let mut command =
                         CrabCommand::new(script(&filename(ext)), Some("".to_owned()), None);
-                    assert!(match_rule(&mut command, None));
+                    assert!(match_rule(&mut command, None, &context));
 
-                    side_effect(command, None);
-                    let entries = fs::read_dir(".").unwrap();
-                    let mut files = entries
-                        .map(|res| res.map(|e| e.path()))
-                        .collect::<Result<Vec<_>, std::io::Error>>()
-                        .unwrap();
-                    let unquoted = format!("./{}", unquoted(ext));
-                    let mut expected_files = vec![Path::new(&unquoted), Path::new("./d")];
-                    files.sort();
-                    expected_files.sort();
-                    assert_eq!(files, expected_files);
+                    side_effect(command, None, &context);
+                    assert_eq!(context.files(), vec![PathBuf::from(unquoted(ext))]);
 
                     let system_shell = Bash {};
                     let mut command =
                         CrabCommand::new(script(&filename(ext)), Some("".to_owned()), None);
                     assert_eq!(
-                        get_new_command(&mut command, Some(&system_shell)),
+                        get_new_command(&mut command, Some(&system_shell), &context),
                         vec![fixed(&quoted(""), &filename(ext))]
                     );
                 }
             }
         }
     }
+
+    #[test]
+    fn test_side_effect_keeps_files_outside_current_dir() {
+        let mut tar = Builder::new(Vec::new());
+        let mut header = Header::new_gnu();
+        header.set_size(1);
+        // `set_path` refuses to write `..`, so the name is written directly
+        header.as_gnu_mut().unwrap().name[..10].copy_from_slice(b"../outside");
+        header.set_cksum();
+        tar.append(&header, &b"*"[..]).unwrap();
+        let archive = tar.into_inner().unwrap();
+
+        let context = FakeContext::new()
+            .with_current_dir("/home/crab/project")
+            .with_file("foo.tar", &archive)
+            .with_file("/home/crab/outside", b"*");
+        let command = CrabCommand::new("tar xvf foo.tar".to_owned(), Some("".to_owned()), None);
+        side_effect(command, None, &context);
+        assert!(context
+            .files()
+            .contains(&PathBuf::from("/home/crab/outside")));
+    }
 }
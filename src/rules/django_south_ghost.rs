@@ -1,4 +1,4 @@
-use super::Rule;
+use super::BuiltinRule;
 use crate::{cli::command::CrabCommand, shell::Shell};
 
 pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
@@ -14,8 +14,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     vec![format!("{} --delete-ghost-migrations", command.script)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "django_south_ghost".to_owned(),
         None,
         None,
@@ -0,0 +1,181 @@
+use super::{
+    utils::{
+        fedora::{get_installer, get_providing_packages},
+        wrappers::get_wrapped_program,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+use regex::Regex;
+
+/// Returns the packages that provide the executable, as suggested by the PackageKit
+/// handler (`Install package 'vim-enhanced' to provide command 'vim'?`) or found with
+/// `dnf provides`.
+fn get_packages(command: &CrabCommand, output: &str, context: &dyn Context) -> Vec<String> {
+    let re = Regex::new(r"Install package '([^']+)' to provide command '[^']+'\?").unwrap();
+    if let Some(caps) = re.captures(output) {
+        return vec![caps[1].to_owned()];
+    }
+    match get_wrapped_program(&command.script, context) {
+        Some(executable) if context.which(&executable).is_none() => {
+            get_providing_packages(&executable, context)
+        }
+        _ => vec![],
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("command not found")
+            && get_installer(context).is_some()
+            && !get_packages(command, output, context).is_empty()
+    } else {
+        false
+    }
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let (Some(output), Some(installer)) = (&command.output, get_installer(context)) else {
+        return vec![];
+    };
+    get_packages(command, output, context)
+        .iter()
+        .map(|package| {
+            system_shell
+                .unwrap()
+                .and(vec![&format!("{installer} {package}"), &command.script])
+        })
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "dnf_command_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use crate::shell::Bash;
+    use rstest::rstest;
+
+    const PACKAGEKIT: &str = "bash: vim: command not found...
+Install package 'vim-enhanced' to provide command 'vim'? [N/y]";
+    const PROVIDES_HTOP: &str = "\
+htop-3.3.0-3.fc40.x86_64 : Interactive process viewer
+Repo        : fedora
+Matched from:
+Filename    : /usr/bin/htop
+";
+
+    #[rstest]
+    #[case("vim", PACKAGEKIT, FakeContext::new().with_program("dnf"), true)]
+    #[case(
+        "htop",
+        "bash: htop: command not found",
+        FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/htop", PROVIDES_HTOP),
+        true
+    )]
+    #[case(
+        "sudo htop",
+        "sudo: htop: command not found",
+        FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/htop", PROVIDES_HTOP),
+        true
+    )]
+    #[case(
+        "foo",
+        "bash: foo: command not found",
+        FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/htop", PROVIDES_HTOP),
+        false
+    )]
+    #[case(
+        "htop",
+        "",
+        FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/htop", PROVIDES_HTOP),
+        false
+    )]
+    #[case("vim", PACKAGEKIT, FakeContext::new(), false)]
+    fn test_match(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] context: FakeContext,
+        #[case] is_match: bool,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
+    }
+
+    #[rstest]
+    #[case(
+        "vim file.txt",
+        PACKAGEKIT,
+        FakeContext::new().with_program("dnf"),
+        vec!["sudo dnf install vim-enhanced && vim file.txt"]
+    )]
+    #[case(
+        "htop",
+        "bash: htop: command not found",
+        FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/htop", PROVIDES_HTOP),
+        vec!["sudo dnf install htop && htop"]
+    )]
+    #[case(
+        "sudo htop",
+        "sudo: htop: command not found",
+        FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/htop", PROVIDES_HTOP),
+        vec!["sudo dnf install htop && sudo htop"]
+    )]
+    #[case(
+        "vim",
+        PACKAGEKIT,
+        FakeContext::new().with_program("yum"),
+        vec!["sudo yum install vim-enhanced && vim"]
+    )]
+    #[case(
+        "vim",
+        PACKAGEKIT,
+        FakeContext::new().with_program("rpm-ostree").with_file("/run/ostree-booted", b""),
+        vec!["rpm-ostree install --apply-live vim-enhanced && vim"]
+    )]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] context: FakeContext,
+        #[case] expected: Vec<&str>,
+    ) {
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, Some(&system_shell), &context),
+            expected
+        );
+    }
+}
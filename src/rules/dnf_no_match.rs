@@ -0,0 +1,104 @@
+use super::{
+    match_rule_without_sudo,
+    utils::{
+        fedora::{get_packages, DNF_APPS},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+/// Returns the first package that could not be found, from `No match for argument: vmi`
+/// or `Unable to find a match: vmi`.
+fn get_missing_package(output: &str) -> Option<String> {
+    let re = Regex::new(r"(?:No match for argument|Unable to find a match): (\S+)").unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_package(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, DNF_APPS.to_vec(), None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(package) = command.output.as_deref().and_then(get_missing_package) else {
+        return vec![];
+    };
+    let packages = get_packages(context);
+    let packages = packages.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    replace_command(command, &package, packages)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "dnf_no_match".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NO_MATCH: &str = "Last metadata expiration check: 0:12:01 ago.
+No match for argument: htpo
+Error: Unable to find a match: htpo";
+
+    #[rstest]
+    #[case("dnf install htpo", NO_MATCH, true)]
+    #[case("sudo dnf install htpo", NO_MATCH, true)]
+    #[case(
+        "yum install htpo",
+        "No package htpo available.\nError: Unable to find a match: htpo",
+        true
+    )]
+    #[case("dnf install htop", "", false)]
+    #[case("zypper install htpo", NO_MATCH, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("dnf install htpo", vec!["dnf install htop", "dnf install btop"])]
+    #[case("sudo dnf install -y htpo", vec!["sudo dnf install -y htop", "sudo dnf install -y btop"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let context = FakeContext::new().with_program("dnf").with_command_output(
+            "dnf -q repoquery --qf %{name}\n",
+            "htop\nbtop\nvim-enhanced\n",
+        );
+        let mut command = CrabCommand::new(command.to_owned(), Some(NO_MATCH.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
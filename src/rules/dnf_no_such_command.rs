@@ -0,0 +1,135 @@
+use super::{
+    match_rule_without_sudo,
+    utils::{
+        fedora::{get_operations, DNF_APPS},
+        match_rule_with_is_app,
+        wrappers::get_wrapped_program,
+    },
+    BuiltinRule,
+};
+use crate::{
+    cli::command::CrabCommand,
+    context::Context,
+    shell::Shell,
+    utils::{replace_argument, replace_command},
+};
+use regex::Regex;
+
+/// Returns the unknown operation, from the output of dnf 4 and yum
+/// (`No such command: isntall.`) or dnf 5 (`Unknown argument "isntall" for command`).
+fn get_misspelled_operation(output: &str) -> Option<String> {
+    let re = Regex::new(r#"(?:No such command: |Unknown argument ")([^\s."]+)"#).unwrap();
+    re.captures(output)
+        .map(|caps| caps[1].to_owned())
+        .filter(|operation| !operation.starts_with('-'))
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_misspelled_operation(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, DNF_APPS.to_vec(), None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(misspelled) = command.output.as_deref().and_then(get_misspelled_operation) else {
+        return vec![];
+    };
+    let Some(program) = get_wrapped_program(&command.script, context) else {
+        return vec![];
+    };
+    // `uninstall` is closer to `install` than to `remove`, which is what is meant
+    if misspelled == "uninstall" {
+        return vec![replace_argument(&command.script, &misspelled, "remove")];
+    }
+    let operations = get_operations(&program, context);
+    let operations = operations.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    replace_command(command, &misspelled, operations)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "dnf_no_such_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const DNF_NO_SUCH_COMMAND: &str = "No such command: isntall. Please use /usr/bin/dnf --help
+It could be a DNF plugin command, try: \"dnf install 'dnf-command(isntall)'\"";
+    const YUM_NO_SUCH_COMMAND: &str = "No such command: uninstall. Please use /usr/bin/yum --help";
+    const DNF5_UNKNOWN_ARGUMENT: &str =
+        "Unknown argument \"upgarde\" for command \"dnf5\". Add \"--help\" for more information about the arguments.";
+
+    #[rstest]
+    #[case("dnf isntall vim", DNF_NO_SUCH_COMMAND, true)]
+    #[case("sudo dnf isntall vim", DNF_NO_SUCH_COMMAND, true)]
+    #[case("yum uninstall vim", YUM_NO_SUCH_COMMAND, true)]
+    #[case("dnf5 upgarde", DNF5_UNKNOWN_ARGUMENT, true)]
+    #[case(
+        "dnf5 --foo",
+        "Unknown argument \"--foo\" for command \"dnf5\".",
+        false
+    )]
+    #[case("dnf install vim", "", false)]
+    #[case("apt isntall vim", DNF_NO_SUCH_COMMAND, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("dnf isntall vim", DNF_NO_SUCH_COMMAND, vec!["dnf install vim", "dnf reinstall vim", "dnf list vim"])]
+    #[case("sudo dnf isntall vim", DNF_NO_SUCH_COMMAND, vec!["sudo dnf install vim", "sudo dnf reinstall vim", "sudo dnf list vim"])]
+    #[case("yum uninstall vim", YUM_NO_SUCH_COMMAND, vec!["yum remove vim"])]
+    #[case("dnf5 upgarde", DNF5_UNKNOWN_ARGUMENT, vec!["dnf5 upgrade", "dnf5 remove"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let dnf5_help = "\
+Usage:
+  dnf5 [GLOBAL OPTIONS] <COMMAND> ...
+
+Software Management Commands:
+  install                   Install software
+  upgrade                   Upgrade software
+  remove                    Remove (uninstall) software
+";
+        let context = FakeContext::new().with_command_output("dnf5 --help", dnf5_help);
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
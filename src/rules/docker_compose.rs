@@ -0,0 +1,123 @@
+use super::{
+    utils::{
+        docker::{get_unknown_command, DOCKER_APPS},
+        join_script,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+/// Returns the program and arguments to run the command with the other flavor of Compose:
+/// the `compose` plugin (`docker compose`) or the standalone `docker-compose`.
+fn get_swapped_compose(command: &CrabCommand, context: &dyn Context) -> Option<Vec<String>> {
+    let output = command.output.as_deref()?;
+    let program = command.script_parts.first()?;
+    let mut parts = command.script_parts.clone();
+    if let Some(app) = program.strip_suffix("-compose") {
+        if !DOCKER_APPS.contains(&app) || !output.contains("not found") {
+            return None;
+        }
+        context.which(app)?;
+        parts.splice(0..1, [app.to_owned(), "compose".to_owned()]);
+    } else {
+        let unknown = get_unknown_command(output)?;
+        if !DOCKER_APPS.contains(&program.as_str())
+            || command.script_parts.get(1).map(|s| s.as_str()) != Some("compose")
+            || unknown.name != "compose"
+        {
+            return None;
+        }
+        let standalone = format!("{program}-compose");
+        context.which(&standalone)?;
+        parts.splice(0..2, [standalone]);
+    }
+    Some(parts)
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    get_swapped_compose(command, context).is_some()
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_swapped_compose(command, context)
+        .map(|parts| join_script(system_shell, &parts))
+        .into_iter()
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "docker_compose".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NOT_FOUND: &str = "bash: docker-compose: command not found";
+    const NO_PLUGIN: &str = "docker: 'compose' is not a docker command.\nSee 'docker --help'";
+    const PODMAN_NO_PLUGIN: &str = "Error: unrecognized command `podman compose`";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_program("docker")
+            .with_program("podman")
+            .with_program("podman-compose")
+    }
+
+    #[rstest]
+    #[case("docker-compose up -d", NOT_FOUND, context(), true)]
+    #[case("podman compose up", PODMAN_NO_PLUGIN, context(), true)]
+    #[case("docker compose up", NO_PLUGIN, context(), false)]
+    #[case("docker compose up", NO_PLUGIN, context().with_program("docker-compose"), true)]
+    #[case("docker-compose up -d", NOT_FOUND, FakeContext::new(), false)]
+    #[case(
+        "docker psa",
+        "docker: 'psa' is not a docker command.",
+        context(),
+        false
+    )]
+    #[case("docker compose up", "", context().with_program("docker-compose"), false)]
+    fn test_match(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] context: FakeContext,
+        #[case] is_match: bool,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
+    }
+
+    #[rstest]
+    #[case("docker-compose up -d", NOT_FOUND, vec!["docker compose up -d"])]
+    #[case("docker-compose -f dev.yml logs -f web", NOT_FOUND, vec!["docker compose -f dev.yml logs -f web"])]
+    #[case("docker compose up -d", NO_PLUGIN, vec!["docker-compose up -d"])]
+    #[case("podman compose up", PODMAN_NO_PLUGIN, vec!["podman-compose up"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = context().with_program("docker-compose");
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
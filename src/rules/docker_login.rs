@@ -1,5 +1,5 @@
-use super::{get_new_command_without_sudo, utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{get_new_command_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     if let Some(output) = &command.output {
@@ -11,7 +11,11 @@ fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(auxiliary_match_rule, command, vec!["docker"], None)
 }
 
@@ -19,12 +23,16 @@ pub fn auxiliary_get_new_command(command: &CrabCommand) -> Vec<String> {
     vec!["docker login && ".to_owned() + &command.script]
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_without_sudo(auxiliary_get_new_command, command)
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(auxiliary_get_new_command, command, context)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "docker_login".to_owned(),
         None,
         None,
@@ -38,6 +46,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
     const ERR_RESPONSE1: &str = "r#
@@ -74,7 +83,10 @@ cb95fa0faeb1: Layer already exists
     #[case("docker push artifactory:9090/foo/bar:fdb7c6d", ERR_RESPONSE3, false)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
@@ -86,6 +98,9 @@ cb95fa0faeb1: Layer already exists
         #[case] expected: Vec<&str>,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
@@ -0,0 +1,184 @@
+use super::{
+    utils::{docker::DOCKER_APPS, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{
+    cli::command::CrabCommand,
+    context::Context,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Object {
+    Container,
+    Image,
+}
+
+/// Returns the container or image that does not exist, from Docker
+/// (`No such container: webb`) or Podman (`no container with name or ID "webb" found`,
+/// `ngnix: image not known`).
+fn get_missing_object(output: &str) -> Option<(Object, String)> {
+    let re = Regex::new(
+        r#"No such (container|image): (\S+)|no container with name or ID "([^"]+)" found|Error: (\S+): image not known"#,
+    )
+    .unwrap();
+    let caps = re.captures(output)?;
+    if let (Some(kind), Some(name)) = (caps.get(1), caps.get(2)) {
+        let object = match kind.as_str() {
+            "container" => Object::Container,
+            _ => Object::Image,
+        };
+        Some((object, name.as_str().to_owned()))
+    } else if let Some(name) = caps.get(3) {
+        Some((Object::Container, name.as_str().to_owned()))
+    } else {
+        caps.get(4)
+            .map(|name| (Object::Image, name.as_str().to_owned()))
+    }
+}
+
+/// Returns the names of all containers, or the images written like `name`: with their tag
+/// only if `name` has one.
+fn get_names(program: &str, object: Object, name: &str, context: &dyn Context) -> Vec<String> {
+    let args: &[&str] = match object {
+        Object::Container => &["ps", "-a", "--format", "{{.Names}}"],
+        Object::Image if name.contains(':') => &["images", "--format", "{{.Repository}}:{{.Tag}}"],
+        Object::Image => &["images", "--format", "{{.Repository}}"],
+    };
+    let mut names: Vec<String> = context
+        .run(program, args)
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .filter(|line| !line.is_empty() && !line.contains("<none>"))
+                .map(|line| line.to_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.dedup();
+    names
+}
+
+/// Returns the missing object as it was typed. Docker adds the `latest` tag to images
+/// without one.
+fn get_typed_object(command: &CrabCommand) -> Option<(Object, String)> {
+    let (object, name) = get_missing_object(command.output.as_deref()?)?;
+    command
+        .script_parts
+        .iter()
+        .find(|part| **part == name || format!("{part}:latest") == name)
+        .map(|part| (object, part.to_owned()))
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    get_typed_object(command).is_some()
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, DOCKER_APPS.to_vec(), Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some((object, name)) = get_typed_object(command) else {
+        return vec![];
+    };
+    let names = get_names(&command.script_parts[0], object, &name, context);
+    let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    get_close_matches(&name, &names, None, None)
+        .iter()
+        .map(|new| replace_argument(&command.script, &name, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "docker_no_such_object".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const CONTAINERS: &str = "web\nworker\npostgres\n";
+    const IMAGES: &str = "nginx:latest\nnginx:1.25\npostgres:16\n<none>:<none>\n";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_command_output("docker ps -a --format {{.Names}}", CONTAINERS)
+            .with_command_output("podman ps -a --format {{.Names}}", CONTAINERS)
+            .with_command_output(
+                "docker images --format {{.Repository}}",
+                "nginx\nnginx\npostgres\n<none>\n",
+            )
+            .with_command_output("docker images --format {{.Repository}}:{{.Tag}}", IMAGES)
+            .with_command_output(
+                "podman images --format {{.Repository}}",
+                "docker.io/library/nginx\n",
+            )
+    }
+
+    #[rstest]
+    #[case(
+        "docker logs webb",
+        "Error response from daemon: No such container: webb",
+        true
+    )]
+    #[case(
+        "docker rmi ngnix",
+        "Error response from daemon: No such image: ngnix:latest",
+        true
+    )]
+    #[case(
+        "docker rmi ngnix:1.25",
+        "Error response from daemon: No such image: ngnix:1.25",
+        true
+    )]
+    #[case(
+        "podman logs webb",
+        "Error: no container with name or ID \"webb\" found: no such container",
+        true
+    )]
+    #[case("podman rmi ngnix", "Error: ngnix: image not known", true)]
+    #[case("docker logs web", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("docker logs -f webb", "Error response from daemon: No such container: webb", vec!["docker logs -f web"])]
+    #[case("docker exec -it wroker sh", "Error response from daemon: No such container: wroker", vec!["docker exec -it worker sh"])]
+    #[case("docker rmi ngnix", "Error response from daemon: No such image: ngnix:latest", vec!["docker rmi nginx"])]
+    #[case("docker rmi ngnix:1.25", "Error response from daemon: No such image: ngnix:1.25", vec!["docker rmi nginx:1.25"])]
+    #[case("podman stop webb", "Error: no container with name or ID \"webb\" found: no such container", vec!["podman stop web"])]
+    #[case("podman rmi docker.io/library/ngnix", "Error: docker.io/library/ngnix: image not known", vec!["podman rmi docker.io/library/nginx"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
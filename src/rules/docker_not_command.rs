@@ -0,0 +1,152 @@
+use super::{
+    utils::{
+        docker::{get_commands, get_unknown_command, DOCKER_APPS},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{
+    cli::command::CrabCommand,
+    context::Context,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output)
+            .is_some_and(|unknown| command.script_parts.contains(&unknown.name))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, DOCKER_APPS.to_vec(), Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(unknown) = command.output.as_deref().and_then(get_unknown_command) else {
+        return vec![];
+    };
+    let commands = get_commands(&command.script_parts[0], &unknown.parents, context);
+    let commands: Vec<&str> = commands.iter().map(|s| s.as_str()).collect();
+    get_close_matches(&unknown.name, &commands, None, None)
+        .iter()
+        .map(|new| replace_argument(&command.script, &unknown.name, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "docker_not_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const DOCKER_HELP: &str = "
+Usage:  docker [OPTIONS] COMMAND
+
+Common Commands:
+  run         Create and run a new container from an image
+  exec        Execute a command in a running container
+  ps          List containers
+  build       Build an image from a Dockerfile
+  pull        Download an image from a registry
+  images      List images
+
+Management Commands:
+  builder     Manage builds
+  buildx*     Docker Buildx
+  compose*    Docker Compose
+  container   Manage containers
+  image       Manage images
+  network     Manage networks
+  volume      Manage volumes
+";
+    const CONTAINER_HELP: &str = "
+Usage:  docker container COMMAND
+
+Manage containers
+
+Commands:
+  attach      Attach local standard input, output, and error streams to a running container
+  inspect     Display detailed information on one or more containers
+  logs        Fetch the logs of a container
+  ls          List containers
+  prune       Remove all stopped containers
+  rm          Remove one or more containers
+";
+    const PODMAN_HELP: &str = "Manage pods, containers and images
+
+Usage:
+  podman [options] [command]
+
+Available Commands:
+  images      List images in local storage
+  ps          List containers
+  pull        Pull an image from a registry
+  run         Run a command in a new container
+";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_command_output("docker --help", DOCKER_HELP)
+            .with_command_output("docker container --help", CONTAINER_HELP)
+            .with_command_output("podman --help", PODMAN_HELP)
+    }
+
+    #[rstest]
+    #[case(
+        "docker psa",
+        "docker: 'psa' is not a docker command.\nSee 'docker --help'",
+        true
+    )]
+    #[case(
+        "docker contaner ls",
+        "docker: unknown command: docker contaner\n",
+        true
+    )]
+    #[case("podman imges", "Error: unrecognized command `podman imges`", true)]
+    #[case("docker ps", "CONTAINER ID   IMAGE", false)]
+    #[case("nerdctl psa", "docker: 'psa' is not a docker command.", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("docker psa", "docker: 'psa' is not a docker command.\nSee 'docker --help'", vec!["docker ps"])]
+    #[case("docker contaner ls -a", "docker: unknown command: docker contaner\n", vec!["docker container ls -a", "docker network ls -a"])]
+    #[case("docker container lss -a", "docker: unknown command: docker container lss\n", vec!["docker container ls -a"])]
+    #[case("podman imges", "Error: unrecognized command `podman imges`", vec!["podman images"])]
+    #[case("docker biuld .", "docker: 'biuld' is not a docker command.", vec!["docker build .", "docker buildx .", "docker builder ."])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -0,0 +1,81 @@
+use super::{
+    utils::{docker::DOCKER_APPS, match_rule_with_is_app, quote, wrappers::get_privilege_command},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("permission denied while trying to connect to the Docker daemon socket")
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, DOCKER_APPS.to_vec(), None)
+}
+
+/// Users who were just added to the `docker` group can use it with `sg` until they log in
+/// again, without running the command as root.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    vec![
+        format!("{} {}", get_privilege_command(context), command.script),
+        format!("sg docker -c {}", quote(system_shell, &command.script)),
+    ]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "docker_permission_denied".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const DENIED: &str = "permission denied while trying to connect to the Docker daemon socket at unix:///var/run/docker.sock: Get \"http://%2Fvar%2Frun%2Fdocker.sock/v1.24/containers/json\": dial unix /var/run/docker.sock: connect: permission denied";
+
+    #[rstest]
+    #[case("docker ps", DENIED, true)]
+    #[case("podman ps", DENIED, true)]
+    #[case("sudo docker ps", DENIED, false)]
+    #[case("docker ps", "CONTAINER ID   IMAGE", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("docker ps", FakeContext::new(), vec!["sudo docker ps", "sg docker -c 'docker ps'"])]
+    #[case("docker run --rm -it alpine sh", FakeContext::new().with_program("doas"), vec!["doas docker run --rm -it alpine sh", "sg docker -c 'docker run --rm -it alpine sh'"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] context: FakeContext,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(DENIED.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
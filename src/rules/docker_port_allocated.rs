@@ -0,0 +1,152 @@
+use super::{
+    utils::{docker::DOCKER_APPS, join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+use regex::Regex;
+
+/// Returns the host port that is already in use, from Docker
+/// (`Bind for 0.0.0.0:8080 failed: port is already allocated`) or from Podman and Docker
+/// Desktop (`listen tcp 0.0.0.0:8080: bind: address already in use`).
+fn get_allocated_port(output: &str) -> Option<u16> {
+    let re = Regex::new(
+        r"Bind for \S*:(\d+) failed: port is already allocated|listen tcp\d? \S*:(\d+): bind: address already in use",
+    )
+    .unwrap();
+    let caps = re.captures(output)?;
+    caps.get(1).or(caps.get(2))?.as_str().parse().ok()
+}
+
+/// Splits the argument at `index` into the option it is attached to, e.g. `-p` in
+/// `-p8080:80` or `--publish=` in `--publish=8080:80`, and the published ports, if it is the
+/// value of `-p`/`--publish`.
+fn get_publish_value(script_parts: &[String], index: usize) -> Option<(&str, &str)> {
+    let part = script_parts[index].as_str();
+    if index > 0 && matches!(script_parts[index - 1].as_str(), "-p" | "--publish") {
+        return Some(("", part));
+    }
+    if let Some(value) = part.strip_prefix("--publish=") {
+        return Some(("--publish=", value));
+    }
+    if let Some(value) = part.strip_prefix("-p=") {
+        return Some(("-p=", value));
+    }
+    match part.strip_prefix("-p") {
+        Some(value) if !value.is_empty() => Some(("-p", value)),
+        _ => None,
+    }
+}
+
+/// Returns the position of the `-p`/`--publish` argument binding `port` on the host, e.g.
+/// `8080:80` or `127.0.0.1:8080:80/tcp`.
+fn get_publish_position(script_parts: &[String], port: u16) -> Option<usize> {
+    let port = port.to_string();
+    (0..script_parts.len()).position(|index| {
+        let Some((_, value)) = get_publish_value(script_parts, index) else {
+            return false;
+        };
+        let fields: Vec<&str> = value.split(':').collect();
+        fields.len() >= 2 && fields[fields.len() - 2] == port
+    })
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_allocated_port(output)
+            .is_some_and(|port| get_publish_position(&command.script_parts, port).is_some())
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, DOCKER_APPS.to_vec(), Some(1))
+}
+
+/// Publishes the container port on the next free host port instead.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(port) = command.output.as_deref().and_then(get_allocated_port) else {
+        return vec![];
+    };
+    let Some(position) = get_publish_position(&command.script_parts, port) else {
+        return vec![];
+    };
+    let Some(free_port) = (port.saturating_add(1)..=port.saturating_add(100))
+        .find(|candidate| context.is_port_available(*candidate))
+    else {
+        return vec![];
+    };
+    let Some((prefix, value)) = get_publish_value(&command.script_parts, position) else {
+        return vec![];
+    };
+    let mut fields: Vec<&str> = value.split(':').collect();
+    let host = fields.len() - 2;
+    let free_port = free_port.to_string();
+    fields[host] = &free_port;
+    let publish = format!("{prefix}{}", fields.join(":"));
+    let mut parts = command.script_parts.clone();
+    parts[position] = publish;
+    vec![join_script(system_shell, &parts)]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "docker_port_allocated".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const DOCKER: &str = "docker: Error response from daemon: driver failed programming external connectivity on endpoint web (4f1c): Bind for 0.0.0.0:8080 failed: port is already allocated.";
+    const PODMAN: &str =
+        "Error: rootlessport listen tcp 0.0.0.0:8080: bind: address already in use";
+
+    #[rstest]
+    #[case("docker run -p 8080:80 nginx", DOCKER, true)]
+    #[case("podman run --publish=8080:80 nginx", PODMAN, true)]
+    #[case("docker run -p8080:80 nginx", DOCKER, true)]
+    #[case("docker run -p 9090:80 nginx", DOCKER, false)]
+    #[case("docker run -p 8080:80 nginx", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("docker run -d -p 8080:80 nginx", DOCKER, vec!["docker run -d -p 8082:80 nginx"])]
+    #[case("docker run -p 127.0.0.1:8080:80/tcp nginx", DOCKER, vec!["docker run -p 127.0.0.1:8082:80/tcp nginx"])]
+    #[case("podman run --publish=8080:80 -p 8443:443 nginx", PODMAN, vec!["podman run --publish=8082:80 -p 8443:443 nginx"])]
+    #[case("podman run --publish 8080:80 nginx", PODMAN, vec!["podman run --publish 8082:80 nginx"])]
+    #[case("docker run -p8080:80 -p8443:443 nginx", DOCKER, vec!["docker run -p8082:80 -p8443:443 nginx"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = FakeContext::new().with_used_port(8080).with_used_port(8081);
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
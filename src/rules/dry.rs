@@ -1,4 +1,4 @@
-use super::Rule;
+use super::{utils::join_script, BuiltinRule};
 use crate::{cli::command::CrabCommand, shell::Shell};
 
 pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
@@ -6,11 +6,11 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
 }
 
 pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    vec![command.script_parts[1..].join(" ")]
+    vec![join_script(system_shell, &command.script_parts[1..])]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "dry".to_owned(),
         None,
         Some(900),
@@ -38,6 +38,7 @@ mod tests {
     #[rstest]
     #[case("cd cd foo", "", vec!["cd foo"])]
     #[case("git git push origin/master", "", vec!["git push origin/master"])]
+    #[case("git git commit -m \"it's done\"", "", vec![r"git commit -m 'it'\''s done'"])]
     fn test_get_new_command(
         #[case] command: &str,
         #[case] stdout: &str,
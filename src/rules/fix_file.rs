@@ -1,7 +1,6 @@
-use super::Rule;
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::BuiltinRule;
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use regex::{Captures, Regex};
-use std::env;
 use std::path::Path;
 use std::sync::OnceLock;
 
@@ -50,11 +49,11 @@ struct FileMatch {
 }
 
 /// Searches the output for a file path and line number that exists on the filesystem.
-fn search(output: &str) -> Option<Captures<'_>> {
+fn search<'a>(output: &'a str, context: &dyn Context) -> Option<Captures<'a>> {
     for regex in get_patterns() {
         if let Some(captures) = regex.captures(output) {
             if let Some(file_match) = captures.name("file") {
-                if Path::new(file_match.as_str()).is_file() {
+                if context.is_file(Path::new(file_match.as_str())) {
                     return Some(captures);
                 }
             }
@@ -63,22 +62,30 @@ fn search(output: &str) -> Option<Captures<'_>> {
     None
 }
 
-pub fn match_rule(command: &mut CrabCommand, _system_shell: Option<&dyn Shell>) -> bool {
-    if env::var("EDITOR").is_err() {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    _system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    if context.env_var("EDITOR").is_none() {
         return false;
     }
     if let Some(output) = &command.output {
-        search(output).is_some()
+        search(output, context).is_some()
     } else {
         false
     }
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     if let Some(output) = &command.output {
-        if let Some(captures) = search(output) {
-            if let (Ok(editor), Some(file), Some(line)) = (
-                env::var("EDITOR"),
+        if let Some(captures) = search(output, context) {
+            if let (Some(editor), Some(file), Some(line)) = (
+                context.env_var("EDITOR"),
                 captures.name("file"),
                 captures.name("line"),
             ) {
@@ -92,8 +99,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     vec![]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "fix_file".to_owned(),
         None,
         None,
@@ -108,11 +115,9 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
-    use std::env;
-    use std::fs;
-    use tempfile::tempdir;
 
     #[rstest]
     #[case(
@@ -147,24 +152,16 @@ mod tests {
         #[case] line: &str,
         #[case] output: &str,
     ) {
-        let temp_dir = tempdir().unwrap();
-        env::set_current_dir(temp_dir.path()).unwrap();
-
-        let file_path = temp_dir.path().join(file);
-        if let Some(parent) = file_path.parent() {
-            if !parent.is_dir() {
-                fs::create_dir_all(parent).unwrap();
-            }
-        }
-        fs::File::create(&file_path).unwrap();
+        let context = FakeContext::new()
+            .with_file(file, b"")
+            .with_env("EDITOR", "dummy_editor");
 
         let modified_output = output.replace("{file}", file);
         let mut command = CrabCommand::new(script.to_string(), Some(modified_output), None);
 
         // Test match
-        env::set_var("EDITOR", "dummy_editor");
         assert!(
-            match_rule(&mut command, None),
+            match_rule(&mut command, None, &context),
             "Match failed for script: '{script}'"
         );
 
@@ -172,18 +169,20 @@ mod tests {
         let system_shell = Bash {};
         let expected_cmd = format!("dummy_editor {file} +{line} && {script}");
         assert_eq!(
-            get_new_command(&mut command, Some(&system_shell)),
+            get_new_command(&mut command, Some(&system_shell), &context),
             vec![expected_cmd]
         );
-
-        env::remove_var("EDITOR");
     }
 
     #[test]
     fn test_no_editor() {
-        let mut command = CrabCommand::new("gcc a.c".to_string(), Some("...".to_string()), None);
-        env::remove_var("EDITOR");
-        assert!(!match_rule(&mut command, None));
+        let mut command = CrabCommand::new(
+            "gcc a.c".to_string(),
+            Some("a.c:3:1: error...".to_string()),
+            None,
+        );
+        let context = FakeContext::new().with_file("a.c", b"");
+        assert!(!match_rule(&mut command, None, &context));
     }
 
     #[test]
@@ -193,9 +192,8 @@ mod tests {
             Some("a.c:3:1: error...".to_string()),
             None,
         );
-        env::set_var("EDITOR", "dummy_editor");
-        // Don't create the file, so Path::is_file() will be false
-        assert!(!match_rule(&mut command, None));
-        env::remove_var("EDITOR");
+        // Don't create the file, so `is_file` will be false
+        let context = FakeContext::new().with_env("EDITOR", "dummy_editor");
+        assert!(!match_rule(&mut command, None, &context));
     }
 }
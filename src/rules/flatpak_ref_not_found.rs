@@ -0,0 +1,146 @@
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+/// A ref flatpak could not find, and where it looked for it.
+enum MissingRef {
+    /// Not in the remotes, e.g. `Nothing matches org.gimp.GIMq in remote flathub`.
+    Remote(String),
+    /// Not installed, e.g. `app/org.gimp.GIMq/x86_64/master not installed`.
+    Installed(String),
+}
+
+fn get_missing_ref(output: &str) -> Option<MissingRef> {
+    let remote = Regex::new(r"Nothing matches (\S+) in ").unwrap();
+    let installed = Regex::new(r"error: (?:app/)?([^/\s]+)/\S* not installed").unwrap();
+    if let Some(caps) = remote.captures(output) {
+        Some(MissingRef::Remote(caps[1].to_owned()))
+    } else {
+        installed
+            .captures(output)
+            .map(|caps| MissingRef::Installed(caps[1].to_owned()))
+    }
+}
+
+fn get_applications(context: &dyn Context, args: &[&str]) -> Vec<String> {
+    context
+        .run("flatpak", args)
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .map(|line| line.trim().to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_ref(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["flatpak"], None)
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let (missing, applications) = match command.output.as_deref().and_then(get_missing_ref) {
+        Some(MissingRef::Remote(missing)) => (
+            missing,
+            get_applications(context, &["remote-ls", "--app", "--columns=application"]),
+        ),
+        Some(MissingRef::Installed(missing)) => (
+            missing,
+            get_applications(context, &["list", "--app", "--columns=application"]),
+        ),
+        None => return vec![],
+    };
+    let applications = applications.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    replace_command(command, &missing, applications)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "flatpak_ref_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NOTHING_MATCHES: &str =
+        "Looking for matches…\nerror: Nothing matches org.gimp.GIMq in remote flathub";
+    const NOT_INSTALLED: &str = "error: app/org.gimp.GIMq/x86_64/master not installed";
+    const NOT_INSTALLED_UNSPECIFIED: &str =
+        "error: org.gimp.GIMq/*unspecified*/*unspecified* not installed";
+
+    #[rstest]
+    #[case("flatpak install flathub org.gimp.GIMq", NOTHING_MATCHES, true)]
+    #[case("flatpak run org.gimp.GIMq", NOT_INSTALLED, true)]
+    #[case("flatpak uninstall org.gimp.GIMq", NOT_INSTALLED_UNSPECIFIED, true)]
+    #[case("flatpak run org.gimp.GIMP", "", false)]
+    #[case("snap run org.gimp.GIMq", NOT_INSTALLED, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "flatpak install flathub org.gimp.GIMq",
+        NOTHING_MATCHES,
+        vec![
+            "flatpak install flathub org.gimp.GIMP",
+            "flatpak install flathub org.gnome.Maps",
+            "flatpak install flathub org.videolan.VLC",
+        ]
+    )]
+    #[case("flatpak run org.gimp.GIMq", NOT_INSTALLED, vec!["flatpak run org.gimp.GIMP"])]
+    #[case(
+        "flatpak uninstall org.gimp.GIMq",
+        NOT_INSTALLED_UNSPECIFIED,
+        vec!["flatpak uninstall org.gimp.GIMP"]
+    )]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = FakeContext::new()
+            .with_command_output(
+                "flatpak remote-ls --app --columns=application",
+                "org.gimp.GIMP\norg.gnome.Maps\norg.videolan.VLC\n",
+            )
+            .with_command_output(
+                "flatpak list --app --columns=application",
+                "org.gimp.GIMP\n",
+            );
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
@@ -0,0 +1,168 @@
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_argument};
+use regex::Regex;
+
+/// Returns the remote flatpak could not find, e.g. `flathub` in
+/// `error: Remote "flathub" not found` or `error: No remote refs found for ‘flathub’`.
+fn get_missing_remote(output: &str) -> Option<String> {
+    let re = Regex::new(r#"(?:Remote ["‘']|No remote refs found (?:similar to |for )‘)([^"’']+)"#)
+        .unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn get_remotes(context: &dyn Context, installation: &str) -> Vec<String> {
+    context
+        .run("flatpak", &["remotes", installation, "--columns=name"])
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .map(|line| line.trim().to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_user_installation(command: &CrabCommand) -> bool {
+    command.script_parts.iter().any(|part| part == "--user")
+}
+
+/// Returns `true` if the remote is only configured in the other installation, i.e. the
+/// system one when `--user` was passed and the user one otherwise.
+fn is_in_other_installation(command: &CrabCommand, context: &dyn Context) -> bool {
+    let Some(remote) = command.output.as_deref().and_then(get_missing_remote) else {
+        return false;
+    };
+    let other_installation = if is_user_installation(command) {
+        "--system"
+    } else {
+        "--user"
+    };
+    get_remotes(context, other_installation).contains(&remote)
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(
+        |command| is_in_other_installation(command, context),
+        command,
+        vec!["flatpak"],
+        Some(1),
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    if is_user_installation(command) {
+        return vec![replace_argument(&command.script, "--user", "--system")];
+    }
+    // `--user` is an option of the subcommand, e.g. `flatpak install --user`
+    let mut parts = command.script_parts.clone();
+    match parts.iter().skip(1).position(|part| !part.starts_with('-')) {
+        Some(index) => {
+            parts.insert(index + 2, "--user".to_owned());
+            vec![join_script(system_shell, &parts)]
+        }
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "flatpak_remote_installation".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const REMOTE_NOT_FOUND: &str = "error: Remote \"flathub\" not found";
+    const NO_REMOTE_REFS: &str = "Looking for matches…\nerror: No remote refs found for ‘flathub’";
+
+    #[rstest]
+    #[case(
+        "flatpak install --user flathub org.gimp.GIMP",
+        REMOTE_NOT_FOUND,
+        "system",
+        true
+    )]
+    #[case(
+        "flatpak install --user flathub org.gimp.GIMP",
+        NO_REMOTE_REFS,
+        "system",
+        true
+    )]
+    #[case(
+        "flatpak install flathub org.gimp.GIMP",
+        REMOTE_NOT_FOUND,
+        "user",
+        true
+    )]
+    #[case(
+        "flatpak install flathub org.gimp.GIMP",
+        REMOTE_NOT_FOUND,
+        "system",
+        false
+    )]
+    #[case(
+        "flatpak install --user flathub org.gimp.GIMP",
+        REMOTE_NOT_FOUND,
+        "user",
+        false
+    )]
+    #[case("flatpak install flathub org.gimp.GIMP", "", "user", false)]
+    fn test_match(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] flathub_installation: &str,
+        #[case] is_match: bool,
+    ) {
+        let context = FakeContext::new().with_command_output(
+            &format!("flatpak remotes --{flathub_installation} --columns=name"),
+            "flathub\n",
+        );
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
+    }
+
+    #[rstest]
+    #[case(
+        "flatpak install --user flathub org.gimp.GIMP",
+        vec!["flatpak install --system flathub org.gimp.GIMP"]
+    )]
+    #[case(
+        "flatpak install flathub org.gimp.GIMP",
+        vec!["flatpak install --user flathub org.gimp.GIMP"]
+    )]
+    #[case(
+        "flatpak -v update flathub",
+        vec!["flatpak -v update --user flathub"]
+    )]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command =
+            CrabCommand::new(command.to_owned(), Some(REMOTE_NOT_FOUND.to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, shell::Shell};
+
+const PATTERNS: [&str; 5] = [
+    "auth login",
+    "bad credentials",
+    "401 unauthorized",
+    "authentication required",
+    "not logged into any",
+];
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        let output = output.to_lowercase();
+        command.script_parts.get(1).map(|s| s.as_str()) != Some("auth")
+            && PATTERNS.iter().any(|pattern| output.contains(pattern))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["gh", "glab"], None)
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    vec![format!("{} auth login", command.script_parts[0])]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "gh_auth_login".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const NOT_LOGGED_IN: &str = "To get started with GitHub CLI, please run:  gh auth login
+Alternatively, populate the GH_TOKEN environment variable with a GitHub API authentication token.";
+    const BAD_CREDENTIALS: &str = "HTTP 401: Bad credentials (https://api.github.com/graphql)
+Try authenticating with:  gh auth login";
+    const GLAB_UNAUTHORIZED: &str = "ERROR: 401 Unauthorized.";
+
+    #[rstest]
+    #[case("gh pr list", NOT_LOGGED_IN, true)]
+    #[case("gh issue create --fill", BAD_CREDENTIALS, true)]
+    #[case("glab mr list", GLAB_UNAUTHORIZED, true)]
+    #[case("gh auth status", NOT_LOGGED_IN, false)]
+    #[case("gh pr list", "no pull requests match your search in crab/shop", false)]
+    #[case("git push", BAD_CREDENTIALS, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("gh pr list", NOT_LOGGED_IN, vec!["gh auth login"])]
+    #[case("glab mr list", GLAB_UNAUTHORIZED, vec!["glab auth login"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -0,0 +1,74 @@
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, shell::Shell};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        // Global flags, like `--repo`, can come before the command
+        command.script_parts[1..].iter().any(|part| part == "pr")
+            && output.contains("a pull request for branch")
+            && output.contains("already exists")
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["gh"], Some(1))
+}
+
+/// Opens the existing pull request of the current branch instead.
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    vec![format!("{} pr view --web", command.script_parts[0])]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "gh_pr_already_exists".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const ALREADY_EXISTS: &str =
+        "a pull request for branch \"fix-typo\" into branch \"main\" already exists:
+https://github.com/crab/shop/pull/42";
+
+    #[rstest]
+    #[case("gh pr create --fill", ALREADY_EXISTS, true)]
+    #[case("gh pr create --title Fix --body ''", ALREADY_EXISTS, true)]
+    #[case("gh --repo crab/shop pr create --fill", ALREADY_EXISTS, true)]
+    #[case("gh pr create --fill", "https://github.com/crab/shop/pull/43", false)]
+    #[case(
+        "gh repo create shop",
+        "GraphQL: Name already exists on this account",
+        false
+    )]
+    #[case("glab mr create --fill", ALREADY_EXISTS, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[test]
+    fn test_get_new_command() {
+        let mut command = CrabCommand::new(
+            "gh pr create --fill".to_owned(),
+            Some(ALREADY_EXISTS.to_owned()),
+            None,
+        );
+        assert_eq!(
+            get_new_command(&mut command, None),
+            vec!["gh pr view --web"]
+        );
+    }
+}
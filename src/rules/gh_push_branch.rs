@@ -0,0 +1,61 @@
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, shell::Shell};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("must first push the current branch")
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["gh"], None)
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    vec![system_shell
+        .unwrap()
+        .and(vec!["git push -u origin HEAD", &command.script])]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "gh_push_branch".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::shell::Bash;
+    use rstest::rstest;
+
+    const NOT_PUSHED: &str =
+        "aborted: you must first push the current branch to a remote, or use the --head flag";
+
+    #[rstest]
+    #[case("gh pr create --fill", NOT_PUSHED, true)]
+    #[case("gh pr create --fill", "https://github.com/crab/shop/pull/43", false)]
+    #[case("git push", NOT_PUSHED, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("gh pr create --fill", vec!["git push -u origin HEAD && gh pr create --fill"])]
+    #[case("gh pr create -t Fix -b ''", vec!["git push -u origin HEAD && gh pr create -t Fix -b ''"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new(command.to_owned(), Some(NOT_PUSHED.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, Some(&system_shell)), expected);
+    }
+}
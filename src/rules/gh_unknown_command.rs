@@ -0,0 +1,140 @@
+use super::{
+    utils::{
+        cobra::{get_command_suggestions, get_unknown_command},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_argument};
+
+/// Top-level commands of `gh`, used when it does not suggest any itself.
+const COMMANDS: [&str; 27] = [
+    "alias",
+    "api",
+    "attestation",
+    "auth",
+    "browse",
+    "cache",
+    "codespace",
+    "completion",
+    "config",
+    "extension",
+    "gist",
+    "gpg-key",
+    "issue",
+    "label",
+    "org",
+    "pr",
+    "project",
+    "release",
+    "repo",
+    "ruleset",
+    "run",
+    "search",
+    "secret",
+    "ssh-key",
+    "status",
+    "variable",
+    "workflow",
+];
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some_and(|unknown| command.script_parts.contains(&unknown))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["gh"], Some(1))
+}
+
+/// gh suggests commands for its subcommands too, e.g. `list` for `gh pr lsit`, so the list
+/// of top-level commands is only used when it has no suggestion.
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    let Some(output) = &command.output else {
+        return vec![];
+    };
+    let Some(unknown) = get_unknown_command(output) else {
+        return vec![];
+    };
+    let commands: &[&str] = if command.script_parts.get(1) == Some(&unknown) {
+        &COMMANDS
+    } else {
+        &[]
+    };
+    get_command_suggestions(output, &unknown, commands)
+        .iter()
+        .map(|new| replace_argument(&command.script, &unknown, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "gh_unknown_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const RPO: &str = "unknown command \"rpo\" for \"gh\"
+
+Did you mean this?
+\trepo
+
+Usage:  gh <command> <subcommand> [flags]
+
+Available commands:
+  alias
+  api
+  auth
+  browse";
+    const PR_LSIT: &str = "unknown command \"lsit\" for \"gh pr\"
+
+Did you mean this?
+\tlist
+
+Usage:  gh pr <command> [flags]
+
+Available commands:
+  checkout
+  checks
+  close";
+    const ISUE: &str = "unknown command \"isue\" for \"gh\"
+
+Usage:  gh <command> <subcommand> [flags]";
+
+    #[rstest]
+    #[case("gh rpo clone cli/cli", RPO, true)]
+    #[case("gh pr lsit --state open", PR_LSIT, true)]
+    #[case("gh repo clone cli/cli", "", false)]
+    #[case("glab rpo clone cli/cli", RPO, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("gh rpo clone cli/cli", RPO, vec!["gh repo clone cli/cli"])]
+    #[case("gh pr lsit --state open", PR_LSIT, vec!["gh pr list --state open"])]
+    #[case("gh isue list", ISUE, vec!["gh issue list"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -1,31 +1,26 @@
 use crate::{
     cli::command::CrabCommand,
+    context::Context,
     rules::{
-        utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-        Rule,
+        utils::{
+            git::{get_new_command_with_git_support, match_rule_with_git_support},
+            quote,
+        },
+        BuiltinRule,
     },
     shell::Shell,
 };
 use regex::Regex;
-use shlex::Quoter; // Import the Quoter
 use std::path::Path;
 
-fn get_missing_file(command: &CrabCommand, path_exists: Option<bool>) -> Option<String> {
+fn get_missing_file(command: &CrabCommand, context: &dyn Context) -> Option<String> {
     if let Some(stdout) = &command.output {
         let re = Regex::new(r"error: pathspec '([^']*)' did not match any file\(s\) known to git.")
             .unwrap();
 
         if let Some(captures) = re.captures(stdout) {
             let path = &captures[1];
-            if path.is_empty() {
-                None
-            } else if let Some(path_exists) = path_exists {
-                if path_exists {
-                    Some(path.to_owned())
-                } else {
-                    None
-                }
-            } else if Path::new(&path).exists() {
+            if !path.is_empty() && context.exists(Path::new(path)) {
                 Some(path.to_owned())
             } else {
                 None
@@ -38,52 +33,49 @@ fn get_missing_file(command: &CrabCommand, path_exists: Option<bool>) -> Option<
     }
 }
 
-fn mockable_match_rule(command: &CrabCommand, path_exists: Option<bool>) -> bool {
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(stdout) = &command.output {
         stdout.contains("did not match any file(s) known to git.")
-            && get_missing_file(command, path_exists).is_some()
+            && get_missing_file(command, context).is_some()
     } else {
         false
     }
 }
 
-fn auxiliary_match_rule(command: &CrabCommand) -> bool {
-    mockable_match_rule(command, None)
-}
-
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    match_rule_with_git_support(auxiliary_match_rule, command)
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_git_support(|command| auxiliary_match_rule(command, context), command)
 }
 
-fn mockable_get_new_command(
+fn auxiliary_get_new_command(
     command: &CrabCommand,
     system_shell: Option<&dyn Shell>,
-    path_exists: Option<bool>,
+    context: &dyn Context,
 ) -> Vec<String> {
-    let missing_file = get_missing_file(command, path_exists).unwrap_or_default();
-    // Use the shlex Quoter to handle spaces and special characters
-    let quoter = Quoter::new();
-    let quoted_missing_file = quoter.quote(&missing_file).unwrap_or_default();
-
-    let str_git_add = format!("git add -- {quoted_missing_file}");
+    let missing_file = get_missing_file(command, context).unwrap_or_default();
+    let str_git_add = format!("git add -- {}", quote(system_shell, &missing_file));
     vec![system_shell
         .unwrap()
         .and(vec![&str_git_add, &command.script])]
 }
 
-fn auxiliary_get_new_command(
-    command: &CrabCommand,
+pub fn get_new_command(
+    command: &mut CrabCommand,
     system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
 ) -> Vec<String> {
-    mockable_get_new_command(command, system_shell, None)
-}
-
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
+    get_new_command_with_git_support(
+        |command, system_shell| auxiliary_get_new_command(command, system_shell, context),
+        command,
+        system_shell,
+    )
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "git_add".to_owned(),
         None,
         None,
@@ -96,8 +88,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{mockable_get_new_command, mockable_match_rule};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -117,8 +110,12 @@ mod tests {
         } else {
             "".to_string()
         };
-        let command = CrabCommand::new(script.to_owned(), Some(stdout), None);
-        assert_eq!(mockable_match_rule(&command, Some(path_exists)), expected);
+        let mut command = CrabCommand::new(script.to_owned(), Some(stdout), None);
+        let mut context = FakeContext::new();
+        if path_exists {
+            context = context.with_file(target, b"");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), expected);
     }
 
     #[rstest]
@@ -141,12 +138,13 @@ mod tests {
     fn test_get_new_command(#[case] script: &str, #[case] target: &str, #[case] expected: &str) {
         let stdout = format!("error: pathspec '{target}' did not match any file(s) known to git.");
         let system_shell = Bash {};
-        let command = CrabCommand::new(script.to_owned(), Some(stdout), None);
+        let mut command = CrabCommand::new(script.to_owned(), Some(stdout), None);
+        let context = FakeContext::new().with_file(target, b"");
 
         // The assertion now correctly expects single quotes from shlex in the `git add`
         // part and preserves the original double quotes in the `git commit` part.
         assert_eq!(
-            mockable_get_new_command(&command, Some(&system_shell), Some(true))[0],
+            get_new_command(&mut command, Some(&system_shell), &context)[0],
             expected
         );
     }
@@ -1,4 +1,4 @@
-use super::{utils::git::get_new_command_with_git_support, Rule};
+use super::{utils::git::get_new_command_with_git_support, BuiltinRule};
 use crate::{
     cli::command::CrabCommand, rules::utils::git::match_rule_with_git_support, shell::Shell,
 };
@@ -44,8 +44,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_branch_0flag".to_owned(),
         None,
         None,
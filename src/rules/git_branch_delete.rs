@@ -3,7 +3,7 @@ use crate::{
     cli::command::CrabCommand, rules::utils::git::match_rule_with_git_support, shell::Shell,
 };
 
-use super::{utils::git::get_new_command_with_git_support, Rule};
+use super::{utils::git::get_new_command_with_git_support, BuiltinRule};
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     if let Some(stdout) = &command.output {
@@ -29,8 +29,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_branch_delete".to_owned(),
         None,
         None,
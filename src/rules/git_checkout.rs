@@ -1,25 +1,16 @@
-use super::{utils::git::get_new_command_with_git_support, Rule};
+use super::{utils::git::get_new_command_with_git_support, BuiltinRule};
 use crate::utils::replace_argument;
 use crate::{
-    cli::command::CrabCommand, rules::utils::git::match_rule_with_git_support, shell::Shell,
-    utils::get_closest,
+    cli::command::CrabCommand, context::Context, rules::utils::git::match_rule_with_git_support,
+    shell::Shell, utils::get_closest,
 };
 use regex::Regex;
 
-use std::process::Command;
-use std::str;
-
-fn get_branches(mock_output: Option<&str>) -> Vec<String> {
-    let mut stdout: String;
-    if let Some(mock_output_str) = mock_output {
-        stdout = mock_output_str.to_owned();
-    } else {
-        let output = Command::new("git")
-            .args(["branch", "-a", "--no-color", "--no-column"])
-            .output()
-            .expect("Failed to execute command");
-        stdout = str::from_utf8(&output.stdout).unwrap().to_owned();
-    }
+fn get_branches(context: &dyn Context) -> Vec<String> {
+    let stdout = context
+        .run("git", &["branch", "-a", "--no-color", "--no-column"])
+        .map(|output| output.stdout)
+        .unwrap_or_default();
 
     let mut branches = Vec::new();
 
@@ -50,14 +41,18 @@ fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_git_support(auxiliary_match_rule, command)
 }
 
-fn mockable_get_new_command(
+fn auxiliary_get_new_command(
     command: &CrabCommand,
     system_shell: Option<&dyn Shell>,
-    mock_output: Option<&str>,
+    context: &dyn Context,
 ) -> Vec<String> {
     if let Some(stdout) = &command.output {
         let re = Regex::new(r"error: pathspec '([^']*)' did not match any file\(s\) known to git")
@@ -65,7 +60,7 @@ fn mockable_get_new_command(
         if let Some(caps) = re.captures(stdout) {
             let missing_file = caps.get(1).map_or("", |m| m.as_str());
 
-            let branches = get_branches(mock_output);
+            let branches = get_branches(context);
             let branches: Vec<&str> = branches.iter().map(|s| s.as_str()).collect();
             let closest_branch = get_closest(missing_file, &branches, None, Some(false));
 
@@ -98,19 +93,20 @@ fn mockable_get_new_command(
     }
 }
 
-fn auxiliary_get_new_command(
-    command: &CrabCommand,
+pub fn get_new_command(
+    command: &mut CrabCommand,
     system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
 ) -> Vec<String> {
-    mockable_get_new_command(command, system_shell, None)
-}
-
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
+    get_new_command_with_git_support(
+        |command, system_shell| auxiliary_get_new_command(command, system_shell, context),
+        command,
+        system_shell,
+    )
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "git_checkout".to_owned(),
         None,
         None,
@@ -123,12 +119,16 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_branches, match_rule, mockable_get_new_command};
+    use super::{get_branches, get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
 
     use rstest::rstest;
-    use std::str;
+
+    fn context(branches: &str) -> FakeContext {
+        FakeContext::new().with_command_output("git branch -a --no-color --no-column", branches)
+    }
 
     fn did_not_match(target: &str, did_you_forget: bool) -> String {
         let mut error =
@@ -144,7 +144,7 @@ mod tests {
     #[case("git commit unknown", did_not_match("unknown", false))]
     fn test_match(#[case] command: &str, #[case] output: String) {
         let crab_command = &mut CrabCommand::new(command.to_owned(), Some(output), None);
-        assert!(match_rule(crab_command, None));
+        assert!(match_rule(crab_command, None, &FakeContext::new()));
     }
 
     #[rstest]
@@ -153,7 +153,7 @@ mod tests {
     #[case("git commit known", "")]
     fn test_not_match(#[case] command: &str, #[case] output: String) {
         let crab_command = &mut CrabCommand::new(command.to_owned(), Some(output), None);
-        assert!(!match_rule(crab_command, None));
+        assert!(!match_rule(crab_command, None, &FakeContext::new()));
     }
 
     #[rstest]
@@ -169,7 +169,7 @@ mod tests {
     #[case("* master\n  just-another-branch", vec!["master", "just-another-branch"])]
     #[case("* master\n  remotes/origin/master\n  just-another-branch", vec!["master", "master", "just-another-branch"])]
     fn test_get_branches(#[case] branches: String, #[case] branch_list: Vec<&str>) {
-        assert_eq!(get_branches(Some(&branches)), branch_list)
+        assert_eq!(get_branches(&context(&branches)), branch_list)
     }
 
     #[rstest]
@@ -208,7 +208,7 @@ mod tests {
         let crab_command = &mut CrabCommand::new(command.to_owned(), Some(output), None);
         let system_shell = Bash {};
         assert_eq!(
-            mockable_get_new_command(crab_command, Some(&system_shell), Some(&branches)),
+            get_new_command(crab_command, Some(&system_shell), &context(&branches)),
             new_command
         );
     }
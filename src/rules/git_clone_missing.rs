@@ -1,14 +1,16 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use regex::Regex;
 
-use which::which;
+use super::BuiltinRule;
 
-use super::Rule;
-
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     let stdout = command.output.clone().unwrap_or("".to_owned());
     if command.script_parts.len() != 1
-        || which(&command.script_parts[0]).is_ok()
+        || context.which(&command.script_parts[0]).is_some()
         || !(stdout.contains("No such file or directory")
             || stdout.contains("not found")
             || stdout.contains("is not recognised as"))
@@ -22,12 +24,16 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
     }
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     vec![format!("git clone {}", command.script)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "git_clone_missing".to_owned(),
         None,
         None,
@@ -42,11 +48,13 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use crate::{parameterized_get_new_command_tests, parameterized_match_rule_tests};
 
     parameterized_match_rule_tests! {
         match_rule,
+        context: FakeContext::new().with_program("notacommand"),
         match_rule_01: ("https://github.com/nvbn/thefuck.git", "No such file or directory", true),
         match_rule_02: ("https://github.com/nvbn/thefuck.git", "not found", true),
         match_rule_03: ("https://github.com/nvbn/thefuck.git", "is not recognised as", true),
@@ -100,6 +108,7 @@ mod tests {
 
     parameterized_get_new_command_tests! {
         get_new_command,
+        context: FakeContext::new(),
         get_new_command_01: ("https://github.com/nvbn/thefuck.git", "No such file or directory", "git clone https://github.com/nvbn/thefuck.git"),
         get_new_command_02: ("https://github.com/nvbn/thefuck.git", "not found", "git clone https://github.com/nvbn/thefuck.git"),
         get_new_command_03: ("https://github.com/nvbn/thefuck.git", "is not recognised as", "git clone https://github.com/nvbn/thefuck.git"),
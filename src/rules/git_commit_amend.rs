@@ -2,7 +2,7 @@ use crate::{
     cli::command::CrabCommand,
     rules::{
         utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-        Rule,
+        BuiltinRule,
     },
     shell::Shell,
 };
@@ -26,8 +26,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_commit_amend".to_owned(),
         None,
         None,
@@ -1,4 +1,7 @@
-use super::{utils::git::get_new_command_with_git_support, Rule};
+use super::{
+    utils::{git::get_new_command_with_git_support, join_script},
+    BuiltinRule,
+};
 use crate::utils::replace_argument;
 use crate::{
     cli::command::CrabCommand, rules::utils::git::match_rule_with_git_support, shell::Shell,
@@ -35,7 +38,7 @@ fn auxiliary_get_new_command(
         } else {
             let mut cmd = command.script_parts.clone();
             cmd.insert(2, "save".to_owned());
-            vec![cmd.join(" ")]
+            vec![join_script(system_shell, &cmd)]
         }
     } else {
         Vec::<String>::new()
@@ -46,8 +49,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_fix_stash".to_owned(),
         None,
         None,
@@ -1,8 +1,11 @@
 use crate::{
     cli::command::CrabCommand,
     rules::{
-        utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-        Rule,
+        utils::{
+            git::{get_new_command_with_git_support, match_rule_with_git_support},
+            join_script,
+        },
+        BuiltinRule,
     },
     shell::Shell,
 };
@@ -50,7 +53,7 @@ fn auxiliary_get_new_command(
             }
         }
 
-        vec![command_parts.join(" ")]
+        vec![join_script(system_shell, &command_parts)]
     } else {
         vec![]
     }
@@ -60,8 +63,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_flag_after_filename".to_owned(),
         None,
         None,
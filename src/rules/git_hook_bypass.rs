@@ -2,7 +2,7 @@ use crate::{
     cli::command::CrabCommand,
     rules::{
         utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-        Rule,
+        BuiltinRule,
     },
     shell::Shell,
     utils::replace_argument,
@@ -44,12 +44,12 @@ pub fn get_new_command(
     get_new_command_with_git_support(auxiliary_get_new_command, command, _system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_hook_bypass".to_owned(),
         None,
         Some(1100),
-        Some(false),
+        None,
         Box::new(match_rule),
         get_new_command,
         None,
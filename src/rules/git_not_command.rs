@@ -1,29 +1,61 @@
 use crate::{
     cli::command::CrabCommand,
-    rules::utils::git::match_rule_with_git_support,
+    context::Context,
+    rules::utils::git::{get_git_commands, match_rule_with_git_support},
     shell::Shell,
-    utils::{get_all_matched_commands, replace_command},
+    utils::{get_all_matched_commands, get_close_matches, replace_command},
 };
 
-use super::{utils::git::get_new_command_with_git_support, Rule};
+use super::{utils::git::get_new_command_with_git_support, BuiltinRule};
 use regex::Regex;
 
-fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+/// Returns the subcommand of a command that was not run, along with the closest git
+/// commands, if git does not know it.
+fn get_unknown_subcommand(
+    command: &CrabCommand,
+    context: &dyn Context,
+) -> Option<(String, Vec<String>)> {
+    let subcommand = command.script_parts.get(1)?;
+    if subcommand.starts_with('-') {
+        return None;
+    }
+    let git_commands = get_git_commands(context);
+    if git_commands.is_empty() || git_commands.contains(subcommand) {
+        return None;
+    }
+    let git_commands = git_commands.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    let matched = get_close_matches(subcommand, &git_commands, None, None)
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    if matched.is_empty() {
+        None
+    } else {
+        Some((subcommand.to_owned(), matched))
+    }
+}
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(stdout) = &command.output {
         stdout.contains(" is not a git command. See 'git --help'.")
             && (stdout.contains("The most similar command") || stdout.contains("Did you mean"))
     } else {
-        false
+        get_unknown_subcommand(command, context).is_some()
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    match_rule_with_git_support(auxiliary_match_rule, command)
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_git_support(|command| auxiliary_match_rule(command, context), command)
 }
 
 fn auxiliary_get_new_command(
     command: &CrabCommand,
     system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
 ) -> Vec<String> {
     if let Some(stdout) = &command.output {
         let re = Regex::new(r"git: '([^']*)' is not a git command").unwrap();
@@ -41,21 +73,35 @@ fn auxiliary_get_new_command(
             broken_cmd,
             matched.iter().map(|s| s.as_str()).collect(),
         )
+    } else if let Some((subcommand, matched)) = get_unknown_subcommand(command, context) {
+        replace_command(
+            command,
+            &subcommand,
+            matched.iter().map(|s| s.as_str()).collect(),
+        )
     } else {
         Vec::<String>::new()
     }
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_with_git_support(
+        |command, system_shell| auxiliary_get_new_command(command, system_shell, context),
+        command,
+        system_shell,
+    )
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "git_not_command".to_owned(),
         None,
         None,
-        None,
+        Some(false),
         Box::new(match_rule),
         get_new_command,
         None,
@@ -66,6 +112,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
     const GIT_NOT_COMMAND: &str = r#"git: 'brnch' is not a git command. See 'git --help'.
@@ -98,7 +145,10 @@ The most similar commands are
     #[case("git branch", GIT_COMMAND.to_owned(), false)]
     fn test_match(#[case] script: &str, #[case] output: String, #[case] is_match: bool) {
         let crab_command = &mut CrabCommand::new(script.to_owned(), Some(output), None);
-        assert_eq!(match_rule(crab_command, None), is_match);
+        assert_eq!(
+            match_rule(crab_command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
@@ -111,6 +161,41 @@ The most similar commands are
         #[case] new_command: Vec<&str>,
     ) {
         let crab_command = &mut CrabCommand::new(script.to_owned(), Some(output), None);
-        assert_eq!(get_new_command(crab_command, None), new_command);
+        assert_eq!(
+            get_new_command(crab_command, None, &FakeContext::new()),
+            new_command
+        );
+    }
+
+    const GIT_HELP_ALL: &str = r#"Main Porcelain Commands
+   branch                  List, create, or delete branches
+   commit                  Record changes to the repository
+   status                  Show the working tree status
+
+Command aliases
+   co                      Alias for 'checkout'
+"#;
+
+    #[rstest]
+    #[case("git brnch", true)]
+    #[case("git stauts -s", true)]
+    #[case("git status", false)]
+    #[case("git co main", false)]
+    #[case("git --version", false)]
+    #[case("git qwertyuiop", false)]
+    #[case("git", false)]
+    fn test_match_without_output(#[case] script: &str, #[case] is_match: bool) {
+        let context = FakeContext::new().with_command_output("git help -a", GIT_HELP_ALL);
+        let crab_command = &mut CrabCommand::new(script.to_owned(), None, None);
+        assert_eq!(match_rule(crab_command, None, &context), is_match);
+    }
+
+    #[rstest]
+    #[case("git brnch", vec!["git branch"])]
+    #[case("git stauts -s", vec!["git status -s"])]
+    fn test_get_new_command_without_output(#[case] script: &str, #[case] new_command: Vec<&str>) {
+        let context = FakeContext::new().with_command_output("git help -a", GIT_HELP_ALL);
+        let crab_command = &mut CrabCommand::new(script.to_owned(), None, None);
+        assert_eq!(get_new_command(crab_command, None, &context), new_command);
     }
 }
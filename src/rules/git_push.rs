@@ -1,7 +1,7 @@
 use crate::{
     cli::command::CrabCommand,
     rules::utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-    rules::Rule,
+    rules::{utils::join_script, BuiltinRule},
     shell::Shell,
     utils::replace_argument,
 };
@@ -61,7 +61,7 @@ fn auxiliary_get_new_command(
             .trim()
             .to_string();
         vec![replace_argument(
-            &command_parts.join(" "),
+            &join_script(system_shell, &command_parts),
             "push",
             &format!("push {arguments}"),
         )]
@@ -74,8 +74,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_push".to_owned(),
         None,
         None,
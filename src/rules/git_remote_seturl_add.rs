@@ -1,7 +1,7 @@
 use crate::{
     cli::command::CrabCommand,
     rules::utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-    rules::Rule,
+    rules::BuiltinRule,
     shell::Shell,
     utils::replace_argument,
 };
@@ -29,8 +29,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_remote_seturl_add".to_owned(),
         None,
         None,
@@ -1,8 +1,11 @@
 use crate::{
     cli::command::CrabCommand,
     rules::{
-        utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-        Rule,
+        utils::{
+            git::{get_new_command_with_git_support, match_rule_with_git_support},
+            join_script,
+        },
+        BuiltinRule,
     },
     shell::Shell,
 };
@@ -29,15 +32,15 @@ fn auxiliary_get_new_command(
     if let Some(index) = command_parts.iter().position(|r| r == "rm") {
         command_parts.insert(index + 1, "-r".to_string());
     }
-    vec![command_parts.join(" ")]
+    vec![join_script(system_shell, &command_parts)]
 }
 
 pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_rm_recursive".to_owned(),
         None,
         None,
@@ -72,6 +75,7 @@ mod tests {
     #[rstest]
     #[case("git rm foo", "fatal: not removing 'foo' recursively without -r", vec!["git rm -r foo"])]
     #[case("git rm foo bar", "fatal: not removing 'foo bar' recursively without -r", vec!["git rm -r foo bar"])]
+    #[case("git rm 'foo bar'", "fatal: not removing 'foo bar' recursively without -r", vec!["git rm -r 'foo bar'"])]
     fn test_get_new_command(
         #[case] command: &str,
         #[case] stdout: &str,
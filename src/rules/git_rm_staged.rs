@@ -1,8 +1,11 @@
 use crate::{
     cli::command::CrabCommand,
     rules::{
-        utils::git::{get_new_command_with_git_support, match_rule_with_git_support},
-        Rule,
+        utils::{
+            git::{get_new_command_with_git_support, match_rule_with_git_support},
+            join_script,
+        },
+        BuiltinRule,
     },
     shell::Shell,
 };
@@ -27,9 +30,9 @@ fn auxiliary_get_new_command(
     let mut command_parts = command.script_parts.clone();
     if let Some(index) = command_parts.iter().position(|r| r == "rm") {
         command_parts.insert(index + 1, "--cached".to_string());
-        let mut command_list = vec![command_parts.join(" ")];
+        let mut command_list = vec![join_script(system_shell, &command_parts)];
         command_parts[index + 1] = "-f".to_string();
-        command_list.push(command_parts.join(" "));
+        command_list.push(join_script(system_shell, &command_parts));
         return command_list;
     }
     vec![]
@@ -39,8 +42,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     get_new_command_with_git_support(auxiliary_get_new_command, command, system_shell)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "git_rm_staged".to_owned(),
         None,
         None,
@@ -0,0 +1,121 @@
+use super::{
+    utils::{
+        cobra::{get_command_suggestions, get_unknown_command},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_argument};
+
+/// Top-level commands of `glab`, used when it does not suggest any itself.
+const COMMANDS: [&str; 27] = [
+    "alias",
+    "api",
+    "auth",
+    "changelog",
+    "check-update",
+    "ci",
+    "cluster",
+    "completion",
+    "config",
+    "deploy-key",
+    "duo",
+    "incident",
+    "issue",
+    "job",
+    "label",
+    "mr",
+    "release",
+    "repo",
+    "schedule",
+    "securefile",
+    "snippet",
+    "ssh-key",
+    "stack",
+    "token",
+    "user",
+    "variable",
+    "version",
+];
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some_and(|unknown| command.script_parts.contains(&unknown))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["glab"], Some(1))
+}
+
+/// glab suggests commands for its subcommands too, e.g. `list` for `glab mr lst`, so the
+/// list of top-level commands is only used when it has no suggestion.
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    let Some(output) = &command.output else {
+        return vec![];
+    };
+    let Some(unknown) = get_unknown_command(output) else {
+        return vec![];
+    };
+    let commands: &[&str] = if command.script_parts.get(1) == Some(&unknown) {
+        &COMMANDS
+    } else {
+        &[]
+    };
+    get_command_suggestions(output, &unknown, commands)
+        .iter()
+        .map(|new| replace_argument(&command.script, &unknown, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "glab_unknown_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const MR_LST: &str = "Error: unknown command \"lst\" for \"glab mr\"
+
+Did you mean this?
+\tlist
+
+Run 'glab mr --help' for usage.";
+    const ISUE: &str = "Error: unknown command \"isue\" for \"glab\"
+Run 'glab --help' for usage.";
+
+    #[rstest]
+    #[case("glab mr lst --assignee=@me", MR_LST, true)]
+    #[case("glab isue list", ISUE, true)]
+    #[case("glab mr list", "", false)]
+    #[case("gh mr lst", MR_LST, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("glab mr lst --assignee=@me", MR_LST, vec!["glab mr list --assignee=@me"])]
+    #[case("glab isue list", ISUE, vec!["glab issue list", "glab user list"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -1,49 +1,46 @@
-use super::{utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use std::path::Path;
-use which::which;
 
-fn gradlew_exists() -> bool {
-    Path::new("gradlew").exists()
-}
-
-fn is_terminal_command(terminal_command: &str) -> bool {
-    which(terminal_command).is_ok()
-}
-
-fn auxiliary_match_rule<F, G>(
-    command: &CrabCommand,
-    fn_is_terminal_command: F,
-    fn_file_exists: G,
-) -> bool
-where
-    F: Fn(&str) -> bool,
-    G: Fn() -> bool,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
-        !fn_is_terminal_command(&command.script_parts[0])
+        context.which(&command.script_parts[0]).is_none()
             && output.contains("not found")
-            && fn_file_exists()
+            && context.exists(Path::new("gradlew"))
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(
-        |command| auxiliary_match_rule(command, is_terminal_command, gradlew_exists),
+        |command| auxiliary_match_rule(command, context),
         command,
         vec!["gradle"],
         None,
     )
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    vec![format!("./gradlew {}", command.script_parts[1..].join(" "))]
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    vec![format!(
+        "./gradlew {}",
+        join_script(system_shell, &command.script_parts[1..])
+    )]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "gradle_wrapper".to_owned(),
         None,
         None,
@@ -56,9 +53,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{auxiliary_match_rule, get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
-    use crate::rules::utils::match_rule_with_is_app;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -76,15 +73,14 @@ mod tests {
         #[case] is_match: bool,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            match_rule_with_is_app(
-                |command| auxiliary_match_rule(command, |s| is_terminal_command, || file_exists),
-                &command,
-                vec!["gradle"],
-                None,
-            ),
-            is_match
-        );
+        let mut context = FakeContext::new();
+        if is_terminal_command {
+            context = context.with_program("gradle");
+        }
+        if file_exists {
+            context = context.with_executable_file("gradlew");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
     }
 
     #[rstest]
@@ -98,6 +94,9 @@ mod tests {
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
@@ -1,25 +1,25 @@
-use super::{utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use std::path::Path;
 
-fn get_actual_file(parts: &[String]) -> Option<&String> {
+fn get_actual_file<'a>(parts: &'a [String], context: &dyn Context) -> Option<&'a String> {
     for part in parts.iter().skip(1) {
         let part_path = Path::new(part);
-        if part_path.is_file() || part_path.is_dir() {
+        if context.is_file(part_path) || context.is_dir(part_path) {
             return Some(part);
         }
     }
     None
 }
 
-fn auxiliary_match_rule<F>(command: &CrabCommand, fn_get_actual_file: F) -> bool
-where
-    F: Fn(&[String]) -> Option<&String>,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         if command.script_parts.len() > 1 {
             output.contains(": No such file or directory")
-                && fn_get_actual_file(&command.script_parts).is_some()
+                && get_actual_file(&command.script_parts, context).is_some()
         } else {
             false
         }
@@ -28,40 +28,30 @@ where
     }
 }
 
-fn mockable_match_rule<F>(
+pub fn match_rule(
     command: &mut CrabCommand,
-    fn_get_actual_file: &F,
     system_shell: Option<&dyn Shell>,
-) -> bool
-where
-    F: Fn(&[String]) -> Option<&String>,
-{
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(
-        |command| auxiliary_match_rule(command, fn_get_actual_file),
+        |command| auxiliary_match_rule(command, context),
         command,
         vec!["grep", "egrep"],
         None,
     )
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    mockable_match_rule(command, &get_actual_file, system_shell)
-}
-
-fn mockable_get_new_command<F>(
+pub fn get_new_command(
     command: &mut CrabCommand,
-    fn_get_actual_file: &F,
     system_shell: Option<&dyn Shell>,
-) -> Vec<String>
-where
-    F: Fn(&[String]) -> Option<&String>,
-{
+    context: &dyn Context,
+) -> Vec<String> {
     if command.script_parts.len() > 1 {
-        if let Some(actual_file) = fn_get_actual_file(&command.script_parts) {
+        if let Some(actual_file) = get_actual_file(&command.script_parts, context) {
             let mut parts = command.script_parts.clone();
             parts.remove(parts.iter().position(|x| x == actual_file).unwrap());
             parts.push(actual_file.to_string());
-            vec![parts.join(" ")]
+            vec![join_script(system_shell, &parts)]
         } else {
             vec![]
         }
@@ -69,12 +59,9 @@ where
         vec![]
     }
 }
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    mockable_get_new_command(command, &get_actual_file, system_shell)
-}
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "grep_arguments_order".to_owned(),
         None,
         None,
@@ -87,19 +74,13 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{mockable_get_new_command, mockable_match_rule};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
-    use std::path::Path;
 
-    fn mocked_get_actual_file(parts: &[String]) -> Option<&String> {
-        for part in parts.iter().skip(1) {
-            let part_path = Path::new(part);
-            if !part.starts_with('-') || part_path.is_dir() {
-                return Some(part);
-            }
-        }
-        None
+    fn context() -> FakeContext {
+        FakeContext::new().with_file("test.py", b"")
     }
 
     #[rstest]
@@ -114,10 +95,7 @@ mod tests {
     #[case("egrep -lir test .", "", false)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            mockable_match_rule(&mut command, &mocked_get_actual_file, None),
-            is_match
-        );
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
     }
 
     #[rstest]
@@ -133,9 +111,6 @@ mod tests {
         #[case] expected: Vec<&str>,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            mockable_get_new_command(&mut command, &mocked_get_actual_file, None),
-            expected
-        );
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
     }
 }
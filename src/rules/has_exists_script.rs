@@ -1,30 +1,28 @@
-use super::{get_new_command_without_sudo, match_rule_without_sudo, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{get_new_command_without_sudo, match_rule_without_sudo, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use std::path::Path;
 
-fn file_exists(path: &str) -> bool {
-    Path::new(path).exists()
-}
-
-fn auxiliary_match_rule<F>(command: &CrabCommand, fn_file_exists: F) -> bool
-where
-    F: Fn(&str) -> bool,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         output.contains("command not found")
             && command
                 .script_parts
                 .first()
-                .is_some_and(|s| fn_file_exists(s))
+                .is_some_and(|s| context.exists(Path::new(s)))
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_without_sudo(
-        |command| auxiliary_match_rule(command, file_exists),
+        |command| auxiliary_match_rule(command, context),
         command,
+        context,
     )
 }
 
@@ -32,12 +30,16 @@ pub fn auxiliary_get_new_command(command: &CrabCommand) -> Vec<String> {
     vec![format!("./{}", command.script)]
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_without_sudo(auxiliary_get_new_command, command)
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(auxiliary_get_new_command, command, context)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "has_exists_script".to_owned(),
         None,
         None,
@@ -50,9 +52,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{auxiliary_match_rule, get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
-    use crate::rules::match_rule_without_sudo;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -67,13 +69,11 @@ mod tests {
         #[case] is_match: bool,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            match_rule_without_sudo(
-                |command| auxiliary_match_rule(command, |s| file_exists),
-                &mut command
-            ),
-            is_match
-        );
+        let mut context = FakeContext::new();
+        if file_exists {
+            context = context.with_executable_file("main");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
     }
 
     #[rstest]
@@ -85,6 +85,9 @@ mod tests {
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
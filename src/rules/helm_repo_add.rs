@@ -0,0 +1,104 @@
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, shell::Shell};
+use regex::Regex;
+
+/// Well-known chart repositories, by the name they are usually added with.
+const REPOSITORIES: [(&str, &str); 10] = [
+    ("argo", "https://argoproj.github.io/argo-helm"),
+    ("bitnami", "https://charts.bitnami.com/bitnami"),
+    ("elastic", "https://helm.elastic.co"),
+    ("grafana", "https://grafana.github.io/helm-charts"),
+    ("hashicorp", "https://helm.releases.hashicorp.com"),
+    (
+        "ingress-nginx",
+        "https://kubernetes.github.io/ingress-nginx",
+    ),
+    ("jetstack", "https://charts.jetstack.io"),
+    (
+        "prometheus-community",
+        "https://prometheus-community.github.io/helm-charts",
+    ),
+    ("traefik", "https://traefik.github.io/charts"),
+    ("vmware-tanzu", "https://vmware-tanzu.github.io/helm-charts"),
+];
+
+/// Returns the URL of the repository helm does not know about, e.g. `bitnami` in
+/// `Error: INSTALLATION FAILED: repo bitnami not found`.
+fn get_missing_repository(command: &CrabCommand) -> Option<(&'static str, &'static str)> {
+    let re = Regex::new(r"repo (\S+) not found").unwrap();
+    let caps = re.captures(command.output.as_deref()?)?;
+    REPOSITORIES.into_iter().find(|(name, _)| *name == &caps[1])
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    get_missing_repository(command).is_some()
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["helm"], Some(1))
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    match get_missing_repository(command) {
+        Some((name, url)) => vec![system_shell.unwrap().and(vec![
+            &format!("helm repo add {name} {url}"),
+            &command.script,
+        ])],
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "helm_repo_add".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::shell::Bash;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "helm install web bitnami/nginx",
+        "Error: INSTALLATION FAILED: repo bitnami not found",
+        true
+    )]
+    #[case(
+        "helm upgrade --install prom prometheus-community/kube-prometheus-stack",
+        "Error: repo prometheus-community not found",
+        true
+    )]
+    #[case(
+        "helm install web acme/nginx",
+        "Error: INSTALLATION FAILED: repo acme not found",
+        false
+    )]
+    #[case("helm install web bitnami/nginx", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("helm install web bitnami/nginx", "Error: INSTALLATION FAILED: repo bitnami not found", vec!["helm repo add bitnami https://charts.bitnami.com/bitnami && helm install web bitnami/nginx"])]
+    #[case("helm show values jetstack/cert-manager", "Error: repo jetstack not found", vec!["helm repo add jetstack https://charts.jetstack.io && helm show values jetstack/cert-manager"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, Some(&system_shell)), expected);
+    }
+}
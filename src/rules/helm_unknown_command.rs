@@ -0,0 +1,102 @@
+use super::{
+    utils::{
+        cobra::{get_command_suggestions, get_unknown_command},
+        kubernetes::HELM_COMMANDS,
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_argument};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some_and(|unknown| command.script_parts.contains(&unknown))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["helm"], Some(1))
+}
+
+/// helm suggests commands for its subcommands too, e.g. `add` for `helm repo ad`, so the
+/// list of top-level commands is only used when it has no suggestion.
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    let Some(output) = &command.output else {
+        return vec![];
+    };
+    let Some(unknown) = get_unknown_command(output) else {
+        return vec![];
+    };
+    let commands: &[&str] = if command.script_parts.get(1) == Some(&unknown) {
+        &HELM_COMMANDS
+    } else {
+        &[]
+    };
+    get_command_suggestions(output, &unknown, commands)
+        .iter()
+        .map(|new| replace_argument(&command.script, &unknown, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "helm_unknown_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const INSTAL: &str = "Error: unknown command \"instal\" for \"helm\"
+
+Did you mean this?
+\tinstall
+
+Run 'helm --help' for usage.";
+    const REPO_AD: &str = "Error: unknown command \"ad\" for \"helm repo\"
+
+Did you mean this?
+\tadd
+
+Run 'helm repo --help' for usage.";
+    const UPGARDE: &str = "Error: unknown command \"upgarde\" for \"helm\"
+Run 'helm --help' for usage.";
+
+    #[rstest]
+    #[case("helm instal web bitnami/nginx", INSTAL, true)]
+    #[case(
+        "helm repo ad bitnami https://charts.bitnami.com/bitnami",
+        REPO_AD,
+        true
+    )]
+    #[case("helm install web bitnami/nginx", "", false)]
+    #[case("kubectl instal", INSTAL, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("helm instal web bitnami/nginx", INSTAL, vec!["helm install web bitnami/nginx"])]
+    #[case("helm repo ad bitnami https://charts.bitnami.com/bitnami", REPO_AD, vec!["helm repo add bitnami https://charts.bitnami.com/bitnami"])]
+    #[case("helm upgarde web bitnami/nginx", UPGARDE, vec!["helm upgrade web bitnami/nginx"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -1,12 +1,17 @@
-use super::Rule;
+use super::BuiltinRule;
 use crate::cli::command::CrabCommand;
+use crate::context::Context;
 use crate::shell::Shell;
 use crate::utils::{get_close_matches, get_valid_history_without_current};
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     !get_close_matches(
         &command.script,
-        get_valid_history_without_current(command, system_shell.unwrap())
+        get_valid_history_without_current(command, system_shell.unwrap(), context)
             .iter()
             .map(|s| s.as_str())
             .collect::<Vec<&str>>()
@@ -17,10 +22,14 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
     .is_empty()
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     get_close_matches(
         &command.script,
-        get_valid_history_without_current(command, system_shell.unwrap())
+        get_valid_history_without_current(command, system_shell.unwrap(), context)
             .iter()
             .map(|s| s.as_str())
             .collect::<Vec<&str>>()
@@ -33,8 +42,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     .collect()
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "history".to_owned(),
         None,
         Some(9999),
@@ -46,3 +55,30 @@ pub fn get_rule() -> Rule {
 }
 
 // priority = 9999
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use crate::shell::Bash;
+
+    #[test]
+    fn test_history() {
+        let system_shell = Bash {};
+        let context = FakeContext::new()
+            .with_program("git")
+            .with_file("/home/crab/.bash_history", b"git status\nls -la\n");
+        let mut command = CrabCommand::new("git stauts".to_owned(), Some("".to_owned()), None);
+        assert!(match_rule(&mut command, Some(&system_shell), &context));
+        assert_eq!(
+            get_new_command(&mut command, Some(&system_shell), &context),
+            vec!["git status"]
+        );
+        assert!(!match_rule(
+            &mut command,
+            Some(&system_shell),
+            &FakeContext::new().with_program("git")
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use super::{
+    get_new_command_without_sudo, match_rule_without_sudo,
+    utils::{
+        join_script, match_rule_with_is_app,
+        systemd::{get_close_units, get_unit_files, is_same_unit},
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+/// A unit given to `journalctl` with `-u`, `--unit` or `--user-unit`.
+struct UnitArgument {
+    /// Position of the argument holding the unit.
+    index: usize,
+    /// Text in front of the unit in that argument, e.g. `--unit=`.
+    option: String,
+    name: String,
+    user: bool,
+}
+
+fn get_unit_argument(script_parts: &[String]) -> Option<UnitArgument> {
+    let user = script_parts.iter().any(|part| part == "--user");
+    for (index, part) in script_parts.iter().enumerate() {
+        let (option, name, is_user_unit) = match part.as_str() {
+            "-u" | "--unit" | "--user-unit" => (
+                "",
+                script_parts.get(index + 1)?.as_str(),
+                part == "--user-unit",
+            ),
+            _ => match part.split_once('=') {
+                Some(("--unit", name)) => ("--unit=", name, false),
+                Some(("--user-unit", name)) => ("--user-unit=", name, true),
+                _ => continue,
+            },
+        };
+        return Some(UnitArgument {
+            index: if option.is_empty() { index + 1 } else { index },
+            option: option.to_owned(),
+            name: name.to_owned(),
+            user: user || is_user_unit,
+        });
+    }
+    None
+}
+
+/// journalctl accepts units that do not exist and only reports that there are no entries.
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
+    if let Some(output) = &command.output {
+        let Some(unit) = get_unit_argument(&command.script_parts) else {
+            return false;
+        };
+        output.contains("-- No entries --")
+            && !unit.name.contains('*')
+            && !get_unit_files(unit.user, context)
+                .iter()
+                .any(|known| is_same_unit(&unit.name, known))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| {
+            match_rule_with_is_app(
+                |command| auxiliary_match_rule(command, context),
+                command,
+                vec!["journalctl"],
+                None,
+            )
+        },
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(
+        |command| {
+            let Some(unit) = get_unit_argument(&command.script_parts) else {
+                return vec![];
+            };
+            get_close_units(&unit.name, unit.user, context)
+                .iter()
+                .map(|new_unit| {
+                    let mut parts = command.script_parts.clone();
+                    parts[unit.index] = format!("{}{new_unit}", unit.option);
+                    join_script(system_shell, &parts)
+                })
+                .collect()
+        },
+        command,
+        context,
+    )
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "journalctl_unit_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNIT_FILES: &str = "\
+cron.service                               enabled         enabled
+nginx.service                              enabled         enabled
+nginx-debug.service                        disabled        enabled
+ssh.socket                                 disabled        enabled
+timers.target                              static          -
+";
+    const USER_UNIT_FILES: &str = "\
+pipewire.service                           enabled         enabled
+syncthing.service                          enabled         enabled
+";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_program("systemctl")
+            .with_command_output(
+                "systemctl list-unit-files --no-legend --no-pager",
+                UNIT_FILES,
+            )
+            .with_command_output(
+                "systemctl --user list-unit-files --no-legend --no-pager",
+                USER_UNIT_FILES,
+            )
+    }
+
+    const NO_ENTRIES: &str = "-- No entries --";
+
+    #[rstest]
+    #[case("journalctl -u ngnix", NO_ENTRIES, true)]
+    #[case("sudo journalctl -f --unit ngnix.service", NO_ENTRIES, true)]
+    #[case("journalctl --unit=crn -b", NO_ENTRIES, true)]
+    #[case("journalctl --user-unit sycnthing", NO_ENTRIES, true)]
+    #[case("journalctl -u nginx", NO_ENTRIES, false)]
+    #[case("journalctl --user -u syncthing", NO_ENTRIES, false)]
+    #[case("journalctl -u 'ngin*'", NO_ENTRIES, false)]
+    #[case("journalctl -u ngnix", "Jan 01 00:00:00 crab nginx[1]: started", false)]
+    #[case("journalctl -b", NO_ENTRIES, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("journalctl -u ngnix", vec!["journalctl -u nginx", "journalctl -u nginx-debug"])]
+    #[case("sudo journalctl -f --unit ngnix.service", vec!["sudo journalctl -f --unit nginx.service", "sudo journalctl -f --unit nginx-debug.service"])]
+    #[case("journalctl --unit=crn -b", vec!["journalctl --unit=cron -b"])]
+    #[case("journalctl --user-unit sycnthing", vec!["journalctl --user-unit syncthing"])]
+    #[case("journalctl --user -u pipewrie", vec!["journalctl --user -u pipewire"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(NO_ENTRIES.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{
+    utils::{
+        join_script,
+        kubernetes::{
+            get_cluster_args, get_missing_resource, get_namespace, run_kubectl, set_flag,
+        },
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+/// Returns the other namespaces with a resource of the kind and name that was not found.
+fn get_namespaces_with_resource(command: &CrabCommand, context: &dyn Context) -> Vec<String> {
+    let Some((kind, name)) = command.output.as_deref().and_then(get_missing_resource) else {
+        return vec![];
+    };
+    if kind == "namespaces" {
+        return vec![];
+    }
+    let current = get_namespace(&command.script_parts);
+    let mut args = get_cluster_args(&command.script_parts);
+    args.extend([
+        "get".to_owned(),
+        kind,
+        "--all-namespaces".to_owned(),
+        "--no-headers".to_owned(),
+        "-o".to_owned(),
+        "custom-columns=NAMESPACE:.metadata.namespace,NAME:.metadata.name".to_owned(),
+    ]);
+    run_kubectl(&args, context)
+        .lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .filter(|(namespace, resource)| {
+            resource.trim() == name && current.as_deref() != Some(*namespace)
+        })
+        .map(|(namespace, _)| namespace.to_owned())
+        .collect()
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(
+        |command| !get_namespaces_with_resource(command, context).is_empty(),
+        command,
+        vec!["kubectl"],
+        Some(1),
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_namespaces_with_resource(command, context)
+        .iter()
+        .map(|namespace| {
+            join_script(
+                system_shell,
+                &set_flag(&command.script_parts, &["-n", "--namespace"], namespace),
+            )
+        })
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "kubectl_other_namespace".to_owned(),
+        None,
+        Some(900),
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const ALL_PODS: &str = "\
+default     web-7c9d8
+shop        api-5f6b4
+shop        web-7c9d8
+payments    ledger-0
+";
+
+    fn output(kind: &str, name: &str) -> String {
+        format!("Error from server (NotFound): {kind} \"{name}\" not found")
+    }
+
+    fn context() -> FakeContext {
+        FakeContext::new().with_command_output(
+            "kubectl get pods --all-namespaces --no-headers -o custom-columns=NAMESPACE:.metadata.namespace,NAME:.metadata.name",
+            ALL_PODS,
+        )
+    }
+
+    #[rstest]
+    #[case("kubectl logs ledger-0", &output("pods", "ledger-0"), true)]
+    #[case("kubectl logs -n shop api-5f6b4", &output("pods", "api-5f6b4"), false)]
+    #[case("kubectl logs db-0", &output("pods", "db-0"), false)]
+    #[case("kubectl get ns shop", &output("namespaces", "shop"), false)]
+    #[case("kubectl logs ledger-0", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("kubectl logs ledger-0", "ledger-0", vec!["kubectl -n payments logs ledger-0"])]
+    #[case("kubectl exec -it api-5f6b4 -- sh", "api-5f6b4", vec!["kubectl -n shop exec -it api-5f6b4 -- sh"])]
+    #[case("kubectl logs -n payments web-7c9d8", "web-7c9d8", vec!["kubectl logs -n default web-7c9d8", "kubectl logs -n shop web-7c9d8"])]
+    #[case("kubectl logs --namespace=shop ledger-0", "ledger-0", vec!["kubectl logs --namespace=payments ledger-0"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] name: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(output("pods", name)), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -0,0 +1,110 @@
+use super::{
+    utils::{
+        join_script,
+        kubernetes::{get_missing_resource, get_resource_names, replace_value},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::get_close_matches};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_resource(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["kubectl"], Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some((kind, name)) = command.output.as_deref().and_then(get_missing_resource) else {
+        return vec![];
+    };
+    let names = get_resource_names(command, &kind, context);
+    let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    get_close_matches(&name, &names, None, None)
+        .iter()
+        .map(|new| {
+            join_script(
+                system_shell,
+                &replace_value(&command.script_parts, &name, new),
+            )
+        })
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "kubectl_resource_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    fn output(kind: &str, name: &str) -> String {
+        format!("Error from server (NotFound): {kind} \"{name}\" not found")
+    }
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_command_output(
+                "kubectl get pods -o name",
+                "pod/web-7c9d8\npod/worker-5f6b4\n",
+            )
+            .with_command_output(
+                "kubectl get deployments.apps -o name -n shop",
+                "deployment.apps/api\ndeployment.apps/web\n",
+            )
+            .with_command_output(
+                "kubectl get namespaces -o name",
+                "namespace/default\nnamespace/payments\n",
+            )
+    }
+
+    #[rstest]
+    #[case("kubectl logs web-7c9d9", &output("pods", "web-7c9d9"), true)]
+    #[case("kubectl get pods", "No resources found in default namespace.", false)]
+    #[case("helm status web", &output("secrets", "web"), false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("kubectl logs web-7c9d9 -f", &output("pods", "web-7c9d9"), vec!["kubectl logs web-7c9d8 -f"])]
+    #[case("kubectl describe pod/wroker-5f6b4", &output("pods", "wroker-5f6b4"), vec!["kubectl describe pod/worker-5f6b4"])]
+    #[case("kubectl -n shop rollout restart deploy/apj", &output("deployments.apps", "apj"), vec!["kubectl -n shop rollout restart deploy/api"])]
+    #[case("kubectl get pods --namespace=paymnets", &output("namespaces", "paymnets"), vec!["kubectl get pods --namespace=payments"])]
+    #[case("kubectl logs db-0", &output("pods", "db-0"), vec![])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
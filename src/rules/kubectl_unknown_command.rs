@@ -0,0 +1,87 @@
+use super::{
+    utils::{
+        cobra::{get_command_suggestions, get_unknown_command},
+        kubernetes::KUBECTL_COMMANDS,
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_argument};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some_and(|unknown| command.script_parts.contains(&unknown))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["kubectl"], Some(1))
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    let Some(output) = &command.output else {
+        return vec![];
+    };
+    let Some(unknown) = get_unknown_command(output) else {
+        return vec![];
+    };
+    get_command_suggestions(output, &unknown, &KUBECTL_COMMANDS)
+        .iter()
+        .map(|new| replace_argument(&command.script, &unknown, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "kubectl_unknown_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    fn output(command: &str, suggestions: &[&str]) -> String {
+        let mut output = format!("error: unknown command \"{command}\" for \"kubectl\"\n");
+        if !suggestions.is_empty() {
+            output.push_str("\nDid you mean this?\n");
+            for suggestion in suggestions {
+                output.push_str(&format!("\t{suggestion}\n"));
+            }
+        }
+        output + "\nRun 'kubectl --help' for usage."
+    }
+
+    #[rstest]
+    #[case("kubectl gte pods", &output("gte", &["get", "set"]), true)]
+    #[case("kubectl descrbie pod web", &output("descrbie", &[]), true)]
+    #[case("kubectl get pods", "", false)]
+    #[case("helm instal nginx", &output("instal", &["install"]), false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("kubectl gte pods", &output("gte", &["get", "set"]), vec!["kubectl get pods", "kubectl set pods"])]
+    #[case("kubectl descrbie pod web", &output("descrbie", &[]), vec!["kubectl describe pod web"])]
+    #[case("kubectl -n web lgos api", &output("lgos", &["logs"]), vec!["kubectl -n web logs api"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
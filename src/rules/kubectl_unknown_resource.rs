@@ -0,0 +1,146 @@
+use super::{
+    utils::{
+        join_script,
+        kubernetes::{get_cluster_args, run_kubectl},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::get_close_matches};
+use regex::Regex;
+
+fn get_unknown_resource(output: &str) -> Option<String> {
+    let re = Regex::new(r#"the server doesn't have a resource type "([^"]+)""#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+/// Returns the resource types of the cluster, without their API group, e.g. `deployments`
+/// for `deployments.apps`.
+fn get_resource_types(command: &CrabCommand, context: &dyn Context) -> Vec<String> {
+    let mut args = get_cluster_args(&command.script_parts);
+    args.extend([
+        "api-resources".to_owned(),
+        "-o".to_owned(),
+        "name".to_owned(),
+    ]);
+    let mut types: Vec<String> = run_kubectl(&args, context)
+        .lines()
+        .filter_map(|line| line.split('.').next())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_owned())
+        .collect();
+    types.dedup();
+    types
+}
+
+/// Replaces the resource type, which can also be given as `type/name`.
+fn replace_resource_type(command: &CrabCommand, old: &str, new: &str) -> Vec<String> {
+    command
+        .script_parts
+        .iter()
+        .map(|part| match part.strip_prefix(old) {
+            Some("") => new.to_owned(),
+            Some(name) if name.starts_with('/') => format!("{new}{name}"),
+            _ => part.to_owned(),
+        })
+        .collect()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_resource(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["kubectl"], Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(unknown) = command.output.as_deref().and_then(get_unknown_resource) else {
+        return vec![];
+    };
+    let types = get_resource_types(command, context);
+    let types: Vec<&str> = types.iter().map(|s| s.as_str()).collect();
+    get_close_matches(&unknown, &types, None, None)
+        .iter()
+        .map(|new| join_script(system_shell, &replace_resource_type(command, &unknown, new)))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "kubectl_unknown_resource".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const API_RESOURCES: &str = "\
+configmaps
+namespaces
+pods
+services
+daemonsets.apps
+deployments.apps
+statefulsets.apps
+ingresses.networking.k8s.io
+";
+
+    fn output(resource: &str) -> String {
+        format!("error: the server doesn't have a resource type \"{resource}\"")
+    }
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_command_output("kubectl api-resources -o name", API_RESOURCES)
+            .with_command_output(
+                "kubectl --context=prod api-resources -o name",
+                "pods\ncronjobs.batch\n",
+            )
+    }
+
+    #[rstest]
+    #[case("kubectl get podz", &output("podz"), true)]
+    #[case("kubectl get pods", "", false)]
+    #[case("oc get podz", &output("podz"), false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("kubectl get podz", "podz", vec!["kubectl get pods"])]
+    #[case("kubectl describe deploymnets api", "deploymnets", vec!["kubectl describe deployments api", "kubectl describe daemonsets api"])]
+    #[case("kubectl get ingress/web", "ingress", vec!["kubectl get ingresses/web", "kubectl get services/web"])]
+    #[case("kubectl --context prod get cronjob", "cronjob", vec!["kubectl --context prod get cronjobs"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] resource: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(output(resource)), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -0,0 +1,179 @@
+use super::{
+    utils::{
+        join_script,
+        kubernetes::{get_flag_value, get_missing_resource, get_namespace, run_kubectl, set_flag},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+/// Returns the arguments selecting the kubeconfig of the command, if one is given.
+fn get_kubeconfig_args(command: &CrabCommand) -> Vec<String> {
+    get_flag_value(&command.script_parts, None, "--kubeconfig")
+        .map(|kubeconfig| format!("--kubeconfig={kubeconfig}"))
+        .into_iter()
+        .collect()
+}
+
+/// Returns the contexts of the kubeconfig other than the one used by the command.
+///
+/// Only the local kubeconfig is read, no cluster is contacted.
+fn get_other_contexts(command: &CrabCommand, context: &dyn Context) -> Vec<String> {
+    let run_config = |args: &[&str]| -> String {
+        let mut config_args = get_kubeconfig_args(command);
+        config_args.extend(args.iter().map(|arg| arg.to_string()));
+        run_kubectl(&config_args, context)
+    };
+    let current = get_flag_value(&command.script_parts, None, "--context")
+        .unwrap_or_else(|| run_config(&["config", "current-context"]).trim().to_owned());
+    run_config(&["config", "get-contexts", "-o", "name"])
+        .lines()
+        .filter(|other| !other.is_empty() && *other != current)
+        .map(|other| other.to_owned())
+        .collect()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
+    command
+        .output
+        .as_deref()
+        .and_then(get_missing_resource)
+        .is_some()
+        && !get_other_contexts(command, context).is_empty()
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(
+        |command| auxiliary_match_rule(command, context),
+        command,
+        vec!["kubectl"],
+        Some(1),
+    )
+}
+
+/// Looks for the resource that was not found, or the namespace of the command, in each
+/// of the other contexts. Since that queries every cluster, it is only done here and not
+/// to match the rule.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some((kind, name)) = command.output.as_deref().and_then(get_missing_resource) else {
+        return vec![];
+    };
+    let namespace = get_namespace(&command.script_parts).filter(|_| kind != "namespaces");
+    get_other_contexts(command, context)
+        .iter()
+        .filter(|other| {
+            let mut args = get_kubeconfig_args(command);
+            args.extend([
+                format!("--context={other}"),
+                "get".to_owned(),
+                kind.clone(),
+                name.clone(),
+                "-o".to_owned(),
+                "name".to_owned(),
+            ]);
+            if let Some(namespace) = &namespace {
+                args.extend(["-n".to_owned(), namespace.to_owned()]);
+            }
+            !run_kubectl(&args, context).trim().is_empty()
+        })
+        .map(|other| {
+            join_script(
+                system_shell,
+                &set_flag(&command.script_parts, &["--context"], other),
+            )
+        })
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "kubectl_wrong_context".to_owned(),
+        None,
+        Some(900),
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    fn output(kind: &str, name: &str) -> String {
+        format!("Error from server (NotFound): {kind} \"{name}\" not found")
+    }
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_command_output("kubectl config current-context", "staging\n")
+            .with_command_output(
+                "kubectl config get-contexts -o name",
+                "dev\nprod\nstaging\n",
+            )
+            .with_command_output(
+                "kubectl --context=prod get namespaces payments -o name",
+                "namespace/payments\n",
+            )
+            .with_command_output(
+                "kubectl --context=prod get pods ledger-0 -o name -n payments",
+                "pod/ledger-0\n",
+            )
+            .with_command_output(
+                "kubectl --context=staging get pods ledger-0 -o name -n payments",
+                "pod/ledger-0\n",
+            )
+    }
+
+    #[rstest]
+    #[case("kubectl get pods -n payments", &output("namespaces", "payments"), true)]
+    #[case("kubectl logs -n payments ledger-0", &output("pods", "ledger-0"), true)]
+    #[case("kubectl --context dev logs -n payments ledger-0", &output("pods", "ledger-0"), true)]
+    #[case("kubectl logs -n payments db-0", &output("pods", "db-0"), true)]
+    #[case("kubectl get pods -n payments", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[test]
+    fn test_match_without_other_contexts() {
+        let context = FakeContext::new()
+            .with_command_output("kubectl config current-context", "staging\n")
+            .with_command_output("kubectl config get-contexts -o name", "staging\n");
+        let mut command = CrabCommand::new(
+            "kubectl logs -n payments ledger-0".to_owned(),
+            Some(output("pods", "ledger-0")),
+            None,
+        );
+        assert!(!match_rule(&mut command, None, &context));
+    }
+
+    #[rstest]
+    #[case("kubectl get pods -n payments", &output("namespaces", "payments"), vec!["kubectl --context prod get pods -n payments"])]
+    #[case("kubectl logs -n payments ledger-0", &output("pods", "ledger-0"), vec!["kubectl --context prod logs -n payments ledger-0"])]
+    #[case("kubectl --context dev logs -n payments ledger-0", &output("pods", "ledger-0"), vec!["kubectl --context prod logs -n payments ledger-0", "kubectl --context staging logs -n payments ledger-0"])]
+    #[case("kubectl logs --context=dev -n payments ledger-0", &output("pods", "ledger-0"), vec!["kubectl logs --context=prod -n payments ledger-0", "kubectl logs --context=staging -n payments ledger-0"])]
+    #[case("kubectl logs -n payments db-0", &output("pods", "db-0"), vec![])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
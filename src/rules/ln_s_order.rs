@@ -1,69 +1,72 @@
-use super::{get_new_command_without_sudo, match_rule_without_sudo, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{
+    get_new_command_without_sudo, match_rule_without_sudo, utils::join_script, BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use std::path::Path;
 
-fn file_exists(path: &str) -> bool {
-    Path::new(path).exists()
-}
-
-fn get_destination<F>(script_parts: &Vec<String>, fn_file_exists: F) -> Option<String>
-where
-    F: Fn(&str) -> bool,
-{
+fn get_destination(script_parts: &Vec<String>, context: &dyn Context) -> Option<String> {
     for part in script_parts {
-        if part != "ln" && part != "-s" && part != "--symbolic" && fn_file_exists(part) {
+        if part != "ln" && part != "-s" && part != "--symbolic" && context.exists(Path::new(part)) {
             return Some(part.to_owned());
         }
     }
     None
 }
 
-fn auxiliary_match_rule<F>(command: &CrabCommand, fn_file_exists: F) -> bool
-where
-    F: Fn(&str) -> bool,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         command.script_parts.first().is_some_and(|s| s == "ln")
             && (command.script_parts.contains(&"-s".to_owned())
                 || command.script_parts.contains(&"--symbolic".to_owned()))
             && output.contains("File exists")
-            && get_destination(&command.script_parts, fn_file_exists).is_some()
+            && get_destination(&command.script_parts, context).is_some()
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_without_sudo(
-        |command| auxiliary_match_rule(command, file_exists),
+        |command| auxiliary_match_rule(command, context),
         command,
+        context,
     )
 }
 
-pub fn auxiliary_get_new_command<F>(command: &CrabCommand, fn_file_exists: F) -> Vec<String>
-where
-    F: Fn(&str) -> bool,
-{
-    let destination = get_destination(&command.script_parts, fn_file_exists);
+pub fn auxiliary_get_new_command(
+    command: &CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let destination = get_destination(&command.script_parts, context);
     if let Some(destination) = destination {
         let mut parts = command.script_parts.clone();
         parts.retain(|x| *x != destination);
         parts.push(destination);
-        vec![parts.join(" ")]
+        vec![join_script(system_shell, &parts)]
     } else {
         vec![]
     }
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     get_new_command_without_sudo(
-        |command| auxiliary_get_new_command(command, file_exists),
+        |command| auxiliary_get_new_command(command, system_shell, context),
         command,
+        context,
     )
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "ln_s_order".to_owned(),
         None,
         None,
@@ -76,8 +79,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{auxiliary_get_new_command, auxiliary_match_rule};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
     const ERROR_FILE_EXISTS: &str = "ln: failed to create symbolic link 'source': File exists";
@@ -97,7 +101,11 @@ mod tests {
         #[case] is_match: bool,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(auxiliary_match_rule(&command, |path| file_exists), is_match);
+        let mut context = FakeContext::new();
+        if file_exists {
+            context = context.with_file("dest", b"");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
     }
 
     #[rstest]
@@ -110,6 +118,7 @@ mod tests {
         #[case] expected: Vec<&str>,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(auxiliary_get_new_command(&command, |path| true), expected);
+        let context = FakeContext::new().with_file("dest", b"");
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
     }
 }
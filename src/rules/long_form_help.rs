@@ -1,4 +1,4 @@
-use super::Rule;
+use super::BuiltinRule;
 use crate::{cli::command::CrabCommand, shell::Shell};
 use regex::Regex;
 
@@ -28,8 +28,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "long_form_help".to_owned(),
         None,
         Some(5000),
@@ -1,6 +1,9 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     if let Some(output) = &command.output {
@@ -16,7 +19,7 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
 
 pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
     if !command.script_parts.is_empty() {
-        let arg = command.script_parts[1..].join(" ");
+        let arg = join_script(system_shell, &command.script_parts[1..]);
         vec![match arg.is_empty() {
             true => "ls -A".to_owned(),
             false => format!("ls -A {arg}"),
@@ -26,8 +29,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "ls_all".to_owned(),
         None,
         None,
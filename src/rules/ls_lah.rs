@@ -1,6 +1,9 @@
 use crate::{cli::command::CrabCommand, shell::Shell};
 
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     !command.script_parts.is_empty() && !command.script.contains("ls -l")
@@ -12,12 +15,13 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
 
 pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
     let mut script_parts = command.script_parts.clone();
-    script_parts[0] = "ls -lah".to_string();
-    vec![script_parts.join(" ")]
+    script_parts[0] = "ls".to_string();
+    script_parts.insert(1, "-lah".to_string());
+    vec![join_script(system_shell, &script_parts)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "ls_lah".to_owned(),
         None,
         None,
@@ -1,4 +1,7 @@
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
 use crate::{cli::command::CrabCommand, shell::Shell, utils::get_closest};
 use regex::Regex;
 
@@ -45,7 +48,7 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
             None,
         ) {
             script[1] = closest.to_string();
-            vec![script.join(" ")]
+            vec![join_script(system_shell, &script)]
         } else {
             vec![]
         }
@@ -54,8 +57,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "mercurial".to_owned(),
         None,
         None,
@@ -1,18 +1,49 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use regex::Regex;
+use std::path::Path;
 
-use super::{get_new_command_without_sudo, match_rule_without_sudo, Rule};
+use super::{get_new_command_without_sudo, match_rule_without_sudo, BuiltinRule};
 
-fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+fn is_parents_option(part: &str) -> bool {
+    part == "--parents" || (part.starts_with('-') && !part.starts_with("--") && part.contains('p'))
+}
+
+/// Returns `true` if a `mkdir` command that was not run would fail because the parent of
+/// one of its directories does not exist.
+fn has_missing_parents(command: &CrabCommand, context: &dyn Context) -> bool {
+    if command.script_parts.first().map(|s| s.as_str()) != Some("mkdir")
+        || command
+            .script_parts
+            .iter()
+            .any(|part| is_parents_option(part))
+    {
+        return false;
+    }
+    command.script_parts[1..]
+        .iter()
+        .filter(|part| !part.starts_with('-'))
+        .filter_map(|dir| Path::new(dir).parent())
+        .any(|parent| !parent.as_os_str().is_empty() && !context.is_dir(parent))
+}
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         command.script.contains("mkdir") && output.contains("No such file or directory")
     } else {
-        false
+        has_missing_parents(command, context)
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    match_rule_without_sudo(auxiliary_match_rule, command)
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| auxiliary_match_rule(command, context),
+        command,
+        context,
+    )
 }
 
 fn auxiliary_get_new_command(command: &CrabCommand) -> Vec<String> {
@@ -20,16 +51,20 @@ fn auxiliary_get_new_command(command: &CrabCommand) -> Vec<String> {
     vec![re.replace_all(&command.script, "mkdir -p $1").to_string()]
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    get_new_command_without_sudo(auxiliary_get_new_command, command)
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(auxiliary_get_new_command, command, context)
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "mkdir_p.rs".to_owned(),
         None,
         None,
-        None,
+        Some(false),
         Box::new(match_rule),
         get_new_command,
         None,
@@ -40,6 +75,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -62,7 +98,25 @@ mod tests {
     #[case("", "", false)]
     fn test_match(#[case] command: &str, #[case] output: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(output.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("mkdir foo/bar/baz", true)]
+    #[case("sudo mkdir -m 700 foo/bar/baz", true)]
+    #[case("mkdir src/bar", false)]
+    #[case("mkdir foo", false)]
+    #[case("mkdir -p foo/bar/baz", false)]
+    #[case("mkdir -vp foo/bar/baz", false)]
+    #[case("mkdir --parents foo/bar/baz", false)]
+    #[case("hdfs dfs -mkdir foo/bar/baz", false)]
+    fn test_match_without_output(#[case] command: &str, #[case] is_match: bool) {
+        let context = FakeContext::new().with_dir("src");
+        let mut command = CrabCommand::new(command.to_owned(), None, None);
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
     }
 
     #[rstest]
@@ -77,7 +131,7 @@ mod tests {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(output.to_owned()), None);
         assert_eq!(
-            get_new_command(&mut command, Some(&system_shell)),
+            get_new_command(&mut command, Some(&system_shell), &FakeContext::new()),
             vec![expected]
         );
     }
@@ -1,9 +1,15 @@
+use crate::context::Context;
 use crate::shell::Shell;
 use core::fmt;
+use std::rc::Rc;
 
-use crate::cli::{command::CorrectedCommand, command::CrabCommand};
+use crate::cli::command::{CorrectedCommand, CrabCommand, SideEffect};
+use crate::cli::compound::{find_failed_segment, split_segments, Segment};
+use utils::wrappers::unwrap_script;
 
 mod ag_literal;
+mod apk_unable_to_select;
+mod apk_unknown_applet;
 mod apt_get;
 mod apt_get_search;
 mod apt_list_upgradable;
@@ -32,11 +38,25 @@ mod cpp11;
 mod dirty_untar;
 mod django_south_ghost;
 mod django_south_merge;
+mod dnf_command_not_found;
+mod dnf_no_match;
+mod dnf_no_such_command;
+mod docker_compose;
 mod docker_image_being_used_by_container;
 mod docker_login;
+mod docker_no_such_object;
+mod docker_not_command;
+mod docker_permission_denied;
+mod docker_port_allocated;
 mod dry;
 mod fix_alt_space;
 mod fix_file;
+mod flatpak_ref_not_found;
+mod flatpak_remote_installation;
+mod gh_auth_login;
+mod gh_pr_already_exists;
+mod gh_push_branch;
+mod gh_unknown_command;
 mod git_add;
 mod git_add_force;
 mod git_bisect_usage;
@@ -81,17 +101,26 @@ mod git_stash;
 mod git_stash_pop;
 mod git_tag_force;
 mod git_two_dashes;
+mod glab_unknown_command;
 mod go_run;
 mod gradle_wrapper;
 mod grep_arguments_order;
 mod grep_recursive;
 mod has_exists_script;
+mod helm_repo_add;
+mod helm_unknown_command;
 mod heroku_multiple_apps;
 mod heroku_not_command;
 mod history;
 mod hostscli;
 mod java;
 mod javac;
+mod journalctl_unit_not_found;
+mod kubectl_other_namespace;
+mod kubectl_resource_not_found;
+mod kubectl_unknown_command;
+mod kubectl_unknown_resource;
+mod kubectl_wrong_context;
 mod lein_not_task;
 mod ln_no_hard_link;
 mod ln_s_order;
@@ -109,9 +138,15 @@ mod no_command;
 mod no_such_file;
 mod npm_missing_script;
 mod npm_run_script;
+mod pacman;
+mod pacman_invalid_option;
+mod pacman_not_found;
+mod pacman_sudo;
 mod php_s;
 mod pip_install;
 mod pip_unknown_command;
+mod pnpm_missing_script;
+mod pnpm_no_command;
 mod prove_recursively;
 mod python_command;
 mod python_execute;
@@ -121,35 +156,177 @@ mod rails_migrations_pending;
 mod remove_shell_prompt_literal;
 mod rm_dir;
 mod sl_ls;
+mod snap_not_found;
+mod snap_unknown_command;
 mod sudo;
 mod sudo_command_from_user_path;
+mod systemctl_argument_order;
+mod systemctl_sudo;
+mod systemctl_unit_not_found;
+mod systemctl_user_unit;
+mod terraform_init;
+mod terraform_init_upgrade;
+mod terraform_no_command;
+mod terraform_var_file;
 mod tmux;
 mod touch;
 mod unsudo;
 
 mod utils;
+mod yarn_alias;
+mod yarn_command_replaced;
+mod yarn_missing_script;
+mod zypper_not_found;
+mod zypper_unknown_command;
 
-pub fn get_rules() -> Vec<Rule> {
+const DEFAULT_PRIORITY: u16 = 1000;
+
+/// Rules used to correct the commands reported by the shell "command not found" hook.
+const COMMAND_NOT_FOUND_RULES: [&str; 2] = ["no_command", "history"];
+
+pub fn get_rules() -> Vec<Rc<dyn Rule>> {
     include!(concat!(env!("OUT_DIR"), "/rules_list.rs"))
 }
 
-pub struct Rule {
-    name: String,
-    enabled_by_default: bool,
-    priority: u16,
-    requires_output: bool,
-    pub match_rule: Box<dyn Fn(&mut CrabCommand, Option<&dyn Shell>) -> bool>,
-    get_new_command: fn(&mut CrabCommand, Option<&dyn Shell>) -> Vec<String>,
-    side_effect: Option<fn(CrabCommand, Option<&str>)>,
+/// Settings that describe how a rule takes part in the correction process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleMetadata {
+    pub enabled_by_default: bool,
+    pub priority: u16,
+    pub requires_output: bool,
 }
 
-impl fmt::Display for Rule {
+impl RuleMetadata {
+    fn new(
+        enabled_by_default: Option<bool>,
+        priority: Option<u16>,
+        requires_output: Option<bool>,
+    ) -> Self {
+        let default = Self::default();
+        Self {
+            enabled_by_default: enabled_by_default.unwrap_or(default.enabled_by_default),
+            priority: priority.unwrap_or(default.priority),
+            requires_output: requires_output.unwrap_or(default.requires_output),
+        }
+    }
+}
+
+impl Default for RuleMetadata {
+    fn default() -> Self {
+        Self {
+            enabled_by_default: true,
+            priority: DEFAULT_PRIORITY,
+            requires_output: true,
+        }
+    }
+}
+
+/// A correction rule.
+///
+/// Implementors decide whether a failed command can be fixed (`matches`) and produce the
+/// candidate fixes (`corrections`). Since rules are regular objects, they can hold their
+/// own state, such as compiled regexes, settings or the runners used to query the system.
+pub trait Rule {
+    fn name(&self) -> &str;
+
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata::default()
+    }
+
+    /// Returns `true` if the rule can fix the given command.
+    fn matches(
+        &self,
+        command: &mut CrabCommand,
+        system_shell: Option<&dyn Shell>,
+        context: &dyn Context,
+    ) -> bool;
+
+    /// Returns the new scripts suggested for the given command.
+    fn corrections(
+        &self,
+        command: &mut CrabCommand,
+        system_shell: Option<&dyn Shell>,
+        context: &dyn Context,
+    ) -> Vec<String>;
+
+    /// Returns `true` if `side_effect` should be called when a correction is selected.
+    fn has_side_effect(&self) -> bool {
+        false
+    }
+
+    /// Runs after the user selects one of the corrections produced by this rule.
+    fn side_effect(
+        &self,
+        old_command: CrabCommand,
+        new_script: Option<&str>,
+        context: &dyn Context,
+    ) {
+    }
+}
+
+impl fmt::Display for dyn Rule {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.name)
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Returns `true` if the rule matches the command, taking its metadata into account.
+fn is_match(
+    rule: &dyn Rule,
+    command: &mut CrabCommand,
+    system_shell: &dyn Shell,
+    context: &dyn Context,
+) -> bool {
+    let script_only = command.output.is_none();
+    if script_only && rule.metadata().requires_output {
+        return false;
     }
+    rule.matches(command, Some(system_shell), context)
 }
 
-impl Rule {
+/// Builds the list of `CorrectedCommand` suggested by a rule for the given command.
+fn get_rule_corrected_commands(
+    rule: &Rc<dyn Rule>,
+    command: &mut CrabCommand,
+    system_shell: &dyn Shell,
+    context: &dyn Context,
+) -> Vec<CorrectedCommand> {
+    let priority = rule.metadata().priority;
+    let side_effect: Option<SideEffect> = if rule.has_side_effect() {
+        let rule = Rc::clone(rule);
+        Some(Rc::new(move |old_command, new_script, context| {
+            rule.side_effect(old_command, new_script, context)
+        }))
+    } else {
+        None
+    };
+
+    let mut new_commands: Vec<CorrectedCommand> = vec![];
+    for (n, new_command) in rule
+        .corrections(command, Some(system_shell), context)
+        .iter()
+        .enumerate()
+    {
+        new_commands.push(CorrectedCommand::new(
+            new_command.to_owned(),
+            side_effect.clone(),
+            (n as u16 + 1) * priority,
+        ));
+    }
+    new_commands
+}
+
+/// Adapter used by the built-in rule modules, which are written as plain functions.
+pub struct BuiltinRule {
+    name: String,
+    metadata: RuleMetadata,
+    match_rule: Box<dyn Fn(&mut CrabCommand, Option<&dyn Shell>, &dyn Context) -> bool>,
+    get_new_command: Box<dyn Fn(&mut CrabCommand, Option<&dyn Shell>, &dyn Context) -> Vec<String>>,
+    side_effect: Option<Box<dyn Fn(CrabCommand, Option<&str>, &dyn Context)>>,
+}
+
+impl BuiltinRule {
+    /// Creates a rule whose functions only depend on the command and the shell.
     fn new(
         name: String,
         enabled_by_default: Option<bool>,
@@ -161,71 +338,124 @@ impl Rule {
     ) -> Self {
         Self {
             name,
-            enabled_by_default: enabled_by_default.unwrap_or(true),
-            priority: priority.unwrap_or(1000),
-            requires_output: requires_output.unwrap_or(true),
-            match_rule,
-            get_new_command,
-            side_effect,
+            metadata: RuleMetadata::new(enabled_by_default, priority, requires_output),
+            match_rule: Box::new(move |command, system_shell, _| match_rule(command, system_shell)),
+            get_new_command: Box::new(move |command, system_shell, _| {
+                get_new_command(command, system_shell)
+            }),
+            side_effect: side_effect.map(|side_effect| {
+                Box::new(
+                    move |old_command, new_script: Option<&str>, _: &dyn Context| {
+                        side_effect(old_command, new_script)
+                    },
+                ) as Box<dyn Fn(CrabCommand, Option<&str>, &dyn Context)>
+            }),
         }
     }
 
-    // Returns `True` if rule matches the command.
-    fn is_match(&self, mut command: CrabCommand, system_shell: &dyn Shell) -> bool {
-        let script_only = command.output.is_none();
-        if script_only && self.requires_output {
-            return false;
-        }
-        if (self.match_rule)(&mut command, Some(system_shell)) {
-            return true;
+    /// Creates a rule whose functions also need to query the system through a `Context`.
+    fn with_context(
+        name: String,
+        enabled_by_default: Option<bool>,
+        priority: Option<u16>,
+        requires_output: Option<bool>,
+        match_rule: Box<dyn Fn(&mut CrabCommand, Option<&dyn Shell>, &dyn Context) -> bool>,
+        get_new_command: fn(&mut CrabCommand, Option<&dyn Shell>, &dyn Context) -> Vec<String>,
+        side_effect: Option<fn(CrabCommand, Option<&str>, &dyn Context)>,
+    ) -> Self {
+        Self {
+            name,
+            metadata: RuleMetadata::new(enabled_by_default, priority, requires_output),
+            match_rule,
+            get_new_command: Box::new(get_new_command),
+            side_effect: side_effect.map(|side_effect| {
+                Box::new(side_effect) as Box<dyn Fn(CrabCommand, Option<&str>, &dyn Context)>
+            }),
         }
-        false
+    }
+}
+
+impl Rule for BuiltinRule {
+    fn name(&self) -> &str {
+        &self.name
     }
 
-    fn get_corrected_commands(
+    fn metadata(&self) -> RuleMetadata {
+        self.metadata
+    }
+
+    fn matches(
         &self,
         command: &mut CrabCommand,
-        system_shell: &dyn Shell,
-    ) -> Vec<CorrectedCommand> {
-        let mut new_commands: Vec<CorrectedCommand> = vec![];
-        for (n, new_command) in (self.get_new_command)(command, Some(system_shell))
-            .iter()
-            .enumerate()
-        {
-            new_commands.push(CorrectedCommand::new(
-                new_command.to_owned(),
-                self.side_effect,
-                (n as u16 + 1) * self.priority,
-            ));
+        system_shell: Option<&dyn Shell>,
+        context: &dyn Context,
+    ) -> bool {
+        (self.match_rule)(command, system_shell, context)
+    }
+
+    fn corrections(
+        &self,
+        command: &mut CrabCommand,
+        system_shell: Option<&dyn Shell>,
+        context: &dyn Context,
+    ) -> Vec<String> {
+        (self.get_new_command)(command, system_shell, context)
+    }
+
+    fn has_side_effect(&self) -> bool {
+        self.side_effect.is_some()
+    }
+
+    fn side_effect(
+        &self,
+        old_command: CrabCommand,
+        new_script: Option<&str>,
+        context: &dyn Context,
+    ) {
+        if let Some(side_effect) = &self.side_effect {
+            side_effect(old_command, new_script, context);
         }
-        new_commands
     }
 }
 
-pub fn match_rule_without_sudo<F>(match_function: F, command: &mut CrabCommand) -> bool
+/// Matches the command without the wrappers in front of it, such as `sudo -u deploy`,
+/// `doas`, `env FOO=bar` or `nohup`.
+pub fn match_rule_without_sudo<F>(
+    match_function: F,
+    command: &mut CrabCommand,
+    context: &dyn Context,
+) -> bool
 where
     F: Fn(&CrabCommand) -> bool,
 {
-    if !command.script.starts_with("sudo ") {
+    let unwrapped = unwrap_script(&command.script, context);
+    if unwrapped.prefixes.is_empty() {
         match_function(command)
     } else {
-        let new_script = command.script[5..].to_owned();
-        match_function(&command.update(Some(new_script), None, None))
+        match_function(&command.update(Some(unwrapped.script.to_owned()), None, None))
     }
 }
 
-pub fn get_new_command_without_sudo(
-    get_new_command_function: fn(&CrabCommand) -> Vec<String>,
+/// Gets the new commands for the command without the wrappers in front of it, and puts
+/// the wrappers back, exactly as they were typed, in front of each new command.
+pub fn get_new_command_without_sudo<F>(
+    get_new_command_function: F,
     command: &mut CrabCommand,
-) -> Vec<String> {
-    if !command.script.starts_with("sudo ") {
+    context: &dyn Context,
+) -> Vec<String>
+where
+    F: Fn(&CrabCommand) -> Vec<String>,
+{
+    let unwrapped = unwrap_script(&command.script, context);
+    if unwrapped.prefixes.is_empty() {
         get_new_command_function(command)
     } else {
-        let new_script = command.script[5..].to_owned();
-        command.script = new_script;
+        let prefix = unwrapped.prefix();
+        let new_script = unwrapped.script.to_owned();
+        *command = command.update(Some(new_script), None, None);
         get_new_command_function(command)
             .iter()
-            .map(|cmd| "sudo ".to_owned() + cmd)
+            .map(|cmd| prefix.to_owned() + cmd)
             .collect()
     }
 }
@@ -245,20 +475,353 @@ pub fn get_new_command_without_sudo(
 pub fn get_corrected_commands(
     command: &mut CrabCommand,
     system_shell: &dyn Shell,
+    context: &dyn Context,
 ) -> Vec<CorrectedCommand> {
+    get_corrected_commands_from_rules(&get_rules(), command, system_shell, context)
+}
+
+/// Generate the list of corrected commands for a command line that was not run yet.
+///
+/// Without output, only the rules that can decide from the script alone are used, such as
+/// typos in the name of the program or unknown git subcommands. A blank line has nothing
+/// to correct.
+pub fn get_buffer_corrections(
+    command: &mut CrabCommand,
+    system_shell: &dyn Shell,
+    context: &dyn Context,
+) -> Vec<CorrectedCommand> {
+    if command.script_parts.is_empty() {
+        return vec![];
+    }
+    command.output = None;
+    get_corrected_commands(command, system_shell, context)
+}
+
+/// Generate the list of corrected commands for a command that the shell could not find.
+///
+/// Only the rules that deal with unknown commands are used, since the command was not
+/// run and there is no real output to match.
+pub fn get_command_not_found_corrections(
+    command: &mut CrabCommand,
+    system_shell: &dyn Shell,
+    context: &dyn Context,
+) -> Vec<CorrectedCommand> {
+    let rules = get_rules()
+        .into_iter()
+        .filter(|rule| COMMAND_NOT_FOUND_RULES.contains(&rule.name()))
+        .collect::<Vec<_>>();
+    get_corrected_commands_from_rules(&rules, command, system_shell, context)
+}
+
+/// Generate a list of corrected commands using the given set of rules.
+///
+/// Works like `get_corrected_commands`, but allows callers to provide their own rules,
+/// e.g., rules that keep state or are configured by the user.
+///
+/// If the command is a compound one (e.g. `cd foo && gti status`) and the segment that
+/// failed can be identified, the rules only see that segment and their corrections are
+/// spliced back into the full command line.
+pub fn get_corrected_commands_from_rules(
+    rules: &[Rc<dyn Rule>],
+    command: &mut CrabCommand,
+    system_shell: &dyn Shell,
+    context: &dyn Context,
+) -> Vec<CorrectedCommand> {
+    let segments = split_segments(&command.script);
+    let failed_segment = match &command.output {
+        Some(output) if segments.len() > 1 => find_failed_segment(&segments, output),
+        _ => None,
+    };
+
     let mut corrected_commands: Vec<CorrectedCommand> = vec![];
-    for rule in get_rules() {
-        if (rule.match_rule)(command, Some(system_shell)) {
-            for corrected in rule.get_corrected_commands(command, system_shell) {
-                corrected_commands.push(corrected);
+    if let Some(segment) = failed_segment {
+        let mut segment_command = command.update(Some(segment.script.to_owned()), None, None);
+        for rule in rules {
+            if is_match(rule.as_ref(), &mut segment_command, system_shell, context) {
+                for corrected in
+                    get_rule_corrected_commands(rule, &mut segment_command, system_shell, context)
+                {
+                    corrected_commands.push(splice_corrected_command(
+                        corrected,
+                        &command.script,
+                        segment,
+                    ));
+                }
+            }
+        }
+    } else {
+        for rule in rules {
+            if is_match(rule.as_ref(), command, system_shell, context) {
+                for corrected in get_rule_corrected_commands(rule, command, system_shell, context) {
+                    corrected_commands.push(corrected);
+                }
             }
         }
     }
     organize_commands(corrected_commands)
 }
 
+/// Turns a correction of a single segment into a correction of the full command line.
+///
+/// The side effect, if any, still receives the segment and its correction, since that is
+/// what the rule worked with.
+fn splice_corrected_command(
+    corrected: CorrectedCommand,
+    full_script: &str,
+    segment: &Segment,
+) -> CorrectedCommand {
+    let script = segment.splice(full_script, &corrected.script);
+    let side_effect = corrected.side_effect.map(|side_effect| {
+        let segment_script = segment.script.to_owned();
+        let new_segment_script = corrected.script.to_owned();
+        Rc::new(
+            move |old_command: CrabCommand, _: Option<&str>, context: &dyn Context| {
+                side_effect(
+                    old_command.update(Some(segment_script.to_owned()), None, None),
+                    Some(&new_segment_script),
+                    context,
+                )
+            },
+        ) as SideEffect
+    });
+    CorrectedCommand::new(script, side_effect, corrected.priority)
+}
+
 pub fn organize_commands(mut corrected_commands: Vec<CorrectedCommand>) -> Vec<CorrectedCommand> {
-    corrected_commands.sort_by(|a, b| a.priority.cmp(&b.priority));
+    corrected_commands.sort_by_key(|a| a.priority);
     corrected_commands.dedup_by(|a, b| a.script.eq(&b.script));
     corrected_commands
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_buffer_corrections, get_corrected_commands_from_rules, get_new_command_without_sudo,
+        match_rule_without_sudo, BuiltinRule, Rule, RuleMetadata,
+    };
+    use crate::cli::command::CrabCommand;
+    use crate::context::{Context, FakeContext};
+    use crate::shell::{Bash, Shell};
+    use crate::ENV_VAR_NAME_WRAPPERS;
+    use rstest::rstest;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// A rule that keeps its own configuration and records the arguments of its side effect.
+    struct ReplaceRule {
+        from: String,
+        to: String,
+        side_effect_calls: Rc<RefCell<Vec<(String, String)>>>,
+    }
+
+    impl Rule for ReplaceRule {
+        fn name(&self) -> &str {
+            "replace"
+        }
+
+        fn metadata(&self) -> RuleMetadata {
+            RuleMetadata {
+                priority: 10,
+                ..RuleMetadata::default()
+            }
+        }
+
+        fn matches(
+            &self,
+            command: &mut CrabCommand,
+            system_shell: Option<&dyn Shell>,
+            context: &dyn Context,
+        ) -> bool {
+            command.script.contains(&self.from)
+        }
+
+        fn corrections(
+            &self,
+            command: &mut CrabCommand,
+            system_shell: Option<&dyn Shell>,
+            context: &dyn Context,
+        ) -> Vec<String> {
+            vec![command.script.replace(&self.from, &self.to)]
+        }
+
+        fn has_side_effect(&self) -> bool {
+            true
+        }
+
+        fn side_effect(
+            &self,
+            old_command: CrabCommand,
+            new_script: Option<&str>,
+            context: &dyn Context,
+        ) {
+            self.side_effect_calls.borrow_mut().push((
+                old_command.script,
+                new_script.unwrap_or_default().to_owned(),
+            ));
+        }
+    }
+
+    #[test]
+    fn test_builtin_rule_metadata() {
+        let rule = BuiltinRule::new(
+            "builtin".to_owned(),
+            None,
+            Some(42),
+            Some(false),
+            Box::new(|_, _| true),
+            |_, _| vec![],
+            None,
+        );
+        assert_eq!(rule.name(), "builtin");
+        assert_eq!(
+            rule.metadata(),
+            RuleMetadata {
+                enabled_by_default: true,
+                priority: 42,
+                requires_output: false,
+            }
+        );
+        assert!(!rule.has_side_effect());
+    }
+
+    #[test]
+    fn test_get_corrected_commands_from_stateful_rule() {
+        let side_effect_calls = Rc::new(RefCell::new(vec![]));
+        let rules: Vec<Rc<dyn Rule>> = vec![Rc::new(ReplaceRule {
+            from: "gti".to_owned(),
+            to: "git".to_owned(),
+            side_effect_calls: Rc::clone(&side_effect_calls),
+        })];
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new("gti status".to_owned(), Some("".to_owned()), None);
+
+        let context = FakeContext::new();
+        let corrected =
+            get_corrected_commands_from_rules(&rules, &mut command, &system_shell, &context);
+        assert_eq!(corrected.len(), 1);
+        assert_eq!(corrected[0].script, "git status");
+        assert_eq!(corrected[0].priority, 10);
+
+        let side_effect = corrected[0].side_effect.as_ref().unwrap();
+        side_effect(command, Some(&corrected[0].script), &context);
+        assert_eq!(
+            *side_effect_calls.borrow(),
+            vec![("gti status".to_owned(), "git status".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_get_corrected_commands_without_output() {
+        let rules: Vec<Rc<dyn Rule>> = vec![
+            Rc::new(ReplaceRule {
+                from: "gti".to_owned(),
+                to: "git".to_owned(),
+                side_effect_calls: Rc::new(RefCell::new(vec![])),
+            }),
+            Rc::new(BuiltinRule::new(
+                "script_only".to_owned(),
+                None,
+                None,
+                Some(false),
+                Box::new(|command, _| command.script.starts_with("gti")),
+                |command, _| vec![command.script.replacen("gti", "got", 1)],
+                None,
+            )),
+        ];
+        let mut command = CrabCommand::from_buffer(vec!["gti".to_owned(), "status".to_owned()]);
+
+        // Rules that require the output are skipped, since the command was not run
+        let corrected =
+            get_corrected_commands_from_rules(&rules, &mut command, &Bash {}, &FakeContext::new());
+        assert_eq!(corrected.len(), 1);
+        assert_eq!(corrected[0].script, "got status");
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("   ")]
+    fn test_get_buffer_corrections_for_blank_line(#[case] line: &str) {
+        let mut command = CrabCommand::from_buffer(vec![line.to_owned()]);
+        assert!(get_buffer_corrections(&mut command, &Bash {}, &FakeContext::new()).is_empty());
+    }
+
+    #[test]
+    fn test_get_corrected_commands_for_failed_segment() {
+        let side_effect_calls = Rc::new(RefCell::new(vec![]));
+        let rules: Vec<Rc<dyn Rule>> = vec![Rc::new(ReplaceRule {
+            from: "gti".to_owned(),
+            to: "git".to_owned(),
+            side_effect_calls: Rc::clone(&side_effect_calls),
+        })];
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new(
+            "cd gti && gti status | less".to_owned(),
+            None,
+            Some("bash: gti: command not found".to_owned()),
+        );
+
+        let context = FakeContext::new();
+        let corrected =
+            get_corrected_commands_from_rules(&rules, &mut command, &system_shell, &context);
+        assert_eq!(corrected.len(), 1);
+        // Only the segment that failed is corrected
+        assert_eq!(corrected[0].script, "cd gti && git status | less");
+
+        corrected[0].run(command, &context);
+        assert_eq!(
+            *side_effect_calls.borrow(),
+            vec![("gti status".to_owned(), "git status".to_owned())]
+        );
+    }
+
+    #[rstest]
+    #[case("gti status", true, "git status")]
+    #[case("sudo gti status", true, "sudo git status")]
+    #[case("sudo -u deploy  gti status", true, "sudo -u deploy  git status")]
+    #[case("doas env GIT_DIR=x gti status", true, "doas env GIT_DIR=x git status")]
+    #[case("proxychains gti status", true, "proxychains git status")]
+    #[case("sudo ls gti", false, "sudo ls git")]
+    fn test_without_sudo_helpers(
+        #[case] script: &str,
+        #[case] is_match: bool,
+        #[case] expected: &str,
+    ) {
+        let context = FakeContext::new().with_env(ENV_VAR_NAME_WRAPPERS, "proxychains");
+        let mut command = CrabCommand::new(script.to_owned(), None, None);
+        assert_eq!(
+            match_rule_without_sudo(
+                |command| command.script_parts[0] == "gti",
+                &mut command,
+                &context
+            ),
+            is_match
+        );
+        assert_eq!(
+            get_new_command_without_sudo(
+                |command| vec![command.script.replace("gti", "git")],
+                &mut command,
+                &context
+            ),
+            vec![expected]
+        );
+    }
+
+    #[rstest]
+    #[case("cd foo && gti status", "Something went wrong", "cd foo && git status")]
+    #[case("gti status", "gti: command not found", "git status")]
+    fn test_get_corrected_commands_for_whole_command(
+        #[case] script: &str,
+        #[case] output: &str,
+        #[case] expected: &str,
+    ) {
+        let rules: Vec<Rc<dyn Rule>> = vec![Rc::new(ReplaceRule {
+            from: "gti".to_owned(),
+            to: "git".to_owned(),
+            side_effect_calls: Rc::new(RefCell::new(vec![])),
+        })];
+        let mut command = CrabCommand::new(script.to_owned(), Some(output.to_owned()), None);
+        let corrected =
+            get_corrected_commands_from_rules(&rules, &mut command, &Bash {}, &FakeContext::new());
+        assert_eq!(corrected[0].script, expected);
+    }
+}
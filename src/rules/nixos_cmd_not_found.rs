@@ -1,4 +1,4 @@
-use super::Rule;
+use super::BuiltinRule;
 use crate::{cli::command::CrabCommand, shell::Shell};
 use regex::Regex;
 
@@ -29,8 +29,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "nixos_cmd_not_found".to_owned(),
         None,
         None,
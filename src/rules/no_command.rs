@@ -1,22 +1,27 @@
-use crate::{
-    cli::command::CrabCommand,
-    shell::Shell,
-    utils::{get_all_executable, get_close_matches},
-};
-use which::which;
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::get_close_matches};
 
-use super::Rule;
+use super::{utils::join_script, BuiltinRule};
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
-    which(&command.script_parts[0]).is_err()
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    let Some(program) = command.script_parts.first() else {
+        return false;
+    };
+    context.which(program).is_none()
         & (if let Some(output) = &command.output {
             output.contains("not found") | output.contains("is not recognized as")
         } else {
-            false
+            // The command was not run, so only rule out the builtins of the shell
+            system_shell
+                .is_some_and(|system_shell| !system_shell.get_builtin_commands().contains(program))
         })
         & !get_close_matches(
-            &command.script_parts[0],
-            get_all_executable()
+            program,
+            context
+                .executables()
                 .iter()
                 .map(|s| s.as_str())
                 .collect::<Vec<&str>>()
@@ -27,11 +32,15 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
         .is_empty()
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     let old_command = &command.script_parts[0];
     let old_parameters = {
         if command.script_parts.len() > 1 {
-            " ".to_string() + &command.script_parts[1..].join(" ")
+            " ".to_string() + &join_script(system_shell, &command.script_parts[1..])
         } else {
             "".to_owned()
         }
@@ -39,7 +48,7 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
 
     // TODO: Check shell history
     let mut new_cmds: Vec<&str> = vec![];
-    let executables = get_all_executable();
+    let executables = context.executables();
     let str_executables = executables
         .iter()
         .map(|s| s.as_str())
@@ -55,14 +64,62 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
         .collect()
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "no_command".to_owned(),
         None,
         Some(3000),
-        None,
+        Some(false),
         Box::new(match_rule),
         get_new_command,
         None,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use crate::shell::Bash;
+    use rstest::rstest;
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_program("vim")
+            .with_program("aptitude")
+            .with_program("apt-get")
+    }
+
+    #[rstest]
+    #[case("vom file.py", "vom: not found", true)]
+    #[case("aptget install vim", "aptget: not found", true)]
+    #[case("vom file.py", "", false)]
+    #[case("vim file.py", "vim: not found", false)]
+    #[case("qwerty", "qwerty: not found", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("vom file.py", true)]
+    #[case("vim file.py", false)]
+    #[case("cd /tmp", false)]
+    #[case("qwerty", false)]
+    fn test_match_without_output(#[case] command: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), None, None);
+        assert_eq!(
+            match_rule(&mut command, Some(&Bash {}), &context()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("vom file.py", vec!["vim file.py"])]
+    #[case("aptget install vim", vec!["apt-get install vim", "aptitude install vim"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command = CrabCommand::new(command.to_owned(), Some("".to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -1,63 +1,126 @@
 use super::{
-    utils::{match_rule_with_is_app, npm::is_npm_available},
-    Rule,
+    utils::{
+        join_script, match_rule_with_is_app,
+        package_json::{get_scripts, get_workspaces},
+    },
+    BuiltinRule,
 };
 use crate::{
     cli::command::CrabCommand,
-    rules::utils::npm::{mockable_get_scripts, run_npm_command},
+    context::Context,
     shell::Shell,
-    utils::replace_command,
+    utils::{get_close_matches, replace_command},
 };
 use regex::Regex;
 
+/// Returns the script npm could not find, from npm 6 (`missing script: dvelop`) or npm 7
+/// and later (`Missing script: "dvelop"`).
+fn get_missing_script(output: &str) -> Option<String> {
+    let re = Regex::new(r#"(?i)missing script: "?([^"\s]+)"#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+/// Returns the workspace given with `-w` or `--workspace`, if any, either by name or path.
+fn get_workspace_argument(command: &CrabCommand) -> Option<String> {
+    let mut parts = command.script_parts.iter();
+    while let Some(part) = parts.next() {
+        if part == "-w" || part == "--workspace" {
+            return parts.next().cloned();
+        }
+        if let Some(workspace) = part.strip_prefix("--workspace=") {
+            return Some(workspace.to_owned());
+        }
+    }
+    None
+}
+
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     if let Some(output) = &command.output {
         command
             .script_parts
             .iter()
             .any(|part| part.starts_with("ru"))
-            && output.contains("npm ERR! missing script: ")
+            && get_missing_script(output).is_some()
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(auxiliary_match_rule, command, vec!["npm"], None)
 }
 
-pub fn mockable_get_new_command<F>(command: &mut CrabCommand, fn_get_scripts: F) -> Vec<String>
-where
-    F: Fn() -> Vec<u8>,
-{
-    if let Some(output) = &command.output {
-        let re = Regex::new(r".*missing script: (.*)\n").unwrap();
-        if let Some(caps) = re.captures(output) {
-            println!("{caps:?}");
-            replace_command(
+/// Runs `new_script` in `workspace` instead of `script` in the current package.
+fn run_in_workspace(
+    command: &CrabCommand,
+    script: &str,
+    workspace: &str,
+    new_script: &str,
+    system_shell: Option<&dyn Shell>,
+) -> Option<String> {
+    let mut parts = command.script_parts.clone();
+    let index = parts.iter().rposition(|part| part == script)?;
+    parts[index] = new_script.to_owned();
+    parts.splice(index..index, ["-w".to_owned(), workspace.to_owned()]);
+    Some(join_script(system_shell, &parts))
+}
+
+/// Scripts of the current package come first, followed by the similar scripts of the other
+/// workspaces of a monorepo, run with `-w`. If a workspace was already given, npm looked for
+/// the script there, so only its scripts are used.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(script) = command.output.as_deref().and_then(get_missing_script) else {
+        return vec![];
+    };
+    let workspaces = get_workspaces(context);
+    if let Some(workspace) = get_workspace_argument(command) {
+        let scripts = workspaces
+            .into_iter()
+            .find(|package| package.name == workspace || package.dir.ends_with(&workspace))
+            .map(|package| package.scripts)
+            .unwrap_or_default();
+        return replace_command(
+            command,
+            &script,
+            scripts.iter().map(|s| s.as_str()).collect(),
+        );
+    }
+    let mut new_commands = replace_command(
+        command,
+        &script,
+        get_scripts(context).iter().map(|s| s.as_str()).collect(),
+    );
+    let current_dir = context.current_dir();
+    for package in workspaces {
+        if current_dir.starts_with(&package.dir) {
+            continue;
+        }
+        let scripts: Vec<&str> = package.scripts.iter().map(|s| s.as_str()).collect();
+        for new_script in get_close_matches(&script, &scripts, None, None) {
+            new_commands.extend(run_in_workspace(
                 command,
-                &caps[1],
-                mockable_get_scripts(fn_get_scripts)
-                    .iter()
-                    .map(|s| s.as_str())
-                    .collect(),
-            )
-        } else {
-            vec![]
+                &script,
+                &package.name,
+                new_script,
+                system_shell,
+            ));
         }
-    } else {
-        vec![]
     }
+    new_commands
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    mockable_get_new_command(command, run_npm_command)
-}
-
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "npm_missing_script".to_owned(),
-        Some(is_npm_available()),
+        None,
         None,
         None,
         Box::new(match_rule),
@@ -68,11 +131,22 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{match_rule, mockable_get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
+    const PACKAGE_JSON: &str = r#"{
+  "name": "code-view-web",
+  "scripts": {
+    "build": "webpack --progress --colors -p --config ./webpack.production.config.js",
+    "develop": "webpack-dev-server --progress --colors",
+    "test": "jest",
+    "watch-test": "jest --verbose --watch"
+  }
+}"#;
+
     fn output(script: &str) -> String {
         format!(
             r#"
@@ -98,38 +172,83 @@ npm ERR!     /home/nvbn/exp/code_view/client_web/npm-debug.log
     #[case("npm wach", &output("wach"), false)]
     #[case("vim live-tes", &output("live-tes"), false)]
     #[case("npm run-script sahare", "", false)]
+    #[case("npm run dvelop", "npm ERR! Missing script: \"dvelop\"", true)]
+    #[case("npm run dvelop", "npm error Missing script: \"dvelop\"", true)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
-    #[case("npm ru wach-tests", &output("wach-tests"), vec!["npm ru watch-test"])]
-    #[case("npm -i run-script dvelop", &output("dvelop"), vec!["npm -i run-script develop", "npm -i run-script build"])]
+    #[case("npm ru wach-tests", &output("wach-tests"), vec!["npm ru watch-test", "npm ru test"])]
+    #[case("npm -i run-script dvelop", &output("dvelop"), vec!["npm -i run-script develop", "npm -i run-script build", "npm -i run-script test"])]
     #[case("npm -i run-script buld -X POST", &output("buld"), vec!["npm -i run-script build -X POST", "npm -i run-script develop -X POST"])]
     fn test_get_new_command(
         #[case] command: &str,
         #[case] stdout: &str,
         #[case] expected: Vec<&str>,
     ) {
-        let run_script_stdout = b"
-Lifecycle scripts included in code-view-web:
-  test
-    jest
-
-available via `npm run-script`:
-  build
-    cp node_modules/ace-builds/src-min/ -a resources/ace/ && webpack --progress --colors -p --config ./webpack.production.config.js
-  develop
-    cp node_modules/ace-builds/src/ -a resources/ace/ && webpack-dev-server --progress --colors
-  watch-test
-    jest --verbose --watch
-
-";
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        let context = FakeContext::new().with_file("package.json", PACKAGE_JSON.as_bytes());
+        assert_eq!(
+            get_new_command(&mut command, Some(&system_shell), &context),
+            expected
+        );
+    }
+
+    fn workspace_output(script: &str) -> String {
+        format!(
+            "npm ERR! Missing script: \"{script}\"
+npm ERR!
+npm ERR! To see a list of scripts, run:
+npm ERR!   npm run
+"
+        )
+    }
+
+    /// A monorepo in `/home/crab/shop` whose `web` and `api` workspaces have their own scripts.
+    fn workspace_context() -> FakeContext {
+        FakeContext::new()
+            .with_file(
+                "shop/package.json",
+                br#"{"name": "shop", "workspaces": ["packages/*"], "scripts": {"lint": "eslint ."}}"#,
+            )
+            .with_file(
+                "shop/packages/web/package.json",
+                br#"{"name": "@shop/web", "scripts": {"develop": "vite", "build": "vite build"}}"#,
+            )
+            .with_file(
+                "shop/packages/api/package.json",
+                br#"{"scripts": {"serve": "node index.js", "build": "tsc"}}"#,
+            )
+            .with_dir("shop/packages/api/node_modules")
+            .with_current_dir("/home/crab/shop")
+    }
+
+    #[rstest]
+    #[case("/home/crab/shop", "npm run dvelop", "dvelop", vec!["npm run lint", "npm run -w @shop/web develop"])]
+    #[case("/home/crab/shop", "npm run dvelop -- --port 3000", "dvelop", vec!["npm run lint -- --port 3000", "npm run -w @shop/web develop -- --port 3000"])]
+    #[case("/home/crab/shop", "npm run -w @shop/web dvelop", "dvelop", vec!["npm run -w @shop/web develop", "npm run -w @shop/web build"])]
+    #[case("/home/crab/shop", "npm run --workspace=packages/api biuld", "biuld", vec!["npm run --workspace=packages/api build"])]
+    #[case("/home/crab/shop", "npm run -w packages/web dvelop", "dvelop", vec!["npm run -w packages/web develop", "npm run -w packages/web build"])]
+    #[case("/home/crab/shop/packages/web", "npm run dvelop", "dvelop", vec!["npm run develop", "npm run build"])]
+    #[case("/home/crab/shop/packages/web", "npm run serv", "serv", vec!["npm run develop", "npm run -w packages/api serve"])]
+    fn test_get_new_command_in_workspaces(
+        #[case] current_dir: &str,
+        #[case] command: &str,
+        #[case] script: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let system_shell = Bash {};
+        let context = workspace_context().with_current_dir(current_dir);
+        let mut command =
+            CrabCommand::new(command.to_owned(), Some(workspace_output(script)), None);
         assert_eq!(
-            mockable_get_new_command(&mut command, || run_script_stdout.to_vec()),
+            get_new_command(&mut command, Some(&system_shell), &context),
             expected
         );
     }
@@ -1,60 +1,83 @@
 use super::{
     utils::{
-        match_rule_with_is_app,
-        npm::{mockable_get_scripts, run_npm_command},
+        join_script, match_rule_with_is_app,
+        package_json::{get_scripts, get_workspaces, Package},
     },
-    Rule,
+    BuiltinRule,
 };
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
-fn mockable_match_rule<F>(command: &CrabCommand, fn_run_npm_command: F) -> bool
-where
-    F: Fn() -> Vec<u8>,
-{
-    if let Some(output) = &command.output {
-        println!("{:?}", output.contains("Usage: npm <command>"));
-        println!(
-            "{:?}",
-            !command
-                .script_parts
-                .iter()
-                .any(|part| part.starts_with("ru"))
-        );
-        println!("{:?}", mockable_get_scripts(&fn_run_npm_command));
-        println!("{:?}", &command.script_parts[1]);
-        println!(
-            "{:?}",
-            mockable_get_scripts(&fn_run_npm_command).contains(&command.script_parts[1])
-        );
+/// Scripts that npm runs without `run-script`.
+const LIFECYCLE_COMMANDS: [&str; 4] = ["restart", "start", "stop", "test"];
 
+/// Returns the other workspaces of the monorepo defining `script`.
+fn get_workspaces_with_script(script: &str, context: &dyn Context) -> Vec<Package> {
+    let current_dir = context.current_dir();
+    get_workspaces(context)
+        .into_iter()
+        .filter(|package| {
+            !current_dir.starts_with(&package.dir) && package.scripts.iter().any(|s| s == script)
+        })
+        .collect()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
+    if let Some(output) = &command.output {
+        let script = &command.script_parts[1];
         output.contains("Usage: npm <command>")
             && !command
                 .script_parts
                 .iter()
                 .any(|part| part.starts_with("ru"))
-            && mockable_get_scripts(fn_run_npm_command).contains(&command.script_parts[1])
+            && !LIFECYCLE_COMMANDS.contains(&script.as_str())
+            && (get_scripts(context).contains(script)
+                || !get_workspaces_with_script(script, context).is_empty())
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(
-        |command| mockable_match_rule(command, run_npm_command),
+        |command| auxiliary_match_rule(command, context),
         command,
         vec!["npm"],
         None,
     )
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    let mut parts = command.script_parts.clone();
-    parts.insert(1, "run-script".to_owned());
-    vec![parts.join(" ")]
+/// Scripts of the current package are run directly, those of the other workspaces of a
+/// monorepo with `-w`.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let script = &command.script_parts[1];
+    let workspaces = get_workspaces_with_script(script, context);
+    let mut new_commands = vec![];
+    if workspaces.is_empty() || get_scripts(context).contains(script) {
+        let mut parts = command.script_parts.clone();
+        parts.insert(1, "run-script".to_owned());
+        new_commands.push(join_script(system_shell, &parts));
+    }
+    for package in workspaces {
+        let mut parts = command.script_parts.clone();
+        parts.splice(
+            1..1,
+            ["run-script".to_owned(), "-w".to_owned(), package.name],
+        );
+        new_commands.push(join_script(system_shell, &parts));
+    }
+    new_commands
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "npm_run_script".to_owned(),
         None,
         None,
@@ -67,12 +90,22 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_new_command, mockable_match_rule};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
-    use crate::rules::utils::match_rule_with_is_app;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
+    const PACKAGE_JSON: &str = r#"{
+  "name": "code-view-web",
+  "scripts": {
+    "build": "webpack --progress --colors -p --config ./webpack.production.config.js",
+    "develop": "webpack-dev-server --progress --colors",
+    "test": "jest",
+    "watch-test": "jest --verbose --watch"
+  }
+}"#;
+
     const OUTPUT: &str = r#"Usage: npm <command>
 
 where <command> is one of:
@@ -98,21 +131,8 @@ Specify configs in the ini-formatted file:
 or on the command line via: npm <command> --key value
 Config info can be viewed via: npm help config"#;
 
-    const RUN_SCRIPT_STDOUT: &[u8] = b"
-Lifecycle scripts included in code-view-web:
-  test
-    jest
-
-available via `npm run-script`:
-  build
-    cp node_modules/ace-builds/src-min/ -a resources/ace/ && webpack --progress --colors -p --config ./webpack.production.config.js
-  develop
-    cp node_modules/ace-builds/src/ -a resources/ace/ && webpack-dev-server --progress --colors
-  watch-test
-    jest --verbose --watch";
-
-    fn mocked_run_npm_command() -> Vec<u8> {
-        RUN_SCRIPT_STDOUT.to_vec()
+    fn context() -> FakeContext {
+        FakeContext::new().with_file("package.json", PACKAGE_JSON.as_bytes())
     }
 
     #[rstest]
@@ -124,15 +144,7 @@ available via `npm run-script`:
     #[case("vim watch-test", OUTPUT, false)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            match_rule_with_is_app(
-                |command| mockable_match_rule(command, mocked_run_npm_command),
-                &command,
-                vec!["npm"],
-                None,
-            ),
-            is_match
-        );
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
     }
 
     #[rstest]
@@ -146,6 +158,47 @@ available via `npm run-script`:
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+
+    /// A monorepo in `/home/crab/shop` whose `web` and `api` workspaces have their own scripts.
+    fn workspace_context() -> FakeContext {
+        FakeContext::new()
+            .with_file(
+                "shop/package.json",
+                br#"{"name": "shop", "workspaces": ["packages/*"], "scripts": {"lint": "eslint ."}}"#,
+            )
+            .with_file(
+                "shop/packages/web/package.json",
+                br#"{"name": "@shop/web", "scripts": {"develop": "vite", "build": "vite build"}}"#,
+            )
+            .with_file(
+                "shop/packages/api/package.json",
+                br#"{"scripts": {"serve": "node index.js", "build": "tsc"}}"#,
+            )
+            .with_dir("shop/packages/api/node_modules")
+            .with_current_dir("/home/crab/shop")
+    }
+
+    #[rstest]
+    #[case("/home/crab/shop", "npm develop", vec!["npm run-script -w @shop/web develop"])]
+    #[case("/home/crab/shop", "npm build", vec!["npm run-script -w packages/api build", "npm run-script -w @shop/web build"])]
+    #[case("/home/crab/shop/packages/web", "npm build", vec!["npm run-script build", "npm run-script -w packages/api build"])]
+    #[case("/home/crab/shop", "npm lint", vec!["npm run-script lint"])]
+    fn test_get_new_command_in_workspaces(
+        #[case] current_dir: &str,
+        #[case] command: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = workspace_context().with_current_dir(current_dir);
+        let mut command = CrabCommand::new(command.to_owned(), Some(OUTPUT.to_owned()), None);
+        assert!(match_rule(&mut command, None, &context));
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+
+    #[test]
+    fn test_not_match_unknown_script_in_workspaces() {
+        let mut command = CrabCommand::new("npm deploy".to_owned(), Some(OUTPUT.to_owned()), None);
+        assert!(!match_rule(&mut command, None, &workspace_context()));
     }
 }
@@ -0,0 +1,156 @@
+use super::{
+    utils::{
+        archlinux::{get_pacman, get_pkgfile},
+        wrappers::get_wrapped_program,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("not found")
+            && get_pacman(context).is_some()
+            && get_wrapped_program(&command.script, context)
+                .is_some_and(|executable| !get_pkgfile(&executable, context).is_empty())
+    } else {
+        false
+    }
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let (Some(pacman), Some(executable)) = (
+        get_pacman(context),
+        get_wrapped_program(&command.script, context),
+    ) else {
+        return vec![];
+    };
+    get_pkgfile(&executable, context)
+        .iter()
+        .map(|package| {
+            system_shell
+                .unwrap()
+                .and(vec![&format!("{pacman} -S {package}"), &command.script])
+        })
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "pacman".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use crate::shell::Bash;
+    use rstest::rstest;
+
+    const PKGFILE_VIM: &str = "extra/gvim 9.1.0-1\t/usr/bin/vim\nextra/vim 9.1.0-1\t/usr/bin/vim\n";
+
+    #[rstest]
+    #[case(
+        "vim",
+        "vim: command not found",
+        FakeContext::new().with_program("pacman").with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        true
+    )]
+    #[case(
+        "sudo vim",
+        "sudo: vim: command not found",
+        FakeContext::new().with_program("pacman").with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        true
+    )]
+    #[case(
+        "convert a.png a.jpg",
+        "convert: command not found",
+        FakeContext::new()
+            .with_program("pacman")
+            .with_command_output("pacman -F -q /usr/bin/convert", "extra/imagemagick\n"),
+        true
+    )]
+    #[case(
+        "vim",
+        "",
+        FakeContext::new().with_program("pacman").with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        false
+    )]
+    #[case(
+        "foo",
+        "foo: command not found",
+        FakeContext::new().with_program("pacman").with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        false
+    )]
+    #[case(
+        "vim",
+        "vim: command not found",
+        FakeContext::new().with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        false
+    )]
+    fn test_match(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] context: FakeContext,
+        #[case] is_match: bool,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
+    }
+
+    #[rstest]
+    #[case(
+        "vim",
+        FakeContext::new().with_program("pacman").with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        vec!["sudo pacman -S extra/gvim && vim", "sudo pacman -S extra/vim && vim"]
+    )]
+    #[case(
+        "sudo vim",
+        FakeContext::new().with_program("pacman").with_command_output("pkgfile -b -v vim", PKGFILE_VIM),
+        vec!["sudo pacman -S extra/gvim && sudo vim", "sudo pacman -S extra/vim && sudo vim"]
+    )]
+    #[case(
+        "convert a.png a.jpg",
+        FakeContext::new()
+            .with_program("pacman")
+            .with_program("yay")
+            .with_command_output("pacman -F -q /usr/bin/convert", "extra/imagemagick\n"),
+        vec!["yay -S extra/imagemagick && convert a.png a.jpg"]
+    )]
+    #[case(
+        "convert a.png a.jpg",
+        FakeContext::new()
+            .with_program("pacman")
+            .with_program("paru")
+            .with_command_output("pacman -F -q /usr/bin/convert", "extra/imagemagick\n"),
+        vec!["paru -S extra/imagemagick && convert a.png a.jpg"]
+    )]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] context: FakeContext,
+        #[case] expected: Vec<&str>,
+    ) {
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new(command.to_owned(), Some("".to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, Some(&system_shell), &context),
+            expected
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use super::{
+    get_new_command_without_sudo, match_rule_without_sudo,
+    utils::{archlinux::PACMAN_APPS, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+use regex::Regex;
+
+/// Operations are uppercase, e.g. `-S`, while lowercase letters are options of them.
+fn get_operation_regex() -> Regex {
+    Regex::new(r" -([dfqrstuv])").unwrap()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        output.starts_with("error: invalid option '-")
+            && get_operation_regex().is_match(&command.script)
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, PACMAN_APPS.to_vec(), None),
+        command,
+        context,
+    )
+}
+
+fn auxiliary_get_new_command(command: &CrabCommand) -> Vec<String> {
+    vec![get_operation_regex()
+        .replace(&command.script, |caps: &regex::Captures| {
+            format!(" -{}", caps[1].to_uppercase())
+        })
+        .into_owned()]
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(auxiliary_get_new_command, command, context)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "pacman_invalid_option".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const BAD_OPTION: &str = "error: invalid option '-s'";
+
+    #[rstest]
+    #[case("pacman -s vim", BAD_OPTION, true)]
+    #[case("sudo pacman -s vim", BAD_OPTION, true)]
+    #[case("yay -syu", "error: invalid option '-y'", true)]
+    #[case("paru -qi vim", "error: invalid option '-q'", true)]
+    #[case("pacman -S vim", "", false)]
+    #[case("pacman -k", "error: invalid option '-k'", false)]
+    #[case("apt -s vim", BAD_OPTION, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("pacman -s vim", vec!["pacman -S vim"])]
+    #[case("sudo pacman -s vim", vec!["sudo pacman -S vim"])]
+    #[case("yay -syu", vec!["yay -Syu"])]
+    #[case("pacman -r vim -v", vec!["pacman -R vim -v"])]
+    #[case("paru -qi vim", vec!["paru -Qi vim"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(BAD_OPTION.to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use super::{
+    match_rule_without_sudo,
+    utils::{
+        archlinux::{get_packages, PACMAN_APPS},
+        match_rule_with_is_app,
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+/// Returns the first package pacman could not find, e.g. `vmi` in
+/// `error: target not found: vmi`.
+fn get_missing_target(output: &str) -> Option<String> {
+    let re = Regex::new(r"error: target not found: (\S+)").unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_target(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, PACMAN_APPS.to_vec(), None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(target) = command.output.as_deref().and_then(get_missing_target) else {
+        return vec![];
+    };
+    let packages = get_packages(context);
+    let packages = packages.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    replace_command(command, &target, packages)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "pacman_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const PACKAGES: &str = "linux\nvim\nvim-runtime\nneovim\n";
+
+    #[rstest]
+    #[case("pacman -S vmi", "error: target not found: vmi", true)]
+    #[case("sudo pacman -S vmi", "error: target not found: vmi", true)]
+    #[case("yay -S vmi", "error: target not found: vmi", true)]
+    #[case("paru -S vmi", " -> error: target not found: vmi", true)]
+    #[case("pacman -S vim", "", false)]
+    #[case("apt install vmi", "error: target not found: vmi", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("pacman -S vmi", vec!["pacman -S vim", "pacman -S neovim", "pacman -S vim-runtime"])]
+    #[case("sudo pacman -S vmi --needed", vec!["sudo pacman -S vim --needed", "sudo pacman -S neovim --needed", "sudo pacman -S vim-runtime --needed"])]
+    #[case("yay -Ss vmi", vec!["yay -Ss vim", "yay -Ss neovim", "yay -Ss vim-runtime"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let context = FakeContext::new().with_command_output("pacman -S -l -q", PACKAGES);
+        let mut command = CrabCommand::new(
+            command.to_owned(),
+            Some("error: target not found: vmi".to_owned()),
+            None,
+        );
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
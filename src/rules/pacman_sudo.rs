@@ -0,0 +1,100 @@
+use super::{
+    utils::{
+        archlinux::get_aur_helper,
+        match_rule_with_is_app,
+        wrappers::{get_privilege_command, unwrap_script},
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("you cannot perform this operation unless you are root")
+            && !unwrap_script(&command.script, context).is_escalated()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(
+        |command| auxiliary_match_rule(command, context),
+        command,
+        vec!["pacman"],
+        None,
+    )
+}
+
+/// AUR helpers ask for privileges themselves, so they are suggested first if installed.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let mut new_commands = vec![];
+    if let Some(helper) = get_aur_helper(context) {
+        let arguments = command.script.strip_prefix(&command.script_parts[0]);
+        new_commands.push(format!("{helper}{}", arguments.unwrap_or_default()));
+    }
+    new_commands.push(format!(
+        "{} {}",
+        get_privilege_command(context),
+        command.script
+    ));
+    new_commands
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "pacman_sudo".to_owned(),
+        None,
+        Some(900),
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NOT_ROOT: &str = "error: you cannot perform this operation unless you are root.";
+
+    #[rstest]
+    #[case("pacman -Syu", NOT_ROOT, true)]
+    #[case("pacman -S vim", NOT_ROOT, true)]
+    #[case("sudo pacman -S vim", NOT_ROOT, false)]
+    #[case("pacman -Ss vim", "", false)]
+    #[case("yay -S vim", NOT_ROOT, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("pacman -Syu", FakeContext::new(), vec!["sudo pacman -Syu"])]
+    #[case("pacman -S vim", FakeContext::new().with_program("doas"), vec!["doas pacman -S vim"])]
+    #[case("pacman -S vim", FakeContext::new().with_program("yay"), vec!["yay -S vim", "sudo pacman -S vim"])]
+    #[case("pacman -Rns vim", FakeContext::new().with_program("paru"), vec!["paru -Rns vim", "sudo pacman -Rns vim"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] context: FakeContext,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(NOT_ROOT.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
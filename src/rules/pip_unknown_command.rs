@@ -1,5 +1,5 @@
-use super::{match_rule_without_sudo, utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_argument};
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_argument};
 use regex::Regex;
 
 fn auxiliary_match_rule(command: &CrabCommand) -> bool {
@@ -12,7 +12,11 @@ fn auxiliary_match_rule(command: &CrabCommand) -> bool {
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_without_sudo(
         |command| {
             match_rule_with_is_app(
@@ -23,10 +27,15 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
             )
         },
         command,
+        context,
     )
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     if let Some(output) = &command.output {
         println!("{output:?}");
         let broken_cmd = Regex::new(r#"ERROR: unknown command "([^"]+)""#)
@@ -45,8 +54,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "pip_unknown_command".to_owned(),
         None,
         None,
@@ -61,6 +70,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use rstest::rstest;
 
     fn pip_unknown_cmd(broken: &str, suggested: &str) -> String {
@@ -72,7 +82,10 @@ mod tests {
     #[case("pip i", r#"ERROR: unknown command "i""#, false)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
@@ -84,6 +97,9 @@ mod tests {
         #[case] expected: Vec<&str>,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
@@ -0,0 +1,105 @@
+use super::{
+    utils::{match_rule_with_is_app, package_json::get_scripts},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+/// Returns the script pnpm could not find, from `pnpm run` (`Missing script: buidl`) or
+/// from `pnpm <script>` (`Command "buidl" not found`).
+pub fn get_missing_script(output: &str) -> Option<String> {
+    let re = Regex::new(r#"(?:Missing script: |Command ")([^"\s]+)"#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_script(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["pnpm"], Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    match command.output.as_deref().and_then(get_missing_script) {
+        Some(script) => replace_command(
+            command,
+            &script,
+            get_scripts(context).iter().map(|s| s.as_str()).collect(),
+        ),
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "pnpm_missing_script".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const PACKAGE_JSON: &str = r#"{
+  "name": "code-view-web",
+  "scripts": {
+    "build": "webpack --progress --colors -p --config ./webpack.production.config.js",
+    "develop": "webpack-dev-server --progress --colors",
+    "test": "jest",
+    "watch-test": "jest --verbose --watch"
+  }
+}"#;
+
+    const RUN_OUTPUT: &str = " ERR_PNPM_NO_SCRIPT  Missing script: dvelop
+
+Command \"dvelop\" not found.";
+    const EXEC_OUTPUT: &str = " ERR_PNPM_RECURSIVE_EXEC_FIRST_FAIL  Command \"dvelop\" not found";
+
+    #[rstest]
+    #[case("pnpm run dvelop", RUN_OUTPUT, true)]
+    #[case("pnpm dvelop", EXEC_OUTPUT, true)]
+    #[case("pnpm develop", "", false)]
+    #[case("yarn dvelop", EXEC_OUTPUT, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("pnpm run dvelop", RUN_OUTPUT, vec!["pnpm run develop", "pnpm run build", "pnpm run test"])]
+    #[case("pnpm dvelop", EXEC_OUTPUT, vec!["pnpm develop", "pnpm build", "pnpm test"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = FakeContext::new().with_file("package.json", PACKAGE_JSON.as_bytes());
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
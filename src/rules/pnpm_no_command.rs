@@ -0,0 +1,125 @@
+use super::{pnpm_missing_script::get_missing_script, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{
+    cli::command::CrabCommand,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+
+const COMMANDS: [&str; 41] = [
+    "add",
+    "audit",
+    "bin",
+    "config",
+    "create",
+    "dedupe",
+    "deploy",
+    "dlx",
+    "doctor",
+    "env",
+    "exec",
+    "fetch",
+    "help",
+    "import",
+    "init",
+    "install",
+    "install-test",
+    "licenses",
+    "link",
+    "list",
+    "outdated",
+    "pack",
+    "patch",
+    "patch-commit",
+    "prune",
+    "publish",
+    "rebuild",
+    "recursive",
+    "remove",
+    "restart",
+    "root",
+    "run",
+    "self-update",
+    "server",
+    "setup",
+    "start",
+    "store",
+    "test",
+    "unlink",
+    "update",
+    "why",
+];
+
+/// pnpm runs unknown commands as scripts or binaries, so a mistyped subcommand is
+/// reported as a command that was not found.
+fn get_unknown_command(command: &CrabCommand) -> Option<String> {
+    let unknown = get_missing_script(command.output.as_deref()?)?;
+    (command.script_parts.get(1) == Some(&unknown)).then_some(unknown)
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    get_unknown_command(command).is_some()
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["pnpm"], Some(1))
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    match get_unknown_command(command) {
+        Some(unknown) => get_close_matches(&unknown, &COMMANDS, None, None)
+            .iter()
+            .map(|new| replace_argument(&command.script, &unknown, new))
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "pnpm_no_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    fn output(command: &str) -> String {
+        format!(" ERR_PNPM_RECURSIVE_EXEC_FIRST_FAIL  Command \"{command}\" not found")
+    }
+
+    #[rstest]
+    #[case("pnpm isntall", &output("isntall"), true)]
+    #[case("pnpm ad -D vitest", &output("ad"), true)]
+    #[case(
+        "pnpm run dvelop",
+        " ERR_PNPM_NO_SCRIPT  Missing script: dvelop",
+        false
+    )]
+    #[case("pnpm install", "", false)]
+    #[case("yarn isntall", &output("isntall"), false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("pnpm isntall", &output("isntall"), vec!["pnpm install", "pnpm install-test", "pnpm list"])]
+    #[case("pnpm ad -D vitest", &output("ad"), vec!["pnpm add -D vitest"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
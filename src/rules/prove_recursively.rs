@@ -1,47 +1,57 @@
-use super::{utils::match_rule_with_is_app, Rule};
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use std::path::Path;
 
 fn is_recursive(part: &str) -> bool {
     part == "--recurse" || (!part.starts_with("--") && part.starts_with('-') && part.contains('r'))
 }
 
-fn is_dir(part: &str) -> bool {
-    !part.starts_with('-') && Path::new(part).exists()
+fn is_dir(part: &str, context: &dyn Context) -> bool {
+    !part.starts_with('-') && context.exists(Path::new(part))
 }
 
-fn auxiliary_match_rule<F>(command: &CrabCommand, fn_is_dir: F) -> bool
-where
-    F: Fn(&str) -> bool,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         output.contains("NOTESTS")
             && !command.script_parts[1..]
                 .iter()
                 .any(|part| is_recursive(part))
-            && command.script_parts[1..].iter().any(|part| fn_is_dir(part))
+            && command.script_parts[1..]
+                .iter()
+                .any(|part| is_dir(part, context))
     } else {
         false
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(
-        |command| auxiliary_match_rule(command, is_dir),
+        |command| auxiliary_match_rule(command, context),
         command,
         vec!["prove"],
         None,
     )
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     let mut parts = command.script_parts.clone();
     parts.insert(1, "-r".to_owned());
-    vec![parts.join(" ")]
+    vec![join_script(system_shell, &parts)]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "prove_recursively".to_owned(),
         None,
         None,
@@ -54,9 +64,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{auxiliary_match_rule, get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
-    use crate::rules::utils::match_rule_with_is_app;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -76,15 +86,11 @@ mod tests {
         #[case] is_match: bool,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            match_rule_with_is_app(
-                |command| auxiliary_match_rule(command, |s| is_dir),
-                &command,
-                vec!["prove"],
-                None,
-            ),
-            is_match
-        );
+        let mut context = FakeContext::new();
+        if is_dir {
+            context = context.with_dir("t").with_dir("app/t");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
     }
 
     #[rstest]
@@ -97,6 +103,9 @@ mod tests {
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
@@ -1,4 +1,4 @@
-use super::Rule;
+use super::BuiltinRule;
 use crate::{cli::command::CrabCommand, shell::Shell};
 use regex::Regex;
 
@@ -18,8 +18,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
         .to_owned()]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "remove_shell_prompt_literal".to_owned(),
         None,
         None,
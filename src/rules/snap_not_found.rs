@@ -0,0 +1,111 @@
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+fn get_missing_snap(output: &str) -> Option<String> {
+    let re = Regex::new(r#"error: snap "([^"]+)" not found"#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+/// Returns the names of the snaps the store suggests for `name`, from the table printed
+/// by `snap find`, whose first column is the name.
+fn find_snaps(name: &str, context: &dyn Context) -> Vec<String> {
+    context
+        .run("snap", &["find", name])
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_whitespace().next())
+                .map(|snap| snap.to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_snap(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["snap"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(snap) = command.output.as_deref().and_then(get_missing_snap) else {
+        return vec![];
+    };
+    let snaps = find_snaps(&snap, context);
+    let snaps = snaps.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    replace_command(command, &snap, snaps)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "snap_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NOT_FOUND: &str = "error: snap \"spotfy\" not found";
+    const FIND: &str = "\
+Name              Version   Publisher     Notes  Summary
+spotify           1.2.31    spotify✓      -      Music for everyone
+spotify-qt        v3.11     kraxarn       -      Lightweight Spotify client using Qt
+";
+
+    #[rstest]
+    #[case("snap install spotfy", NOT_FOUND, true)]
+    #[case("sudo snap install --classic spotfy", NOT_FOUND, true)]
+    #[case(
+        "snap install spotify",
+        "spotify 1.2.31 from Spotify✓ installed",
+        false
+    )]
+    #[case("flatpak install spotfy", NOT_FOUND, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("snap install spotfy", vec!["snap install spotify", "snap install spotify-qt"])]
+    #[case("sudo snap install spotfy --classic", vec!["sudo snap install spotify --classic", "sudo snap install spotify-qt --classic"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let context = FakeContext::new().with_command_output("snap find spotfy", FIND);
+        let mut command = CrabCommand::new(command.to_owned(), Some(NOT_FOUND.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
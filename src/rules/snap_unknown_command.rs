@@ -0,0 +1,146 @@
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{
+    cli::command::CrabCommand,
+    context::Context,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+use regex::Regex;
+
+const COMMANDS: [&str; 45] = [
+    "abort",
+    "ack",
+    "alias",
+    "aliases",
+    "changes",
+    "connect",
+    "connections",
+    "disable",
+    "disconnect",
+    "download",
+    "enable",
+    "find",
+    "forget",
+    "get",
+    "help",
+    "info",
+    "install",
+    "interface",
+    "known",
+    "list",
+    "login",
+    "logout",
+    "logs",
+    "pack",
+    "prefer",
+    "refresh",
+    "remove",
+    "restart",
+    "restore",
+    "revert",
+    "run",
+    "save",
+    "saved",
+    "services",
+    "set",
+    "start",
+    "stop",
+    "switch",
+    "tasks",
+    "try",
+    "unalias",
+    "unset",
+    "version",
+    "watch",
+    "whoami",
+];
+
+fn get_unknown_command(output: &str) -> Option<String> {
+    let re = Regex::new(r#"error: unknown command "([^"]+)""#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["snap"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    match command.output.as_deref().and_then(get_unknown_command) {
+        Some(unknown) => get_close_matches(&unknown, &COMMANDS, None, None)
+            .iter()
+            .map(|new| replace_argument(&command.script, &unknown, new))
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "snap_unknown_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNKNOWN_INSTALL: &str = "error: unknown command \"isntall\", see 'snap help'.";
+    const UNKNOWN_REFRESH: &str = "error: unknown command \"refesh\", see 'snap help'.";
+
+    #[rstest]
+    #[case("snap isntall vlc", UNKNOWN_INSTALL, true)]
+    #[case("sudo snap refesh", UNKNOWN_REFRESH, true)]
+    #[case("snap install vlc", "vlc 3.0.20 from VideoLAN installed", false)]
+    #[case("pip isntall vlc", UNKNOWN_INSTALL, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("snap isntall vlc", UNKNOWN_INSTALL, vec!["snap install vlc", "snap list vlc", "snap unalias vlc"])]
+    #[case("sudo snap refesh", UNKNOWN_REFRESH, vec!["sudo snap refresh", "sudo snap prefer", "sudo snap restore"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
+    }
+}
@@ -1,5 +1,15 @@
-use super::Rule;
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{
+    utils::{
+        quote,
+        wrappers::{get_privilege_command, unwrap_script},
+    },
+    BuiltinRule,
+};
+use crate::{
+    cli::{command::CrabCommand, compound::split_segments},
+    context::Context,
+    shell::Shell,
+};
 
 const PATTERNS: [&str; 28] = [
     "permission denied",
@@ -32,11 +42,18 @@ const PATTERNS: [&str; 28] = [
     "updatedb: can not open a temporary file",
 ];
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    // Commands that exited successfully only printed a warning about permissions
+    if command.succeeded() {
+        return false;
+    }
     if let Some(output) = &command.output {
-        if !command.script_parts.is_empty()
-            && !command.script_parts.contains(&"&&".to_owned())
-            && command.script_parts[0] == "sudo"
+        if !command.script_parts.contains(&"&&".to_owned())
+            && unwrap_script(&command.script, context).is_escalated()
         {
             return false;
         }
@@ -49,30 +66,41 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
     false
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+/// Removes the privilege escalation wrappers, with their options, from every command of
+/// the script, since all of it runs with elevated privileges.
+fn without_escalation(script: &str, context: &dyn Context) -> String {
+    let mut new_script = script.to_owned();
+    for segment in split_segments(script).iter().rev() {
+        let unwrapped = unwrap_script(&segment.script, context);
+        new_script = segment.splice(&new_script, &unwrapped.without_escalation());
+    }
+    new_script
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let privilege_command = get_privilege_command(context);
     if command.script.contains("&&") {
+        let script = without_escalation(&command.script, context);
         vec![format!(
-            "sudo sh -c \"{}\"",
-            command
-                .script_parts
-                .iter()
-                .filter(|s| s != &"sudo")
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(" ")
+            "{privilege_command} sh -c {}",
+            quote(system_shell, &script)
         )]
     } else if command.script.contains('>') {
         vec![format!(
-            "sudo sh -c \"{}\"",
-            command.script.replace('"', "\\\"")
+            "{privilege_command} sh -c {}",
+            quote(system_shell, &command.script)
         )]
     } else {
-        vec![format!("sudo {}", command.script)]
+        vec![format!("{privilege_command} {}", command.script)]
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "sudo".to_owned(),
         None,
         None,
@@ -87,6 +115,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -105,6 +134,7 @@ mod tests {
         true
     )]
     #[case("", "must be root", true)]
+    #[case("", "You need to be root to perform this command.", true)]
     #[case("", "You don't have access to the history DB.", true)]
     #[case(
         "",
@@ -113,16 +143,45 @@ mod tests {
     )]
     #[case("", "", false)]
     #[case("sudo ls", "Permission denied", false)]
+    #[case("sudo -u deploy ls", "Permission denied", false)]
+    #[case("doas ls", "Permission denied", false)]
+    #[case("env FOO=1 run0 ls", "Permission denied", false)]
+    #[case("nice -n 5 ls", "Permission denied", true)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case(Some(1), None, true)]
+    #[case(None, Some(9), true)]
+    #[case(None, None, true)]
+    #[case(Some(0), None, false)]
+    fn test_match_exit_status(
+        #[case] exit_code: Option<i32>,
+        #[case] signal: Option<i32>,
+        #[case] is_match: bool,
+    ) {
+        let mut command = CrabCommand::new(
+            "ls /root".to_owned(),
+            None,
+            Some("ls: cannot open directory '/root': Permission denied".to_owned()),
+        )
+        .with_exit_status(exit_code, signal);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
     #[case("ls", "Permission denied", vec!["sudo ls"])]
-    #[case("echo a > b", "Permission denied", vec!["sudo sh -c \"echo a > b\""])]
-    #[case("echo \"a\" >> b", "Permission denied", vec!["sudo sh -c \"echo \\\"a\\\" >> b\""])]
-    #[case("mkdir && touch a", "Permission denied", vec!["sudo sh -c \"mkdir && touch a\""])]
+    #[case("echo a > b", "Permission denied", vec!["sudo sh -c 'echo a > b'"])]
+    #[case("echo \"a\" >> b", "Permission denied", vec!["sudo sh -c 'echo \"a\" >> b'"])]
+    #[case("mkdir && touch a", "Permission denied", vec!["sudo sh -c 'mkdir && touch a'"])]
     fn test_get_new_command(
         #[case] command: &str,
         #[case] stdout: &str,
@@ -130,6 +189,43 @@ mod tests {
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        let context = FakeContext::new().with_program("sudo");
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+
+    #[rstest]
+    #[case(FakeContext::new().with_program("doas"), "ls", vec!["doas ls"])]
+    #[case(FakeContext::new().with_program("run0"), "echo a > b", vec!["run0 sh -c 'echo a > b'"])]
+    #[case(
+        FakeContext::new().with_program("doas"),
+        "doas mkdir a && touch a/b",
+        vec!["doas sh -c 'mkdir a && touch a/b'"]
+    )]
+    #[case(
+        FakeContext::new().with_program("sudo"),
+        "sudo -u deploy mkdir a && sudo -u deploy touch a/b",
+        vec!["sudo sh -c 'mkdir a && touch a/b'"]
+    )]
+    #[case(
+        FakeContext::new().with_program("doas"),
+        "nice -n 5 doas -u root make && doas make install",
+        vec!["doas sh -c 'nice -n 5 make && make install'"]
+    )]
+    #[case(
+        FakeContext::new().with_program("sudo").with_env("OHCRAB_PRIVILEGE_COMMAND", "sudo -E"),
+        "make install",
+        vec!["sudo -E make install"]
+    )]
+    fn test_get_new_command_with_preferred_tool(
+        #[case] context: FakeContext,
+        #[case] command: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(
+            command.to_owned(),
+            Some("Permission denied".to_owned()),
+            None,
+        );
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
     }
 }
@@ -1,26 +1,18 @@
-use super::{utils::match_rule_with_is_app, Rule};
+use super::{utils::match_rule_with_is_app, BuiltinRule};
 use crate::utils::replace_argument;
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 use regex::Regex;
-use which::which;
-
-fn is_terminal_command(terminal_command: &str) -> bool {
-    which(terminal_command).is_ok()
-}
 
 fn get_command_name(command_output: &str) -> Option<String> {
     let re = Regex::new(r"sudo: (.*): command not found").unwrap();
     re.captures(command_output).map(|caps| caps[1].to_owned())
 }
 
-fn auxiliary_match_rule<F>(command: &CrabCommand, fn_is_terminal_command: F) -> bool
-where
-    F: Fn(&str) -> bool,
-{
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
     if let Some(output) = &command.output {
         if output.contains("command not found") {
             if let Some(command_name) = get_command_name(output) {
-                fn_is_terminal_command(&command_name)
+                context.which(&command_name).is_some()
             } else {
                 false
             }
@@ -32,16 +24,24 @@ where
     }
 }
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     match_rule_with_is_app(
-        |command| auxiliary_match_rule(command, is_terminal_command),
+        |command| auxiliary_match_rule(command, context),
         command,
         vec!["sudo"],
         None,
     )
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
     if let Some(output) = &command.output {
         let command_name = get_command_name(output);
         if let Some(command_name) = command_name {
@@ -58,8 +58,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
     }
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "sudo_command_from_user_path".to_owned(),
         None,
         None,
@@ -72,9 +72,9 @@ pub fn get_rule() -> Rule {
 
 #[cfg(test)]
 mod tests {
-    use super::{auxiliary_match_rule, get_new_command};
+    use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
-    use crate::rules::utils::match_rule_with_is_app;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -95,15 +95,11 @@ mod tests {
         #[case] is_match: bool,
     ) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(
-            match_rule_with_is_app(
-                |command| auxiliary_match_rule(command, |s| is_terminal_command),
-                &command,
-                vec!["sudo"],
-                None,
-            ),
-            is_match
-        );
+        let mut context = FakeContext::new();
+        if is_terminal_command {
+            context = context.with_program("npm").with_program("appcfg");
+        }
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
     }
 
     #[rstest]
@@ -116,6 +112,9 @@ mod tests {
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
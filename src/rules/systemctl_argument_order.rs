@@ -0,0 +1,142 @@
+use super::{
+    get_new_command_without_sudo, match_rule_without_sudo,
+    utils::{join_script, match_rule_with_is_app, systemd::UNIT_COMMANDS},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+/// Options of systemctl that take the following argument as their value.
+const OPTIONS_WITH_VALUE: [&str; 6] = ["-H", "-M", "--host", "--machine", "-p", "-t"];
+
+/// Returns the positions of the argument systemctl took as the command, e.g. `nginx` in
+/// `systemctl nginx start`, and of the actual command after it.
+fn get_swapped_positions(script_parts: &[String]) -> Option<(usize, usize)> {
+    let mut unknown = 1;
+    loop {
+        let part = script_parts.get(unknown)?;
+        if !part.starts_with('-') {
+            break;
+        }
+        unknown += if OPTIONS_WITH_VALUE.contains(&part.as_str()) {
+            2
+        } else {
+            1
+        };
+    }
+    let known = script_parts
+        .iter()
+        .skip(unknown + 1)
+        .position(|part| UNIT_COMMANDS.contains(&part.as_str()))?
+        + unknown
+        + 1;
+    Some((unknown, known))
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        (output.contains("Unknown command verb") || output.contains("Unknown operation"))
+            && get_swapped_positions(&command.script_parts).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["systemctl"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(
+        |command| {
+            let Some((unknown, known)) = get_swapped_positions(&command.script_parts) else {
+                return vec![];
+            };
+            let mut parts = command.script_parts.clone();
+            let verb = parts.remove(known);
+            parts.insert(unknown, verb);
+            vec![join_script(system_shell, &parts)]
+        },
+        command,
+        context,
+    )
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "systemctl_argument_order".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("systemctl nginx start", "Unknown command verb 'nginx'.", true)]
+    #[case("sudo systemctl nginx restart", "Unknown command verb nginx.", true)]
+    #[case(
+        "systemctl --user pipewire status",
+        "Unknown operation 'pipewire'.",
+        true
+    )]
+    #[case(
+        "systemctl -H crab@host nginx start",
+        "Unknown command verb 'nginx'.",
+        true
+    )]
+    #[case(
+        "systemctl -H crab@host start",
+        "Unknown command verb 'crab@host'.",
+        false
+    )]
+    #[case("systemctl nginx", "Unknown command verb 'nginx'.", false)]
+    #[case("systemctl strat nginx", "Unknown command verb 'strat'.", false)]
+    #[case("systemctl start nginx", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("systemctl nginx start", vec!["systemctl start nginx"])]
+    #[case("sudo systemctl nginx restart", vec!["sudo systemctl restart nginx"])]
+    #[case("systemctl --user pipewire status", vec!["systemctl --user status pipewire"])]
+    #[case("systemctl nginx php-fpm reload", vec!["systemctl reload nginx php-fpm"])]
+    #[case("systemctl -H crab@host nginx start", vec!["systemctl -H crab@host start nginx"])]
+    #[case("systemctl -t service --machine box nginx status", vec!["systemctl -t service --machine box status nginx"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command = CrabCommand::new(
+            command.to_owned(),
+            Some("Unknown command verb 'nginx'.".to_owned()),
+            None,
+        );
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
+    }
+}
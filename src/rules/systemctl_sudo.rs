@@ -0,0 +1,86 @@
+use super::{
+    utils::{match_rule_with_is_app, systemd::is_user_manager, wrappers::get_privilege_command},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        (output.contains("Interactive authentication required") || output.contains("Access denied"))
+            && !is_user_manager(&command.script_parts)
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["systemctl"], Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    vec![format!(
+        "{} {}",
+        get_privilege_command(context),
+        command.script
+    )]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "systemctl_sudo".to_owned(),
+        None,
+        Some(900),
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const INTERACTIVE: &str =
+        "Failed to restart nginx.service: Interactive authentication required.
+See system logs and 'systemctl status nginx.service' for details.";
+    const ACCESS_DENIED: &str = "Failed to enable unit: Access denied";
+
+    #[rstest]
+    #[case("systemctl restart nginx", INTERACTIVE, true)]
+    #[case("systemctl enable --now nginx", ACCESS_DENIED, true)]
+    #[case("sudo systemctl restart nginx", INTERACTIVE, false)]
+    #[case("systemctl --user restart pipewire", ACCESS_DENIED, false)]
+    #[case("systemctl status nginx", "Active: active (running)", false)]
+    #[case("service nginx restart", INTERACTIVE, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("systemctl restart nginx", FakeContext::new(), vec!["sudo systemctl restart nginx"])]
+    #[case("systemctl daemon-reload", FakeContext::new().with_program("doas"), vec!["doas systemctl daemon-reload"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] context: FakeContext,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(INTERACTIVE.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
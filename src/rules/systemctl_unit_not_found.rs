@@ -0,0 +1,152 @@
+use super::{
+    get_new_command_without_sudo, match_rule_without_sudo,
+    utils::{
+        match_rule_with_is_app,
+        systemd::{get_close_units, is_same_unit, is_user_manager},
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_argument};
+use regex::Regex;
+
+/// Returns the unit as it was typed, e.g. `ngnix` for `Unit ngnix.service not found.`
+pub fn get_missing_unit(command: &CrabCommand) -> Option<String> {
+    let re = Regex::new(r"Unit (\S+) (?:not found|could not be found)").unwrap();
+    let caps = re.captures(command.output.as_deref()?)?;
+    command
+        .script_parts
+        .iter()
+        .skip(1)
+        .find(|part| is_same_unit(part, &caps[1]))
+        .cloned()
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| {
+            match_rule_with_is_app(
+                |command| get_missing_unit(command).is_some(),
+                command,
+                vec!["systemctl"],
+                None,
+            )
+        },
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    get_new_command_without_sudo(
+        |command| {
+            let Some(unit) = get_missing_unit(command) else {
+                return vec![];
+            };
+            get_close_units(&unit, is_user_manager(&command.script_parts), context)
+                .iter()
+                .map(|new_unit| replace_argument(&command.script, &unit, new_unit))
+                .collect()
+        },
+        command,
+        context,
+    )
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "systemctl_unit_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNIT_FILES: &str = "\
+cron.service                               enabled         enabled
+nginx.service                              enabled         enabled
+nginx-debug.service                        disabled        enabled
+ssh.socket                                 disabled        enabled
+timers.target                              static          -
+";
+    const USER_UNIT_FILES: &str = "\
+pipewire.service                           enabled         enabled
+syncthing.service                          enabled         enabled
+";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_program("systemctl")
+            .with_command_output(
+                "systemctl list-unit-files --no-legend --no-pager",
+                UNIT_FILES,
+            )
+            .with_command_output(
+                "systemctl --user list-unit-files --no-legend --no-pager",
+                USER_UNIT_FILES,
+            )
+    }
+
+    #[rstest]
+    #[case(
+        "systemctl restart ngnix",
+        "Failed to restart ngnix.service: Unit ngnix.service not found.",
+        true
+    )]
+    #[case(
+        "sudo systemctl start ngnix.service",
+        "Failed to start ngnix.service: Unit ngnix.service not found.",
+        true
+    )]
+    #[case(
+        "systemctl status ngnix",
+        "Unit ngnix.service could not be found.",
+        true
+    )]
+    #[case(
+        "systemctl --user restart sycnthing",
+        "Failed to restart sycnthing.service: Unit sycnthing.service not found.",
+        true
+    )]
+    #[case("systemctl restart nginx", "", false)]
+    #[case("service ngnix restart", "Unit ngnix.service not found.", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("systemctl restart ngnix", "Failed to restart ngnix.service: Unit ngnix.service not found.", vec!["systemctl restart nginx", "systemctl restart nginx-debug"])]
+    #[case("sudo systemctl start ngnix.service", "Failed to start ngnix.service: Unit ngnix.service not found.", vec!["sudo systemctl start nginx.service", "sudo systemctl start nginx-debug.service"])]
+    #[case("systemctl status crn", "Unit crn.service could not be found.", vec!["systemctl status cron"])]
+    #[case("systemctl --user restart sycnthing", "Failed to restart sycnthing.service: Unit sycnthing.service not found.", vec!["systemctl --user restart syncthing"])]
+    #[case("systemctl start postgres", "Failed to start postgres.service: Unit postgres.service not found.", vec![])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -0,0 +1,133 @@
+use super::{
+    match_rule_without_sudo,
+    systemctl_unit_not_found::get_missing_unit,
+    utils::{
+        join_script, match_rule_with_is_app,
+        systemd::{get_unit_files, is_same_unit, is_user_manager},
+        wrappers::unwrap_script,
+    },
+    BuiltinRule,
+};
+use crate::{
+    cli::command::{shlex_split, CrabCommand},
+    context::Context,
+    shell::Shell,
+};
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
+    if is_user_manager(&command.script_parts) {
+        return false;
+    }
+    let Some(unit) = get_missing_unit(command) else {
+        return false;
+    };
+    get_unit_files(true, context)
+        .iter()
+        .any(|user_unit| is_same_unit(&unit, user_unit))
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| {
+            match_rule_with_is_app(
+                |command| auxiliary_match_rule(command, context),
+                command,
+                vec!["systemctl"],
+                None,
+            )
+        },
+        command,
+        context,
+    )
+}
+
+/// The user manager runs as the user, so privilege escalation is dropped.
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let unwrapped = unwrap_script(&command.script, context);
+    let prefix: String = unwrapped
+        .prefixes
+        .iter()
+        .filter(|prefix| !prefix.escalates)
+        .map(|prefix| prefix.text)
+        .collect();
+    let mut parts = shlex_split(unwrapped.script);
+    parts.insert(1, "--user".to_owned());
+    vec![prefix + &join_script(system_shell, &parts)]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "systemctl_user_unit".to_owned(),
+        None,
+        Some(900),
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNIT_FILES: &str = "\
+cron.service                               enabled         enabled
+nginx.service                              enabled         enabled
+nginx-debug.service                        disabled        enabled
+ssh.socket                                 disabled        enabled
+timers.target                              static          -
+";
+    const USER_UNIT_FILES: &str = "\
+pipewire.service                           enabled         enabled
+syncthing.service                          enabled         enabled
+";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_program("systemctl")
+            .with_command_output(
+                "systemctl list-unit-files --no-legend --no-pager",
+                UNIT_FILES,
+            )
+            .with_command_output(
+                "systemctl --user list-unit-files --no-legend --no-pager",
+                USER_UNIT_FILES,
+            )
+    }
+
+    fn output(unit: &str) -> String {
+        format!("Failed to restart {unit}.service: Unit {unit}.service not found.")
+    }
+
+    #[rstest]
+    #[case("systemctl restart syncthing", &output("syncthing"), true)]
+    #[case("sudo systemctl restart pipewire.service", &output("pipewire"), true)]
+    #[case("systemctl --user restart syncthing", &output("syncthing"), false)]
+    #[case("systemctl restart ngnix", &output("ngnix"), false)]
+    #[case("systemctl restart syncthing", "", false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context()), is_match);
+    }
+
+    #[rstest]
+    #[case("systemctl restart syncthing", vec!["systemctl --user restart syncthing"])]
+    #[case("sudo systemctl restart pipewire.service", vec!["systemctl --user restart pipewire.service"])]
+    #[case("systemctl -q enable --now syncthing", vec!["systemctl --user -q enable --now syncthing"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(output("syncthing")), None);
+        assert_eq!(get_new_command(&mut command, None, &context()), expected);
+    }
+}
@@ -0,0 +1,102 @@
+use super::{
+    utils::{match_rule_with_is_app, terraform::TERRAFORM_APPS},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell};
+
+const PATTERNS: [&str; 5] = [
+    "initialization required",
+    "please run \"terraform init\"",
+    "please run \"tofu init\"",
+    "this module is not yet installed",
+    "missing or corrupted provider plugins",
+];
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        let output = output.to_lowercase();
+        // Handled by `terraform_init_upgrade`
+        !output.contains("inconsistent dependency lock file")
+            && PATTERNS.iter().any(|pattern| output.contains(pattern))
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, TERRAFORM_APPS.to_vec(), None)
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    let init = format!("{} init", command.script_parts[0]);
+    vec![system_shell.unwrap().and(vec![&init, &command.script])]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "terraform_init".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::shell::Bash;
+    use rstest::rstest;
+
+    const BACKEND: &str = "╷
+│ Error: Backend initialization required, please run \"terraform init\"
+│
+│ Reason: Initial configuration of the requested backend \"s3\"
+╵";
+    const MODULE: &str = "╷
+│ Error: Module not installed
+│
+│   on main.tf line 1:
+│    1: module \"vpc\" {
+│
+│ This module is not yet installed. Run \"tofu init\" to install all modules required by
+│ this configuration.
+╵";
+    const LOCK_FILE: &str = "╷
+│ Error: Inconsistent dependency lock file
+│
+│ The following dependency selections recorded in the lock file are inconsistent with the
+│ current configuration:
+│   - provider registry.terraform.io/hashicorp/aws: locked version selection 4.67.0 doesn't match the updated version constraints \"~> 5.0\"
+│
+│ To update the locked dependency selections to match a changed configuration, run:
+│   terraform init -upgrade
+╵";
+
+    #[rstest]
+    #[case("terraform plan", BACKEND, true)]
+    #[case("tofu apply -auto-approve", MODULE, true)]
+    #[case("terraform plan", LOCK_FILE, false)]
+    #[case(
+        "terraform plan",
+        "No changes. Your infrastructure matches the configuration.",
+        false
+    )]
+    #[case("pulumi up", BACKEND, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("terraform plan", vec!["terraform init && terraform plan"])]
+    #[case("tofu apply -auto-approve", vec!["tofu init && tofu apply -auto-approve"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let system_shell = Bash {};
+        let mut command = CrabCommand::new(command.to_owned(), Some(BACKEND.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, Some(&system_shell)), expected);
+    }
+}
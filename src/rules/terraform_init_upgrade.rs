@@ -0,0 +1,71 @@
+use super::{
+    utils::{match_rule_with_is_app, terraform::TERRAFORM_APPS},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell};
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("Inconsistent dependency lock file")
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, TERRAFORM_APPS.to_vec(), None)
+}
+
+/// Upgrading may pick new provider versions, so it is not chained with the original
+/// command, which should be checked again first.
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    vec![format!("{} init -upgrade", command.script_parts[0])]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "terraform_init_upgrade".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const LOCK_FILE: &str = "╷
+│ Error: Inconsistent dependency lock file
+│
+│ The following dependency selections recorded in the lock file are inconsistent with the
+│ current configuration:
+│   - provider registry.opentofu.org/hashicorp/aws: required by this configuration but no version is selected
+│
+│ To make the initial dependency selections that will initialize the dependency lock file, run:
+│   tofu init
+╵";
+
+    #[rstest]
+    #[case("terraform plan", LOCK_FILE, true)]
+    #[case("tofu validate", LOCK_FILE, true)]
+    #[case("terraform plan", "", false)]
+    #[case("terragrunt plan", LOCK_FILE, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("terraform plan -out=plan.tfplan", vec!["terraform init -upgrade"])]
+    #[case("tofu validate", vec!["tofu init -upgrade"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(LOCK_FILE.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
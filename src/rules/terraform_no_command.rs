@@ -0,0 +1,115 @@
+use super::{
+    utils::{
+        match_rule_with_is_app,
+        terraform::{TERRAFORM_APPS, TERRAFORM_COMMANDS},
+    },
+    BuiltinRule,
+};
+use crate::{
+    cli::command::CrabCommand,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+use regex::Regex;
+
+/// Returns the unknown command and the one suggested, from
+/// `Terraform has no command named "plna". Did you mean "plan"?`.
+fn get_unknown_command(output: &str) -> Option<(String, Option<String>)> {
+    let re =
+        Regex::new(r#"has no command named "([^"]+)"\.(?: Did you mean "([^"]+)"\?)?"#).unwrap();
+    re.captures(output).map(|caps| {
+        (
+            caps[1].to_owned(),
+            caps.get(2).map(|suggestion| suggestion.as_str().to_owned()),
+        )
+    })
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(
+        auxiliary_match_rule,
+        command,
+        TERRAFORM_APPS.to_vec(),
+        Some(1),
+    )
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    let Some((unknown, suggestion)) = command.output.as_deref().and_then(get_unknown_command)
+    else {
+        return vec![];
+    };
+    let suggestions = match suggestion {
+        Some(suggestion) => vec![suggestion],
+        None => get_close_matches(&unknown, &TERRAFORM_COMMANDS, None, None)
+            .into_iter()
+            .map(|command| command.to_owned())
+            .collect(),
+    };
+    suggestions
+        .iter()
+        .map(|new| replace_argument(&command.script, &unknown, new))
+        .collect()
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "terraform_no_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const PLNA: &str = "Terraform has no command named \"plna\". Did you mean \"plan\"?
+
+To see all of Terraform's top-level commands, run:
+  terraform -help
+";
+    const APPLT: &str = "OpenTofu has no command named \"applt\". Did you mean \"apply\"?";
+    const VALIDTE: &str = "Terraform has no command named \"validte\".
+
+To see all of Terraform's top-level commands, run:
+  terraform -help
+";
+
+    #[rstest]
+    #[case("terraform plna", PLNA, true)]
+    #[case("tofu applt -auto-approve", APPLT, true)]
+    #[case("terraform plan", "", false)]
+    #[case("packer plna", PLNA, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("terraform plna", PLNA, vec!["terraform plan"])]
+    #[case("tofu applt -auto-approve", APPLT, vec!["tofu apply -auto-approve"])]
+    #[case("terraform -chdir=infra validte", VALIDTE, vec!["terraform -chdir=infra validate"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -0,0 +1,152 @@
+use super::{
+    utils::{
+        join_script, match_rule_with_is_app,
+        terraform::{get_command_position, TERRAFORM_APPS},
+    },
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+use std::path::PathBuf;
+
+/// Returns the only variable file in the working directory of the command, which can be
+/// changed with `-chdir`.
+///
+/// `terraform.tfvars` and `*.auto.tfvars` are loaded automatically, so they are skipped.
+fn get_var_file(command: &CrabCommand, context: &dyn Context) -> Option<String> {
+    let mut dir = context.current_dir();
+    if let Some(chdir) = command
+        .script_parts
+        .iter()
+        .find_map(|part| part.strip_prefix("-chdir="))
+    {
+        dir = dir.join(PathBuf::from(chdir));
+    }
+    let mut var_files = context.read_dir(&dir).into_iter().filter_map(|path| {
+        let name = path.file_name()?.to_str()?.to_owned();
+        (name.ends_with(".tfvars")
+            && name != "terraform.tfvars"
+            && !name.ends_with(".auto.tfvars")
+            && context.is_file(&path))
+        .then_some(name)
+    });
+    match (var_files.next(), var_files.next()) {
+        (Some(var_file), None) => Some(var_file),
+        _ => None,
+    }
+}
+
+fn auxiliary_match_rule(command: &CrabCommand, context: &dyn Context) -> bool {
+    if let Some(output) = &command.output {
+        output.contains("No value for required variable")
+            && !command
+                .script_parts
+                .iter()
+                .any(|part| part.starts_with("-var-file") || part.starts_with("--var-file"))
+            && get_var_file(command, context).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(
+        |command| auxiliary_match_rule(command, context),
+        command,
+        TERRAFORM_APPS.to_vec(),
+        Some(1),
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let (Some(var_file), Some(position)) = (
+        get_var_file(command, context),
+        get_command_position(&command.script_parts),
+    ) else {
+        return vec![];
+    };
+    let mut parts = command.script_parts.clone();
+    parts.insert(position + 1, format!("-var-file={var_file}"));
+    vec![join_script(system_shell, &parts)]
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "terraform_var_file".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NO_VALUE: &str = "╷
+│ Error: No value for required variable
+│
+│   on variables.tf line 1:
+│    1: variable \"region\" {
+│
+│ The root module input variable \"region\" is not set, and has no default value. Use a
+│ -var or -var-file command line argument to provide a value for this variable.
+╵";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_file("main.tf", b"")
+            .with_file("terraform.tfvars", b"")
+            .with_file("defaults.auto.tfvars", b"")
+            .with_file("prod.tfvars", b"region = \"eu-west-1\"")
+            .with_file("infra/staging.tfvars", b"")
+            .with_file("infra/prod.tfvars", b"")
+    }
+
+    #[rstest]
+    #[case("terraform plan", NO_VALUE, context(), true)]
+    #[case("tofu apply -auto-approve", NO_VALUE, context(), true)]
+    #[case("terraform plan -var-file=dev.tfvars", NO_VALUE, context(), false)]
+    #[case("terraform -chdir=infra plan", NO_VALUE, context(), false)]
+    #[case("terraform plan", NO_VALUE, FakeContext::new().with_file("terraform.tfvars", b""), false)]
+    #[case("terraform plan", "No changes.", context(), false)]
+    fn test_match(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] context: FakeContext,
+        #[case] is_match: bool,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None, &context), is_match);
+    }
+
+    #[rstest]
+    #[case("terraform plan", context(), vec!["terraform plan -var-file=prod.tfvars"])]
+    #[case("tofu apply -auto-approve", context(), vec!["tofu apply -var-file=prod.tfvars -auto-approve"])]
+    #[case(
+        "terraform -chdir=infra plan",
+        FakeContext::new().with_file("infra/staging.tfvars", b""),
+        vec!["terraform -chdir=infra plan -var-file=staging.tfvars"]
+    )]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] context: FakeContext,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(NO_VALUE.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
@@ -1,4 +1,4 @@
-use super::Rule;
+use super::BuiltinRule;
 use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_command};
 use regex::Regex;
 
@@ -31,8 +31,8 @@ pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shel
         Vec::<String>::new()
     }
 }
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
         "tmux".to_owned(),
         None,
         None,
@@ -1,9 +1,13 @@
-use super::Rule;
-use crate::{cli::command::CrabCommand, shell::Shell};
+use super::{utils::wrappers::unwrap_script, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
 
-pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
     if let Some(output) = &command.output {
-        command.script_parts.first().is_some_and(|s| s == "sudo")
+        unwrap_script(&command.script, context).is_escalated()
             && output
                 .to_lowercase()
                 .contains("you cannot perform this operation as root")
@@ -12,12 +16,16 @@ pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -
     }
 }
 
-pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
-    vec![command.script_parts[1..].join(" ")]
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    vec![unwrap_script(&command.script, context).without_escalation()]
 }
 
-pub fn get_rule() -> Rule {
-    Rule::new(
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
         "unsudo".to_owned(),
         None,
         None,
@@ -32,6 +40,7 @@ pub fn get_rule() -> Rule {
 mod tests {
     use super::{get_new_command, match_rule};
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Bash;
     use rstest::rstest;
 
@@ -40,14 +49,20 @@ mod tests {
     #[case("", "", false)]
     #[case("sudo ls", "Permission denied", false)]
     #[case("ls", "you cannot perform this operation as root", false)]
+    #[case("doas -u root ls", "you cannot perform this operation as root", true)]
     fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(match_rule(&mut command, None), is_match);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
     }
 
     #[rstest]
     #[case("sudo ls", "", vec!["ls"])]
     #[case("sudo pacaur -S helloworld", "", vec!["pacaur -S helloworld"])]
+    #[case("sudo -u root -H yay -S helloworld", "", vec!["yay -S helloworld"])]
+    #[case("nohup doas makepkg -si", "", vec!["nohup makepkg -si"])]
     fn test_get_new_command(
         #[case] command: &str,
         #[case] stdout: &str,
@@ -55,6 +70,9 @@ mod tests {
     ) {
         let system_shell = Bash {};
         let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
-        assert_eq!(get_new_command(&mut command, None), expected);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
     }
 }
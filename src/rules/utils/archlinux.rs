@@ -0,0 +1,97 @@
+use crate::context::Context;
+
+use super::wrappers::get_privilege_command;
+
+/// Programs that manage packages on Arch Linux, with the same options as `pacman`.
+pub const PACMAN_APPS: [&str; 3] = ["pacman", "yay", "paru"];
+
+/// AUR helpers, in the order they are preferred. They ask for privileges themselves.
+const AUR_HELPERS: [&str; 2] = ["yay", "paru"];
+
+/// Returns the AUR helper installed, if any.
+pub fn get_aur_helper(context: &dyn Context) -> Option<&'static str> {
+    AUR_HELPERS
+        .into_iter()
+        .find(|helper| context.which(helper).is_some())
+}
+
+/// Returns the command used to install packages: an AUR helper if one is installed,
+/// otherwise `pacman` with elevated privileges, or `None` if this is not Arch Linux.
+pub fn get_pacman(context: &dyn Context) -> Option<String> {
+    if let Some(helper) = get_aur_helper(context) {
+        Some(helper.to_owned())
+    } else if context.which("pacman").is_some() {
+        Some(format!("{} pacman", get_privilege_command(context)))
+    } else {
+        None
+    }
+}
+
+/// Returns the packages (e.g. `extra/vim`) that provide the executable `command`.
+///
+/// `pkgfile` is used when it is installed, as it is faster, falling back to `pacman -F`.
+/// Both need their file database to be synced beforehand.
+pub fn get_pkgfile(command: &str, context: &dyn Context) -> Vec<String> {
+    let output = context
+        .run("pkgfile", &["-b", "-v", command])
+        .filter(|output| output.success)
+        .or_else(|| {
+            context
+                .run("pacman", &["-F", "-q", &format!("/usr/bin/{command}")])
+                .filter(|output| output.success)
+        });
+    match output {
+        Some(output) => output
+            .stdout
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(|package| package.to_owned())
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Returns the names of all the packages in the sync databases.
+pub fn get_packages(context: &dyn Context) -> Vec<String> {
+    context
+        .run("pacman", &["-S", "-l", "-q"])
+        .map(|output| output.stdout.lines().map(|line| line.to_owned()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_pacman, get_pkgfile};
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(FakeContext::new(), None)]
+    #[case(FakeContext::new().with_program("pacman"), Some("sudo pacman"))]
+    #[case(FakeContext::new().with_program("pacman").with_program("doas"), Some("doas pacman"))]
+    #[case(FakeContext::new().with_program("pacman").with_program("paru"), Some("paru"))]
+    #[case(
+        FakeContext::new().with_program("pacman").with_program("paru").with_program("yay"),
+        Some("yay")
+    )]
+    fn test_get_pacman(#[case] context: FakeContext, #[case] expected: Option<&str>) {
+        assert_eq!(get_pacman(&context).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        FakeContext::new().with_command_output(
+            "pkgfile -b -v vim",
+            "extra/gvim 9.1.0-1\t/usr/bin/vim\nextra/vim 9.1.0-1\t/usr/bin/vim\n"
+        ),
+        vec!["extra/gvim", "extra/vim"]
+    )]
+    #[case(
+        FakeContext::new().with_command_output("pacman -F -q /usr/bin/vim", "extra/vim\n"),
+        vec!["extra/vim"]
+    )]
+    #[case(FakeContext::new(), vec![])]
+    fn test_get_pkgfile(#[case] context: FakeContext, #[case] expected: Vec<&str>) {
+        assert_eq!(get_pkgfile("vim", &context), expected);
+    }
+}
@@ -0,0 +1,83 @@
+use regex::Regex;
+
+use crate::utils::{get_all_matched_commands, get_close_matches};
+
+/// Returns the unknown command reported by a cobra based tool like kubectl, helm, gh or
+/// glab, e.g. `gte` in `error: unknown command "gte" for "kubectl"`.
+pub fn get_unknown_command(output: &str) -> Option<String> {
+    let re = Regex::new(r#"unknown command "([^"]+)" for ""#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+/// Returns the commands suggested by cobra after `Did you mean this?`, or the closest ones
+/// of `commands` if there are none.
+///
+/// Some tools, like gh, print their usage after the suggestions, so only the lines up to
+/// the next empty one are read.
+pub fn get_command_suggestions(output: &str, unknown: &str, commands: &[&str]) -> Vec<String> {
+    let suggestions = match output.find("Did you mean this?") {
+        Some(start) => {
+            let end = output[start..]
+                .find("\n\n")
+                .map_or(output.len(), |end| start + end);
+            get_all_matched_commands(&output[start..end], Some(vec!["Did you mean this?"]))
+        }
+        None => vec![],
+    };
+    if !suggestions.is_empty() {
+        return suggestions;
+    }
+    get_close_matches(unknown, commands, None, None)
+        .into_iter()
+        .map(|command| command.to_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_command_suggestions, get_unknown_command};
+    use rstest::rstest;
+
+    const KUBECTL: &str = "error: unknown command \"gte\" for \"kubectl\"
+
+Did you mean this?
+\tget
+\tset
+
+Run 'kubectl --help' for usage.";
+    const GH: &str = "unknown command \"rpo\" for \"gh\"
+
+Did you mean this?
+\trepo
+
+Usage:  gh <command> <subcommand> [flags]
+
+Available commands:
+  alias
+  api
+  auth";
+
+    #[rstest]
+    #[case(KUBECTL, Some("gte"))]
+    #[case(GH, Some("rpo"))]
+    #[case("Error: unknown flag: --wbe", None)]
+    fn test_get_unknown_command(#[case] output: &str, #[case] expected: Option<&str>) {
+        assert_eq!(get_unknown_command(output).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(KUBECTL, "gte", vec!["get", "set"])]
+    #[case(GH, "rpo", vec!["repo"])]
+    #[case("", "descrbie", vec!["describe"])]
+    #[case("", "frobnicate", vec![])]
+    fn test_get_command_suggestions(
+        #[case] output: &str,
+        #[case] unknown: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        assert_eq!(
+            get_command_suggestions(output, unknown, &["delete", "describe", "get", "set"]),
+            expected
+        );
+    }
+}
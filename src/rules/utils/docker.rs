@@ -0,0 +1,160 @@
+use regex::Regex;
+
+use crate::context::Context;
+
+/// Docker and Podman, which share their commands and most of their messages.
+pub const DOCKER_APPS: [&str; 2] = ["docker", "podman"];
+
+/// Commands of Docker, used when they cannot be read from `docker --help`.
+const DOCKER_COMMANDS: [&str; 45] = [
+    "attach",
+    "build",
+    "builder",
+    "buildx",
+    "commit",
+    "compose",
+    "config",
+    "container",
+    "context",
+    "cp",
+    "create",
+    "diff",
+    "events",
+    "exec",
+    "export",
+    "history",
+    "image",
+    "images",
+    "import",
+    "info",
+    "inspect",
+    "kill",
+    "load",
+    "login",
+    "logout",
+    "logs",
+    "network",
+    "pause",
+    "port",
+    "ps",
+    "pull",
+    "push",
+    "rename",
+    "restart",
+    "rm",
+    "rmi",
+    "run",
+    "save",
+    "search",
+    "start",
+    "stats",
+    "stop",
+    "system",
+    "tag",
+    "volume",
+];
+
+/// A command that Docker or Podman did not recognize, e.g. `lss` in `docker container lss`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCommand {
+    /// Management commands in front of it, e.g. `container`.
+    pub parents: Vec<String>,
+    pub name: String,
+}
+
+/// Returns the command that was not recognized, from any of:
+///
+/// - `docker: 'psa' is not a docker command.` (Docker before 24)
+/// - `docker: unknown command: docker container lss` (Docker 24 and later)
+/// - ``Error: unrecognized command `podman container lss` `` (Podman)
+pub fn get_unknown_command(output: &str) -> Option<UnknownCommand> {
+    let re = Regex::new(r"'([^']+)' is not a docker command").unwrap();
+    if let Some(caps) = re.captures(output) {
+        return Some(UnknownCommand {
+            parents: vec![],
+            name: caps[1].to_owned(),
+        });
+    }
+    let re = Regex::new(r"(?:unknown command: |unrecognized command `)([^`\n]+)").unwrap();
+    let caps = re.captures(output)?;
+    let mut words: Vec<String> = caps[1].split_whitespace().map(|s| s.to_owned()).collect();
+    let name = words.pop()?;
+    // The first word is the program itself
+    Some(UnknownCommand {
+        parents: words.into_iter().skip(1).collect(),
+        name,
+    })
+}
+
+/// Returns the commands listed by `<program> [parents] --help`, e.g. `ls` and `prune` for
+/// `docker container`.
+pub fn get_commands(program: &str, parents: &[String], context: &dyn Context) -> Vec<String> {
+    let mut args: Vec<&str> = parents.iter().map(|s| s.as_str()).collect();
+    args.push("--help");
+    let help = context
+        .run(program, &args)
+        .map(|output| output.stdout + &output.stderr)
+        .unwrap_or_default();
+    // Plugins such as `buildx*` are marked with a star
+    let re = Regex::new(r"(?m)^  ([a-z][a-z0-9-]*)\*? {2,}\S").unwrap();
+    let mut commands: Vec<String> = re
+        .captures_iter(&help)
+        .map(|caps| caps[1].to_owned())
+        .collect();
+    if commands.is_empty() && parents.is_empty() {
+        commands = DOCKER_COMMANDS.iter().map(|s| s.to_string()).collect();
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_commands, get_unknown_command, UnknownCommand};
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("docker: 'psa' is not a docker command.\nSee 'docker --help'", vec![], "psa")]
+    #[case("docker: unknown command: docker psa\n\nRun 'docker --help' for more information", vec![], "psa")]
+    #[case("docker: unknown command: docker container lss\n", vec!["container"], "lss")]
+    #[case("Error: unrecognized command `podman container lss`\nTry 'podman container --help' for more information", vec!["container"], "lss")]
+    fn test_get_unknown_command(
+        #[case] output: &str,
+        #[case] parents: Vec<&str>,
+        #[case] name: &str,
+    ) {
+        assert_eq!(
+            get_unknown_command(output),
+            Some(UnknownCommand {
+                parents: parents.iter().map(|s| s.to_string()).collect(),
+                name: name.to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_commands() {
+        let help = "
+Usage:  docker [OPTIONS] COMMAND
+
+Common Commands:
+  run         Create and run a new container from an image
+  ps          List containers
+
+Management Commands:
+  buildx*     Docker Buildx
+  container   Manage containers
+
+Global Options:
+      --config string      Location of client config files
+  -D, --debug              Enable debug mode
+";
+        let context = FakeContext::new().with_command_output("docker --help", help);
+        assert_eq!(
+            get_commands("docker", &[], &context),
+            vec!["run", "ps", "buildx", "container"]
+        );
+        assert!(get_commands("podman", &[], &context).contains(&"images".to_owned()));
+        assert!(get_commands("podman", &["container".to_owned()], &context).is_empty());
+    }
+}
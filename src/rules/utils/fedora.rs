@@ -0,0 +1,214 @@
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::context::Context;
+
+use super::wrappers::get_privilege_command;
+
+/// Package managers of Fedora and RHEL, in the order they are preferred.
+pub const DNF_APPS: [&str; 3] = ["dnf", "dnf5", "yum"];
+
+/// Operations of dnf 4, used when they cannot be read from `dnf --help`.
+const DNF_OPERATIONS: [&str; 28] = [
+    "alias",
+    "autoremove",
+    "check",
+    "check-update",
+    "clean",
+    "deplist",
+    "distro-sync",
+    "downgrade",
+    "group",
+    "help",
+    "history",
+    "info",
+    "install",
+    "list",
+    "makecache",
+    "mark",
+    "module",
+    "provides",
+    "reinstall",
+    "remove",
+    "repoinfo",
+    "repolist",
+    "repoquery",
+    "repository-packages",
+    "search",
+    "swap",
+    "updateinfo",
+    "upgrade",
+];
+
+/// Returns the dnf-like package manager installed, if any.
+pub fn get_dnf(context: &dyn Context) -> Option<&'static str> {
+    DNF_APPS
+        .into_iter()
+        .find(|program| context.which(program).is_some())
+}
+
+/// Returns the command used to install packages, or `None` if this is not Fedora or RHEL.
+///
+/// On image based systems, like Silverblue, packages are layered with `rpm-ostree`, which
+/// asks for privileges itself. They are applied live, otherwise they would only be
+/// available after a reboot.
+pub fn get_installer(context: &dyn Context) -> Option<String> {
+    if context.which("rpm-ostree").is_some() && context.exists(Path::new("/run/ostree-booted")) {
+        Some("rpm-ostree install --apply-live".to_owned())
+    } else {
+        get_dnf(context).map(|dnf| format!("{} {dnf} install", get_privilege_command(context)))
+    }
+}
+
+/// Returns the operations listed by `<program> --help`, e.g. `install` or `upgrade`.
+pub fn get_operations(program: &str, context: &dyn Context) -> Vec<String> {
+    let help = context
+        .run(program, &["--help"])
+        .map(|output| output.stdout + &output.stderr)
+        .unwrap_or_default();
+    let re = Regex::new(r"(?m)^ {0,2}([a-z][a-z-]+) {2,}\S").unwrap();
+    let mut operations: Vec<String> = re
+        .captures_iter(&help)
+        .map(|caps| caps[1].to_owned())
+        .collect();
+    if operations.is_empty() {
+        operations = DNF_OPERATIONS.iter().map(|s| s.to_string()).collect();
+    }
+    operations
+}
+
+/// Returns the name of the package of a NEVRA, e.g. `vim-enhanced` for
+/// `vim-enhanced-2:9.1.0-1.fc40.x86_64`.
+fn get_package_name(nevra: &str) -> Option<&str> {
+    let (nevr, _arch) = nevra.rsplit_once('.')?;
+    let (nev, _release) = nevr.rsplit_once('-')?;
+    let (name, _version) = nev.rsplit_once('-')?;
+    Some(name)
+}
+
+/// Returns the packages that provide the executable `command`, according to
+/// `dnf provides`.
+pub fn get_providing_packages(command: &str, context: &dyn Context) -> Vec<String> {
+    let Some(dnf) = get_dnf(context) else {
+        return vec![];
+    };
+    let stdout = context
+        .run(dnf, &["-q", "provides", &format!("/usr/bin/{command}")])
+        .filter(|output| output.success)
+        .map(|output| output.stdout)
+        .unwrap_or_default();
+    let mut packages: Vec<String> = vec![];
+    // Each match starts with `<nevra> : <summary>`, followed by its details
+    for line in stdout.lines() {
+        let name = line
+            .split_once(" : ")
+            .and_then(|(nevra, _summary)| get_package_name(nevra));
+        if let Some(name) = name {
+            if !packages.iter().any(|package| package == name) {
+                packages.push(name.to_owned());
+            }
+        }
+    }
+    packages
+}
+
+/// Returns the names of the packages available in the enabled repositories.
+pub fn get_packages(context: &dyn Context) -> Vec<String> {
+    let Some(dnf) = get_dnf(context) else {
+        return vec![];
+    };
+    let mut packages: Vec<String> = context
+        .run(dnf, &["-q", "repoquery", "--qf", "%{name}\n"])
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .map(|line| line.trim().to_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.retain(|package| !package.is_empty());
+    packages.sort();
+    packages.dedup();
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_installer, get_operations, get_package_name, get_providing_packages};
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(FakeContext::new(), None)]
+    #[case(FakeContext::new().with_program("dnf"), Some("sudo dnf install"))]
+    #[case(FakeContext::new().with_program("yum"), Some("sudo yum install"))]
+    #[case(
+        FakeContext::new().with_program("dnf").with_program("rpm-ostree"),
+        Some("sudo dnf install")
+    )]
+    #[case(
+        FakeContext::new()
+            .with_program("rpm-ostree")
+            .with_file("/run/ostree-booted", b""),
+        Some("rpm-ostree install --apply-live")
+    )]
+    fn test_get_installer(#[case] context: FakeContext, #[case] expected: Option<&str>) {
+        assert_eq!(get_installer(&context).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("vim-enhanced-2:9.1.0-1.fc40.x86_64", Some("vim-enhanced"))]
+    #[case("python3-libs-3.12.3-2.fc40.i686", Some("python3-libs"))]
+    #[case("vim", None)]
+    fn test_get_package_name(#[case] nevra: &str, #[case] expected: Option<&str>) {
+        assert_eq!(get_package_name(nevra), expected);
+    }
+
+    #[test]
+    fn test_get_providing_packages() {
+        let provides = "\
+vim-enhanced-2:9.1.0-1.fc40.x86_64 : A version of the VIM editor which includes recent enhancements
+Repo        : fedora
+Matched from:
+Filename    : /usr/bin/vim
+
+vim-enhanced-2:9.1.1-1.fc40.x86_64 : A version of the VIM editor which includes recent enhancements
+Repo        : updates
+Matched from:
+Filename    : /usr/bin/vim
+";
+        let context = FakeContext::new()
+            .with_program("dnf")
+            .with_command_output("dnf -q provides /usr/bin/vim", provides);
+        assert_eq!(
+            get_providing_packages("vim", &context),
+            vec!["vim-enhanced"]
+        );
+        assert!(get_providing_packages("nano", &context).is_empty());
+    }
+
+    #[test]
+    fn test_get_operations() {
+        let help = "\
+usage: dnf [options] COMMAND
+
+List of Main Commands:
+
+autoremove                remove all unneeded packages
+check-update              check for available package upgrades
+install                   install a package or packages on your system
+
+Optional arguments:
+  -c [config file], --config [config file]
+                        config file location
+";
+        let context = FakeContext::new().with_command_output("dnf --help", help);
+        assert_eq!(
+            get_operations("dnf", &context),
+            vec!["autoremove", "check-update", "install"]
+        );
+        assert!(get_operations("yum", &context).contains(&"upgrade".to_owned()));
+    }
+}
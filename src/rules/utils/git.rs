@@ -1,9 +1,23 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
-use regex::Regex;
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell};
+use regex::{NoExpand, Regex};
 use shlex::split as shlex_split;
-use shlex::Quoter;
 
-use super::aux_is_app;
+use super::{aux_is_app, join_script};
+
+/// Returns the names of the git commands and aliases listed by `git help -a`.
+pub fn get_git_commands(context: &dyn Context) -> Vec<String> {
+    match context.run("git", &["help", "-a"]) {
+        Some(output) => output
+            .stdout
+            .lines()
+            // Commands are indented, section titles are not
+            .filter(|line| line.starts_with(char::is_whitespace))
+            .filter_map(|line| line.split_whitespace().next())
+            .map(|command| command.to_owned())
+            .collect(),
+        None => vec![],
+    }
+}
 
 /// Provides git support for a given function.
 ///
@@ -27,28 +41,8 @@ where
     }
 
     // perform git aliases expansion
-    if let Some(stdout) = &command.output {
-        if stdout.contains("trace: alias expansion:") {
-            let re = Regex::new(r"trace: alias expansion: ([^ ]*) => ([^\n]*)").unwrap();
-            if let Some(search) = re.captures(stdout) {
-                let alias = search.get(1).map_or("", |m| m.as_str());
-
-                // by default git quotes everything, for example:
-                //     'commit' '--amend'
-                // which is surprising and does not allow to easily test for
-                // eg. 'git commit'
-                let expansion = search
-                    .get(2)
-                    .map_or("", |m| m.as_str())
-                    .split_whitespace()
-                    .map(|part| format!("\"{part}\"")) // shell.quote(part)
-                    .collect::<Vec<_>>()
-                    .join(" ");
-                let new_script = command.script.replace(&format!(r"\b{alias}\b"), &expansion);
-
-                command.script = new_script;
-            }
-        }
+    if let Some(new_script) = expand_alias(command, None) {
+        command.script = new_script;
     }
 
     func(command)
@@ -71,40 +65,42 @@ where
     let mut new_command = command;
 
     // perform git aliases expansion
-    if let Some(stdout) = &new_command.output {
-        if stdout.contains("trace: alias expansion:") {
-            let re = Regex::new(r"trace: alias expansion: ([^ ]*) => ([^\n]*)").unwrap();
-            if let Some(search) = re.captures(stdout) {
-                let shlex_quoter = Quoter::new();
-                let alias = search.get(1).map_or("", |m| m.as_str());
-
-                // by default git quotes everything, for example:
-                //     'commit' '--amend'
-                // which is surprising and does not allow to easily test for
-                // eg. 'git commit'
-                let expansion = search.get(2).map_or("", |m| m.as_str());
-                let expansion = shlex_split(expansion)
-                    .unwrap()
-                    .iter()
-                    .map(|s| shlex_quoter.quote(s).unwrap())
-                    .collect::<Vec<_>>()
-                    .join(" ");
-
-                let re = Regex::new(&format!(r"\b{alias}\b")).unwrap();
-                let new_script = re.replace(&new_command.script, &expansion);
-
-                *new_command = new_command.update(Some(new_script.to_string()), None, None);
-            }
-        }
+    if let Some(new_script) = expand_alias(new_command, system_shell) {
+        *new_command = new_command.update(Some(new_script), None, None);
     }
 
     func(new_command, system_shell)
 }
 
+/// Replaces the git alias in the script with its expansion, if git traced one in the
+/// output.
+fn expand_alias(command: &CrabCommand, system_shell: Option<&dyn Shell>) -> Option<String> {
+    let stdout = command.output.as_ref()?;
+    let re = Regex::new(r"trace: alias expansion: ([^ ]*) => ([^\n]*)").unwrap();
+    let search = re.captures(stdout)?;
+    let alias = search.get(1).map_or("", |m| m.as_str());
+
+    // by default git quotes everything, for example:
+    //     'commit' '--amend'
+    // which is surprising and does not allow to easily test for
+    // eg. 'git commit'
+    let expansion = shlex_split(search.get(2).map_or("", |m| m.as_str()))?;
+    let expansion = join_script(system_shell, &expansion);
+
+    let re = Regex::new(&format!(r"\b{}\b", regex::escape(alias))).unwrap();
+    Some(
+        re.replace(&command.script, NoExpand(&expansion))
+            .to_string(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{aux_is_app, get_new_command_with_git_support, match_rule_with_git_support};
+    use super::{
+        aux_is_app, get_git_commands, get_new_command_with_git_support, match_rule_with_git_support,
+    };
     use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
     use crate::shell::Shell;
     use rstest::rstest;
 
@@ -150,6 +146,11 @@ mod tests {
         "19:22:36.299340 git.c:282   trace: alias expansion: br => 'branch'",
         "git branch -d some_branch"
     )]
+    #[case(
+        "git wip",
+        "19:22:36.299340 git.c:282   trace: alias expansion: wip => 'commit' '-m' 'work in progress'",
+        "git commit -m 'work in progress'"
+    )]
     fn test_get_command_with_git_support(
         #[case] script: &str,
         #[case] output: &str,
@@ -191,4 +192,30 @@ mod tests {
             is_git_command
         );
     }
+
+    const GIT_HELP_ALL: &str = r#"See 'git help <command>' to read about a specific subcommand
+
+Main Porcelain Commands
+   add                     Add file contents to the index
+   branch                  List, create, or delete branches
+
+Ancillary Commands / Manipulators
+   config                  Get and set repository or global options
+
+External commands
+   lfs
+
+Command aliases
+   co                      Alias for 'checkout'
+"#;
+
+    #[test]
+    fn test_get_git_commands() {
+        let context = FakeContext::new().with_command_output("git help -a", GIT_HELP_ALL);
+        assert_eq!(
+            get_git_commands(&context),
+            vec!["add", "branch", "config", "lfs", "co"]
+        );
+        assert!(get_git_commands(&FakeContext::new()).is_empty());
+    }
 }
@@ -0,0 +1,259 @@
+use regex::Regex;
+
+use crate::{cli::command::CrabCommand, context::Context};
+
+/// Top-level commands of `kubectl`, used when it does not suggest any itself.
+pub const KUBECTL_COMMANDS: [&str; 43] = [
+    "annotate",
+    "api-resources",
+    "api-versions",
+    "apply",
+    "attach",
+    "auth",
+    "autoscale",
+    "certificate",
+    "cluster-info",
+    "completion",
+    "config",
+    "cordon",
+    "cp",
+    "create",
+    "debug",
+    "delete",
+    "describe",
+    "diff",
+    "drain",
+    "edit",
+    "events",
+    "exec",
+    "explain",
+    "expose",
+    "get",
+    "help",
+    "kustomize",
+    "label",
+    "logs",
+    "patch",
+    "plugin",
+    "port-forward",
+    "proxy",
+    "replace",
+    "rollout",
+    "run",
+    "scale",
+    "set",
+    "taint",
+    "top",
+    "uncordon",
+    "version",
+    "wait",
+];
+
+/// Top-level commands of `helm`, used when it does not suggest any itself.
+pub const HELM_COMMANDS: [&str; 26] = [
+    "completion",
+    "create",
+    "dependency",
+    "env",
+    "get",
+    "help",
+    "history",
+    "install",
+    "lint",
+    "list",
+    "package",
+    "plugin",
+    "pull",
+    "push",
+    "registry",
+    "repo",
+    "rollback",
+    "search",
+    "show",
+    "status",
+    "template",
+    "test",
+    "uninstall",
+    "upgrade",
+    "verify",
+    "version",
+];
+
+/// Returns the value of a flag given as `-n value`, `--namespace value` or
+/// `--namespace=value`.
+pub fn get_flag_value(script_parts: &[String], short: Option<&str>, long: &str) -> Option<String> {
+    let mut parts = script_parts.iter();
+    while let Some(part) = parts.next() {
+        if part == long || Some(part.as_str()) == short {
+            return parts.next().cloned();
+        }
+        if let Some(value) = part
+            .strip_prefix(long)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_owned());
+        }
+    }
+    None
+}
+
+/// Returns the arguments selecting the kubeconfig and context of the command, to run
+/// listing commands against the same cluster.
+pub fn get_cluster_args(script_parts: &[String]) -> Vec<String> {
+    let mut args = vec![];
+    for flag in ["--kubeconfig", "--context"] {
+        if let Some(value) = get_flag_value(script_parts, None, flag) {
+            args.push(format!("{flag}={value}"));
+        }
+    }
+    args
+}
+
+/// Returns the namespace of the command, if one is given.
+pub fn get_namespace(script_parts: &[String]) -> Option<String> {
+    get_flag_value(script_parts, Some("-n"), "--namespace")
+}
+
+/// Returns the kind and name of a resource that was not found, e.g. `pods` and `web-1`
+/// for `Error from server (NotFound): pods "web-1" not found`.
+pub fn get_missing_resource(output: &str) -> Option<(String, String)> {
+    let re = Regex::new(r#"\(NotFound\): (\S+) "([^"]+)" not found"#).unwrap();
+    re.captures(output)
+        .map(|caps| (caps[1].to_owned(), caps[2].to_owned()))
+}
+
+/// Returns the names of the resources of a kind, in the namespace of the command.
+pub fn get_resource_names(command: &CrabCommand, kind: &str, context: &dyn Context) -> Vec<String> {
+    let mut args = get_cluster_args(&command.script_parts);
+    args.extend([
+        "get".to_owned(),
+        kind.to_owned(),
+        "-o".to_owned(),
+        "name".to_owned(),
+    ]);
+    if kind != "namespaces" {
+        if let Some(namespace) = get_namespace(&command.script_parts) {
+            args.extend(["-n".to_owned(), namespace]);
+        }
+    }
+    run_kubectl(&args, context)
+        .lines()
+        .filter_map(|line| line.split_once('/').map(|(_, name)| name.to_owned()))
+        .collect()
+}
+
+/// Runs `kubectl` and returns its stdout, or an empty string if it failed.
+pub fn run_kubectl(args: &[String], context: &dyn Context) -> String {
+    let args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    context
+        .run("kubectl", &args)
+        .filter(|output| output.success)
+        .map(|output| output.stdout)
+        .unwrap_or_default()
+}
+
+/// Replaces `old` with `new` in the arguments, whether it is given alone, as the value of
+/// a flag (`--namespace=old`) or after a kind (`pod/old`).
+pub fn replace_value(script_parts: &[String], old: &str, new: &str) -> Vec<String> {
+    script_parts
+        .iter()
+        .map(|part| {
+            if part == old {
+                return new.to_owned();
+            }
+            for separator in ['=', '/'] {
+                if let Some((prefix, value)) = part.split_once(separator) {
+                    if value == old {
+                        return format!("{prefix}{separator}{new}");
+                    }
+                }
+            }
+            part.to_owned()
+        })
+        .collect()
+}
+
+/// Sets the value of a flag given with any of `flags`, adding it after the program with
+/// the first of them if it is not given.
+pub fn set_flag(script_parts: &[String], flags: &[&str], value: &str) -> Vec<String> {
+    let mut parts = script_parts.to_vec();
+    let position = parts.iter().position(|part| {
+        flags
+            .iter()
+            .any(|flag| part == flag || part.starts_with(&format!("{flag}=")))
+    });
+    match position {
+        Some(index) if flags.contains(&parts[index].as_str()) => match parts.get_mut(index + 1) {
+            Some(part) => *part = value.to_owned(),
+            None => parts.push(value.to_owned()),
+        },
+        Some(index) => {
+            let (flag, _) = parts[index].split_once('=').unwrap_or_default();
+            parts[index] = format!("{flag}={value}");
+        }
+        None => {
+            parts.insert(1, value.to_owned());
+            parts.insert(1, flags[0].to_owned());
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_cluster_args, get_missing_resource, get_namespace, replace_value, set_flag};
+    use crate::cli::command::shlex_split;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("kubectl get pods -n web", Some("web"), vec![])]
+    #[case("kubectl get pods --namespace=web --context prod", Some("web"), vec!["--context=prod"])]
+    #[case("kubectl --kubeconfig ~/k.yaml --namespace web get pods", Some("web"), vec!["--kubeconfig=~/k.yaml"])]
+    #[case("kubectl get pods", None, vec![])]
+    fn test_get_flags(
+        #[case] script: &str,
+        #[case] namespace: Option<&str>,
+        #[case] cluster_args: Vec<&str>,
+    ) {
+        let parts = shlex_split(script);
+        assert_eq!(get_namespace(&parts).as_deref(), namespace);
+        assert_eq!(get_cluster_args(&parts), cluster_args);
+    }
+
+    #[rstest]
+    #[case("Error from server (NotFound): pods \"web-1\" not found", Some(("pods", "web-1")))]
+    #[case("Error from server (NotFound): deployments.apps \"api\" not found", Some(("deployments.apps", "api")))]
+    #[case("error: the server doesn't have a resource type \"podz\"", None)]
+    fn test_get_missing_resource(#[case] output: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(
+            get_missing_resource(output),
+            expected.map(|(kind, name)| (kind.to_owned(), name.to_owned()))
+        );
+    }
+
+    #[rstest]
+    #[case("kubectl get pod ngnix", "kubectl get pod nginx")]
+    #[case("kubectl logs pod/ngnix -f", "kubectl logs pod/nginx -f")]
+    #[case(
+        "kubectl get pods --namespace=ngnix",
+        "kubectl get pods --namespace=nginx"
+    )]
+    fn test_replace_value(#[case] script: &str, #[case] expected: &str) {
+        assert_eq!(
+            replace_value(&shlex_split(script), "ngnix", "nginx").join(" "),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("kubectl get pods", "kubectl -n web get pods")]
+    #[case("kubectl get pods -n api", "kubectl get pods -n web")]
+    #[case("kubectl get pods --namespace=api", "kubectl get pods --namespace=web")]
+    #[case("kubectl get pods --namespace api", "kubectl get pods --namespace web")]
+    fn test_set_flag(#[case] script: &str, #[case] expected: &str) {
+        assert_eq!(
+            set_flag(&shlex_split(script), &["-n", "--namespace"], "web").join(" "),
+            expected
+        );
+    }
+}
@@ -1,9 +1,32 @@
-use crate::{cli::command::CrabCommand, shell::Shell};
+use crate::{
+    cli::command::CrabCommand,
+    shell::{Bash, Shell},
+};
 use std::path::Path;
 
+pub mod archlinux;
+pub mod cobra;
+pub mod docker;
+pub mod fedora;
 pub mod git;
-pub mod npm;
+pub mod kubernetes;
+pub mod package_json;
 pub mod parameterized_tests;
+pub mod systemd;
+pub mod terraform;
+pub mod wrappers;
+
+/// Quotes `word` for the shell, or for a POSIX shell if it is not known.
+pub fn quote(system_shell: Option<&dyn Shell>, word: &str) -> String {
+    system_shell.unwrap_or(&Bash).quote(word)
+}
+
+/// Rebuilds a command line from its arguments for the shell, or for a POSIX shell if it
+/// is not known. See `Shell::join`.
+pub fn join_script<S: AsRef<str>>(system_shell: Option<&dyn Shell>, parts: &[S]) -> String {
+    let parts = parts.iter().map(|part| part.as_ref()).collect::<Vec<_>>();
+    system_shell.unwrap_or(&Bash).join(&parts)
+}
 
 /// Matches a rule with a given command if it is an application.
 ///
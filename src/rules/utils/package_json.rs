@@ -0,0 +1,367 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::context::Context;
+
+/// A package of a workspace and the scripts defined in its `package.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    /// Value of the `name` field, or the path of the package relative to the workspace
+    /// root if it has none. Either can be given to `npm run --workspace`.
+    pub name: String,
+    pub dir: PathBuf,
+    pub scripts: Vec<String>,
+}
+
+/// Returns the path of the `package.json` used by package managers in the current
+/// directory, i.e. the nearest one found walking up from it.
+pub fn find_package_json(context: &dyn Context) -> Option<PathBuf> {
+    context
+        .current_dir()
+        .ancestors()
+        .map(|dir| dir.join("package.json"))
+        .find(|path| context.is_file(path))
+}
+
+/// Returns the names of the scripts defined in the nearest `package.json`, as they can be
+/// run by npm, yarn and pnpm.
+///
+/// The file is read directly, which is faster than asking a package manager.
+pub fn get_scripts(context: &dyn Context) -> Vec<String> {
+    find_package_json(context)
+        .and_then(|path| read_package_json(&path, context))
+        .map(|package| get_script_names(&package))
+        .unwrap_or_default()
+}
+
+/// Returns the packages of the workspace containing the current directory, declared by the
+/// `workspaces` field of npm and yarn or by the `pnpm-workspace.yaml` file of pnpm.
+///
+/// The root package is not part of them.
+pub fn get_workspaces(context: &dyn Context) -> Vec<Package> {
+    let Some((root, patterns)) = find_workspace_root(context) else {
+        return vec![];
+    };
+    let (excluded, included): (Vec<&String>, Vec<&String>) = patterns
+        .iter()
+        .partition(|pattern| pattern.starts_with('!'));
+    let mut dirs: Vec<PathBuf> = vec![];
+    for pattern in included {
+        for dir in expand_pattern(&root, pattern, context) {
+            if dir != root && !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    for pattern in excluded {
+        let excluded = expand_pattern(&root, &pattern[1..], context);
+        dirs.retain(|dir| !excluded.contains(dir));
+    }
+    dirs.sort();
+    dirs.into_iter()
+        .filter_map(|dir| {
+            let package = read_package_json(&dir.join("package.json"), context)?;
+            let name = match package.get("name") {
+                Some(Value::String(name)) => name.clone(),
+                _ => dir.strip_prefix(&root).ok()?.to_string_lossy().into_owned(),
+            };
+            Some(Package {
+                name,
+                dir,
+                scripts: get_script_names(&package),
+            })
+        })
+        .collect()
+}
+
+fn read_package_json(path: &Path, context: &dyn Context) -> Option<Map<String, Value>> {
+    match serde_json::from_str::<Value>(&context.read_to_string(path)?) {
+        Ok(Value::Object(package)) => Some(package),
+        _ => None,
+    }
+}
+
+fn get_script_names(package: &Map<String, Value>) -> Vec<String> {
+    match package.get("scripts") {
+        Some(Value::Object(scripts)) => scripts.keys().cloned().collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the nearest directory declaring workspaces, walking up from the current
+/// directory, along with the patterns of its workspaces.
+fn find_workspace_root(context: &dyn Context) -> Option<(PathBuf, Vec<String>)> {
+    context.current_dir().ancestors().find_map(|dir| {
+        let patterns = get_workspace_patterns(dir, context);
+        (!patterns.is_empty()).then(|| (dir.to_path_buf(), patterns))
+    })
+}
+
+fn get_workspace_patterns(dir: &Path, context: &dyn Context) -> Vec<String> {
+    if let Some(package) = read_package_json(&dir.join("package.json"), context) {
+        // Yarn also accepts `{"packages": [...], "nohoist": [...]}`
+        let patterns = match package.get("workspaces") {
+            Some(Value::Object(workspaces)) => workspaces.get("packages"),
+            workspaces => workspaces,
+        };
+        if let Some(Value::Array(patterns)) = patterns {
+            return patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str().map(|s| s.to_owned()))
+                .collect();
+        }
+    }
+    context
+        .read_to_string(&dir.join("pnpm-workspace.yaml"))
+        .map(|content| parse_pnpm_workspace(&content))
+        .unwrap_or_default()
+}
+
+/// Returns the `packages` of a `pnpm-workspace.yaml`, the only field that is needed, so the
+/// file is read line by line instead of being parsed as YAML.
+fn parse_pnpm_workspace(content: &str) -> Vec<String> {
+    let mut patterns = vec![];
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed == "packages:";
+        } else if let Some(pattern) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            patterns.push(pattern.trim().trim_matches(['"', '\'']).to_owned());
+        }
+    }
+    patterns
+}
+
+/// Returns the directories matching a workspace pattern, e.g. `packages/*` or `apps/**`.
+fn expand_pattern(root: &Path, pattern: &str, context: &dyn Context) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for segment in pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+    {
+        dirs = match segment {
+            "**" => dirs
+                .iter()
+                .flat_map(|dir| get_dirs_recursively(dir, context))
+                .collect(),
+            _ if segment.contains('*') => dirs
+                .iter()
+                .flat_map(|dir| get_subdirs(dir, context))
+                .filter(|dir| {
+                    dir.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| matches_segment(segment, name))
+                })
+                .collect(),
+            _ => dirs
+                .iter()
+                .map(|dir| dir.join(segment))
+                .filter(|dir| context.is_dir(dir))
+                .collect(),
+        };
+    }
+    dirs
+}
+
+/// Returns the directories inside of `dir`, skipping hidden ones and `node_modules`.
+fn get_subdirs(dir: &Path, context: &dyn Context) -> Vec<PathBuf> {
+    context
+        .read_dir(dir)
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !name.starts_with('.') && name != "node_modules")
+                && context.is_dir(entry)
+        })
+        .collect()
+}
+
+fn get_dirs_recursively(dir: &Path, context: &dyn Context) -> Vec<PathBuf> {
+    let mut dirs = vec![dir.to_path_buf()];
+    for subdir in get_subdirs(dir, context) {
+        dirs.extend(get_dirs_recursively(&subdir, context));
+    }
+    dirs
+}
+
+/// Matches a file name against a glob segment in which `*` stands for any characters.
+fn matches_segment(pattern: &str, name: &str) -> bool {
+    let mut literals = pattern.split('*');
+    let Some(mut rest) = name.strip_prefix(literals.next().unwrap_or_default()) else {
+        return false;
+    };
+    let literals: Vec<&str> = literals.collect();
+    let Some((last, middle)) = literals.split_last() else {
+        return rest.is_empty();
+    };
+    for literal in middle {
+        match rest.find(literal) {
+            Some(index) => rest = &rest[index + literal.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        find_package_json, get_scripts, get_workspaces, matches_segment, parse_pnpm_workspace,
+        Package,
+    };
+    use crate::context::FakeContext;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    const PACKAGE_JSON: &str = r#"{
+  "name": "code-view-web",
+  "scripts": {
+    "build": "webpack --progress --colors -p --config ./webpack.production.config.js",
+    "develop": "webpack-dev-server --progress --colors",
+    "test": "jest",
+    "watch-test": "jest --verbose --watch"
+  }
+}"#;
+
+    /// A monorepo in `/home/crab/shop` whose `web` and `api` workspaces have their own scripts.
+    fn workspace_context(workspaces: &str) -> FakeContext {
+        FakeContext::new()
+            .with_file(
+                "shop/package.json",
+                format!(r#"{{"name": "shop", "workspaces": {workspaces}, "scripts": {{"lint": "eslint ."}}}}"#)
+                    .as_bytes(),
+            )
+            .with_file(
+                "shop/packages/web/package.json",
+                br#"{"name": "@shop/web", "scripts": {"develop": "vite", "build": "vite build"}}"#,
+            )
+            .with_file(
+                "shop/packages/api/package.json",
+                br#"{"scripts": {"serve": "node index.js", "build": "tsc"}}"#,
+            )
+            .with_dir("shop/packages/api/node_modules")
+            .with_current_dir("/home/crab/shop")
+    }
+
+    #[rstest]
+    #[case(PACKAGE_JSON, vec!["build", "develop", "test", "watch-test"])]
+    #[case(r#"{"name": "no-scripts"}"#, vec![])]
+    #[case(r#"{"scripts": ["build"]}"#, vec![])]
+    #[case("{ not json", vec![])]
+    fn test_get_scripts(#[case] package_json: &str, #[case] expected: Vec<&str>) {
+        let context = FakeContext::new().with_file("package.json", package_json.as_bytes());
+        assert_eq!(get_scripts(&context), expected);
+    }
+
+    #[test]
+    fn test_get_scripts_without_package_json() {
+        assert!(get_scripts(&FakeContext::new()).is_empty());
+    }
+
+    #[test]
+    fn test_find_package_json_walks_up() {
+        let context = FakeContext::new()
+            .with_file("package.json", PACKAGE_JSON.as_bytes())
+            .with_current_dir("/home/crab/src/components");
+        assert_eq!(
+            find_package_json(&context),
+            Some(PathBuf::from("/home/crab/package.json"))
+        );
+        assert_eq!(
+            get_scripts(&context),
+            vec!["build", "develop", "test", "watch-test"]
+        );
+    }
+
+    fn package(name: &str, dir: &str, scripts: Vec<&str>) -> Package {
+        Package {
+            name: name.to_owned(),
+            dir: PathBuf::from(dir),
+            scripts: scripts.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(r#"["packages/*"]"#)]
+    #[case(r#"["packages/web", "packages/api"]"#)]
+    #[case(r#"["packages/**"]"#)]
+    #[case(r#"{"packages": ["./packages/*"], "nohoist": ["**/react"]}"#)]
+    fn test_get_workspaces(#[case] workspaces: &str) {
+        let expected = vec![
+            package(
+                "packages/api",
+                "/home/crab/shop/packages/api",
+                vec!["build", "serve"],
+            ),
+            package(
+                "@shop/web",
+                "/home/crab/shop/packages/web",
+                vec!["build", "develop"],
+            ),
+        ];
+        let context = workspace_context(workspaces);
+        assert_eq!(get_workspaces(&context), expected);
+        let context =
+            workspace_context(workspaces).with_current_dir("/home/crab/shop/packages/web");
+        assert_eq!(get_workspaces(&context), expected);
+        assert_eq!(get_scripts(&context), vec!["build", "develop"]);
+    }
+
+    #[rstest]
+    #[case(r#"["packages/*", "!packages/api"]"#, vec!["@shop/web"])]
+    #[case(r#"["packages/w*"]"#, vec!["@shop/web"])]
+    #[case("[]", vec![])]
+    fn test_get_workspaces_filtered(#[case] workspaces: &str, #[case] expected: Vec<&str>) {
+        let names: Vec<String> = get_workspaces(&workspace_context(workspaces))
+            .into_iter()
+            .map(|package| package.name)
+            .collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn test_get_workspaces_pnpm() {
+        let context = FakeContext::new()
+            .with_file("package.json", br#"{"name": "shop"}"#)
+            .with_file(
+                "pnpm-workspace.yaml",
+                b"packages:\n  # all packages\n  - 'packages/*'\n  - \"!**/test/**\"\ncatalog:\n  - react\n",
+            )
+            .with_file(
+                "packages/web/package.json",
+                br#"{"name": "web", "scripts": {"develop": "vite"}}"#,
+            );
+        assert_eq!(
+            get_workspaces(&context),
+            vec![package("web", "/home/crab/packages/web", vec!["develop"])]
+        );
+    }
+
+    #[rstest]
+    #[case("packages:\n  - apps/*\n  - 'libs/**'\n", vec!["apps/*", "libs/**"])]
+    #[case("packages:\n- apps/*\nonlyBuiltDependencies:\n- esbuild\n", vec!["apps/*"])]
+    #[case("catalog:\n  react: ^18\n", vec![])]
+    fn test_parse_pnpm_workspace(#[case] content: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(parse_pnpm_workspace(content), expected);
+    }
+
+    #[rstest]
+    #[case("*", "web", true)]
+    #[case("w*", "web", true)]
+    #[case("*-plugin", "eslint-plugin", true)]
+    #[case("a*b*c", "abc", true)]
+    #[case("a*b*c", "axbyc", true)]
+    #[case("a*b*c", "axbyd", false)]
+    #[case("ab*b", "ab", false)]
+    #[case("web", "api", false)]
+    fn test_matches_segment(#[case] pattern: &str, #[case] name: &str, #[case] expected: bool) {
+        assert_eq!(matches_segment(pattern, name), expected);
+    }
+}
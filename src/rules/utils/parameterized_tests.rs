@@ -1,5 +1,20 @@
 #[macro_export]
 macro_rules! parameterized_match_rule_tests {
+    ($match_rule:expr, context: $context:expr, $($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (script, stdout, check_value) = $value;
+                let system_shell = Bash{};
+                let mut command = CrabCommand::new(
+                            script.to_owned(),
+                            Some(stdout.to_owned()),
+                            None
+                        );
+                assert_eq!($match_rule(&mut command, Some(&system_shell), &$context), check_value);
+            }
+        )*
+    };
     ($match_rule:expr, $($name:ident: $value:expr,)*) => {
         $(
             #[test]
@@ -19,6 +34,21 @@ macro_rules! parameterized_match_rule_tests {
 
 #[macro_export]
 macro_rules! parameterized_get_new_command_tests {
+    ($get_new_command:expr, context: $context:expr, $($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (script, stdout, expected) = $value;
+                let system_shell = Bash{};
+                let mut command = CrabCommand::new(
+                            script.to_owned(),
+                            Some(stdout.to_owned()),
+                            None
+                        );
+                assert_eq!($get_new_command(&mut command, Some(&system_shell), &$context)[0], expected);
+            }
+        )*
+    };
     ($get_new_command:expr, $($name:ident: $value:expr,)*) => {
         $(
             #[test]
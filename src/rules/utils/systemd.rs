@@ -0,0 +1,151 @@
+use crate::{context::Context, utils::get_close_matches};
+
+/// Commands of `systemctl` that take units as arguments, e.g. `start` or `enable`.
+pub const UNIT_COMMANDS: [&str; 24] = [
+    "cat",
+    "clean",
+    "disable",
+    "edit",
+    "enable",
+    "freeze",
+    "help",
+    "is-active",
+    "is-enabled",
+    "is-failed",
+    "kill",
+    "mask",
+    "reenable",
+    "reload",
+    "reload-or-restart",
+    "restart",
+    "show",
+    "start",
+    "status",
+    "stop",
+    "thaw",
+    "try-reload-or-restart",
+    "try-restart",
+    "unmask",
+];
+
+/// Returns whether the command talks to the user manager instead of the system one.
+pub fn is_user_manager(script_parts: &[String]) -> bool {
+    script_parts.iter().any(|part| part == "--user")
+}
+
+/// Returns the names of the unit files of the system manager, or of the user manager if
+/// `user` is set, e.g. `nginx.service`.
+pub fn get_unit_files(user: bool, context: &dyn Context) -> Vec<String> {
+    let mut args = vec!["list-unit-files", "--no-legend", "--no-pager"];
+    if user {
+        args.insert(0, "--user");
+    }
+    context
+        .run("systemctl", &args)
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .filter_map(|line| line.split_whitespace().next())
+                .map(|unit| unit.to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns whether `name` refers to `unit`. Units can be given without the `.service`
+/// suffix.
+pub fn is_same_unit(name: &str, unit: &str) -> bool {
+    name == unit || unit.strip_suffix(".service") == Some(name)
+}
+
+/// Returns the units closest to `name`, written like it: without the `.service` suffix if
+/// `name` has none.
+///
+/// Services are compared without their suffix, which would make them all look alike.
+pub fn get_close_units(name: &str, user: bool, context: &dyn Context) -> Vec<String> {
+    let (stem, suffix) = match name.strip_suffix(".service") {
+        Some(stem) => (stem, ".service"),
+        None => (name, ""),
+    };
+    let units: Vec<String> = get_unit_files(user, context)
+        .into_iter()
+        .filter_map(|unit| match unit.strip_suffix(".service") {
+            Some(service) => Some(service.to_owned()),
+            None => suffix.is_empty().then_some(unit),
+        })
+        .collect();
+    let units: Vec<&str> = units.iter().map(|s| s.as_str()).collect();
+    get_close_matches(stem, &units, None, None)
+        .into_iter()
+        .map(|unit| format!("{unit}{suffix}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_close_units, get_unit_files, is_same_unit};
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNIT_FILES: &str = "\
+cron.service                               enabled         enabled
+nginx.service                              enabled         enabled
+nginx-debug.service                        disabled        enabled
+ssh.socket                                 disabled        enabled
+timers.target                              static          -
+";
+    const USER_UNIT_FILES: &str = "\
+pipewire.service                           enabled         enabled
+syncthing.service                          enabled         enabled
+";
+
+    fn context() -> FakeContext {
+        FakeContext::new()
+            .with_program("systemctl")
+            .with_command_output(
+                "systemctl list-unit-files --no-legend --no-pager",
+                UNIT_FILES,
+            )
+            .with_command_output(
+                "systemctl --user list-unit-files --no-legend --no-pager",
+                USER_UNIT_FILES,
+            )
+    }
+
+    #[test]
+    fn test_get_unit_files() {
+        assert_eq!(
+            get_unit_files(false, &context()),
+            vec![
+                "cron.service",
+                "nginx.service",
+                "nginx-debug.service",
+                "ssh.socket",
+                "timers.target"
+            ]
+        );
+        assert_eq!(
+            get_unit_files(true, &context()),
+            vec!["pipewire.service", "syncthing.service"]
+        );
+    }
+
+    #[rstest]
+    #[case("nginx", "nginx.service", true)]
+    #[case("nginx.service", "nginx.service", true)]
+    #[case("ssh", "ssh.socket", false)]
+    #[case("ngnix", "nginx.service", false)]
+    fn test_is_same_unit(#[case] name: &str, #[case] unit: &str, #[case] expected: bool) {
+        assert_eq!(is_same_unit(name, unit), expected);
+    }
+
+    #[rstest]
+    #[case("ngnix", false, vec!["nginx", "nginx-debug"])]
+    #[case("ngnix.service", false, vec!["nginx.service", "nginx-debug.service"])]
+    #[case("sycnthing", true, vec!["syncthing"])]
+    #[case("postgres", false, vec![])]
+    fn test_get_close_units(#[case] name: &str, #[case] user: bool, #[case] expected: Vec<&str>) {
+        assert_eq!(get_close_units(name, user, &context()), expected);
+    }
+}
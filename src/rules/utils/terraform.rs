@@ -0,0 +1,39 @@
+/// Terraform and its fork OpenTofu, which share their commands and messages.
+pub const TERRAFORM_APPS: [&str; 2] = ["terraform", "tofu"];
+
+/// Commands of Terraform and OpenTofu, used when they do not suggest any themselves.
+pub const TERRAFORM_COMMANDS: [&str; 23] = [
+    "apply",
+    "console",
+    "destroy",
+    "fmt",
+    "force-unlock",
+    "get",
+    "graph",
+    "import",
+    "init",
+    "login",
+    "logout",
+    "metadata",
+    "output",
+    "plan",
+    "providers",
+    "refresh",
+    "show",
+    "state",
+    "taint",
+    "test",
+    "untaint",
+    "validate",
+    "version",
+];
+
+/// Returns the position of the command, e.g. `plan`, skipping global options like
+/// `-chdir=infra`.
+pub fn get_command_position(script_parts: &[String]) -> Option<usize> {
+    script_parts
+        .iter()
+        .skip(1)
+        .position(|part| !part.starts_with('-'))
+        .map(|position| position + 1)
+}
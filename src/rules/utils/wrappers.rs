@@ -0,0 +1,405 @@
+use std::ops::Range;
+use std::path::Path;
+
+use crate::cli::command::shlex_split;
+use crate::cli::compound::is_assignment;
+use crate::context::Context;
+use crate::{ENV_VAR_NAME_PRIVILEGE_COMMAND, ENV_VAR_NAME_WRAPPERS};
+
+/// Privilege escalation tools, in the order they are preferred when suggesting one.
+pub const ESCALATION_TOOLS: [&str; 4] = ["sudo", "doas", "run0", "pkexec"];
+
+/// A command that runs another command, like `sudo`, `env` or `nohup`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wrapper {
+    pub name: String,
+    /// Options that take the following word as their value, e.g. `-u` in `sudo -u root`.
+    pub options_with_value: Vec<String>,
+    /// `true` if the wrapper runs the command with elevated privileges.
+    pub escalates: bool,
+    /// `true` if the wrapper accepts `NAME=value` assignments before the command.
+    pub accepts_assignments: bool,
+}
+
+impl Wrapper {
+    pub fn new(name: &str, options_with_value: &[&str]) -> Self {
+        Self {
+            name: name.to_owned(),
+            options_with_value: options_with_value.iter().map(|s| s.to_string()).collect(),
+            escalates: ESCALATION_TOOLS.contains(&name),
+            accepts_assignments: name == "env",
+        }
+    }
+
+    fn takes_value(&self, option: &str) -> bool {
+        if option.starts_with("--") {
+            !option.contains('=') && self.options_with_value.iter().any(|o| o == option)
+        } else {
+            // Short options can have their value attached, e.g. `-uroot`
+            option.len() == 2 && self.options_with_value.iter().any(|o| o == option)
+        }
+    }
+}
+
+/// Returns the wrappers recognized by default.
+pub fn default_wrappers() -> Vec<Wrapper> {
+    vec![
+        Wrapper::new(
+            "sudo",
+            &[
+                "-u",
+                "-g",
+                "-h",
+                "-p",
+                "-C",
+                "-D",
+                "-r",
+                "-t",
+                "-U",
+                "-T",
+                "-R",
+                "--user",
+                "--group",
+                "--host",
+                "--prompt",
+                "--close-from",
+                "--chdir",
+                "--role",
+                "--type",
+                "--other-user",
+                "--command-timeout",
+                "--chroot",
+            ],
+        ),
+        Wrapper::new("doas", &["-u", "-C"]),
+        Wrapper::new("pkexec", &["--user"]),
+        Wrapper::new(
+            "run0",
+            &[
+                "-u",
+                "-g",
+                "-D",
+                "--user",
+                "--group",
+                "--chdir",
+                "--machine",
+                "--setenv",
+                "--nice",
+                "--unit",
+                "--description",
+                "--property",
+                "--slice",
+            ],
+        ),
+        Wrapper::new(
+            "env",
+            &["-u", "-C", "-S", "--unset", "--chdir", "--split-string"],
+        ),
+        Wrapper::new("time", &["-f", "-o", "--format", "--output"]),
+        Wrapper::new("nice", &["-n", "--adjustment"]),
+        Wrapper::new("nohup", &[]),
+    ]
+}
+
+/// Returns the default wrappers and the ones listed by the user in `OHCRAB_WRAPPERS`,
+/// separated by commas or spaces.
+pub fn get_wrappers(context: &dyn Context) -> Vec<Wrapper> {
+    let mut wrappers = default_wrappers();
+    if let Some(extra) = context.env_var(ENV_VAR_NAME_WRAPPERS) {
+        for name in extra.split([',', ' ']).filter(|name| !name.is_empty()) {
+            if !wrappers.iter().any(|wrapper| wrapper.name == name) {
+                wrappers.push(Wrapper::new(name, &[]));
+            }
+        }
+    }
+    wrappers
+}
+
+/// A wrapper found in front of a script, with its options, e.g. `sudo -u deploy `.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prefix<'a> {
+    /// Name of the wrapper, or `None` for variable assignments like `LANG=C `.
+    pub name: Option<String>,
+    /// Original text of the prefix, including the whitespace after it.
+    pub text: &'a str,
+    pub escalates: bool,
+}
+
+/// A script split into the wrappers in front of it and the command they run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unwrapped<'a> {
+    pub prefixes: Vec<Prefix<'a>>,
+    pub script: &'a str,
+}
+
+impl Unwrapped<'_> {
+    /// Returns the text of all the prefixes, exactly as they were typed.
+    pub fn prefix(&self) -> String {
+        self.prefixes.iter().map(|prefix| prefix.text).collect()
+    }
+
+    pub fn is_escalated(&self) -> bool {
+        self.prefixes.iter().any(|prefix| prefix.escalates)
+    }
+
+    /// Returns the script without the privilege escalation wrappers, keeping the rest.
+    pub fn without_escalation(&self) -> String {
+        self.prefixes
+            .iter()
+            .filter(|prefix| !prefix.escalates)
+            .map(|prefix| prefix.text)
+            .collect::<String>()
+            + self.script
+    }
+}
+
+/// Splits `script` into the wrappers in front of it and the wrapped command, using the
+/// wrappers returned by `get_wrappers`.
+pub fn unwrap_script<'a>(script: &'a str, context: &dyn Context) -> Unwrapped<'a> {
+    unwrap_script_with(script, &get_wrappers(context))
+}
+
+/// Returns the program run by `script`, skipping the wrappers in front of it, e.g. `vim`
+/// for `sudo -u root vim /etc/hosts`.
+pub fn get_wrapped_program(script: &str, context: &dyn Context) -> Option<String> {
+    shlex_split(unwrap_script(script, context).script)
+        .into_iter()
+        .next()
+}
+
+/// Splits `script` into the given `wrappers` in front of it and the wrapped command.
+///
+/// A wrapper without a command after it, like `sudo -i`, is not considered a wrapper.
+pub fn unwrap_script_with<'a>(script: &'a str, wrappers: &[Wrapper]) -> Unwrapped<'a> {
+    let script = script.trim_start();
+    let mut prefixes = vec![];
+    let mut pos = 0;
+
+    while let Some((range, word)) = next_word(script, pos) {
+        let end = if is_assignment(&word) {
+            prefixes.push(Prefix {
+                name: None,
+                text: "",
+                escalates: false,
+            });
+            range.end
+        } else if let Some(wrapper) = find_wrapper(wrappers, &word) {
+            prefixes.push(Prefix {
+                name: Some(wrapper.name.to_owned()),
+                text: "",
+                escalates: wrapper.escalates,
+            });
+            skip_wrapper_arguments(script, range.end, wrapper)
+        } else {
+            break;
+        };
+
+        let next_start = next_word(script, end).map_or(script.len(), |(range, _)| range.start);
+        if next_start == script.len() {
+            // Nothing is being wrapped
+            prefixes.pop();
+            break;
+        }
+        prefixes.last_mut().unwrap().text = &script[range.start..next_start];
+        pos = next_start;
+    }
+
+    Unwrapped {
+        prefixes,
+        script: &script[pos..],
+    }
+}
+
+fn find_wrapper<'a>(wrappers: &'a [Wrapper], word: &str) -> Option<&'a Wrapper> {
+    let name = Path::new(word)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(word);
+    wrappers.iter().find(|wrapper| wrapper.name == name)
+}
+
+/// Returns the position after the options (and assignments) of `wrapper`.
+fn skip_wrapper_arguments(script: &str, mut pos: usize, wrapper: &Wrapper) -> usize {
+    while let Some((range, word)) = next_word(script, pos) {
+        if word == "--" {
+            return range.end;
+        } else if word.starts_with('-') && word.len() > 1 {
+            pos = range.end;
+            if wrapper.takes_value(&word) {
+                pos = next_word(script, pos).map_or(pos, |(range, _)| range.end);
+            }
+        } else if wrapper.accepts_assignments && is_assignment(&word) {
+            pos = range.end;
+        } else {
+            break;
+        }
+    }
+    pos
+}
+
+/// Returns the range of the next shell word starting at `pos`, and its unquoted value.
+fn next_word(script: &str, pos: usize) -> Option<(Range<usize>, String)> {
+    let bytes = script.as_bytes();
+    let mut start = pos;
+    while start < bytes.len() && bytes[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    if start == bytes.len() {
+        return None;
+    }
+
+    let mut end = start;
+    let mut quote = None;
+    while end < bytes.len() {
+        match (quote, bytes[end]) {
+            (None, byte) if byte.is_ascii_whitespace() => break,
+            (None, b'\\') | (Some(b'"'), b'\\') => end += 1,
+            (None, byte @ (b'\'' | b'"')) => quote = Some(byte),
+            (Some(q), byte) if q == byte => quote = None,
+            _ => {}
+        }
+        end += 1;
+    }
+    let end = end.min(bytes.len());
+
+    let word = &script[start..end];
+    let value = shlex_split(word)
+        .into_iter()
+        .next()
+        .unwrap_or(word.to_owned());
+    Some((start..end, value))
+}
+
+/// Returns the command used to run commands with elevated privileges.
+///
+/// It can be set with `OHCRAB_PRIVILEGE_COMMAND` (e.g. `doas` or `sudo -E`), otherwise
+/// the first escalation tool installed is used, falling back to `sudo`.
+pub fn get_privilege_command(context: &dyn Context) -> String {
+    if let Some(command) = context.env_var(ENV_VAR_NAME_PRIVILEGE_COMMAND) {
+        if !command.trim().is_empty() {
+            return command.trim().to_owned();
+        }
+    }
+    ESCALATION_TOOLS
+        .iter()
+        .find(|tool| context.which(tool).is_some())
+        .unwrap_or(&"sudo")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        default_wrappers, get_privilege_command, get_wrapped_program, unwrap_script,
+        unwrap_script_with, Wrapper, ENV_VAR_NAME_PRIVILEGE_COMMAND, ENV_VAR_NAME_WRAPPERS,
+    };
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("ls -la", "", "ls -la")]
+    #[case("sudo apt install vim", "sudo ", "apt install vim")]
+    #[case("sudo -E make install", "sudo -E ", "make install")]
+    #[case("sudo -u deploy git pull", "sudo -u deploy ", "git pull")]
+    #[case("sudo -udeploy git pull", "sudo -udeploy ", "git pull")]
+    #[case("sudo --user=deploy git pull", "sudo --user=deploy ", "git pull")]
+    #[case("sudo -i -- git pull", "sudo -i -- ", "git pull")]
+    #[case(
+        "/usr/bin/sudo  -H   pip install",
+        "/usr/bin/sudo  -H   ",
+        "pip install"
+    )]
+    #[case("doas -u root vim /etc/hosts", "doas -u root ", "vim /etc/hosts")]
+    #[case(
+        "pkexec --user root systemctl restart",
+        "pkexec --user root ",
+        "systemctl restart"
+    )]
+    #[case("run0 --user=root -D / ls", "run0 --user=root -D / ", "ls")]
+    #[case(
+        "env -i FOO=bar BAR=\"a b\" make",
+        "env -i FOO=bar BAR=\"a b\" ",
+        "make"
+    )]
+    #[case("LANG=C make", "LANG=C ", "make")]
+    #[case("time -f '%e s' cargo build", "time -f '%e s' ", "cargo build")]
+    #[case("nice -n 10 cargo build", "nice -n 10 ", "cargo build")]
+    #[case("nice -10 cargo build", "nice -10 ", "cargo build")]
+    #[case("nohup sudo -E time make", "nohup sudo -E time ", "make")]
+    #[case("sudo -i", "", "sudo -i")]
+    #[case("sudo", "", "sudo")]
+    #[case("env", "", "env")]
+    #[case("timeout 10 make", "", "timeout 10 make")]
+    fn test_unwrap_script(#[case] script: &str, #[case] prefix: &str, #[case] inner: &str) {
+        let unwrapped = unwrap_script_with(script, &default_wrappers());
+        assert_eq!(unwrapped.prefix(), prefix);
+        assert_eq!(unwrapped.script, inner);
+        assert_eq!(unwrapped.prefix() + unwrapped.script, script);
+    }
+
+    #[rstest]
+    #[case("sudo -u deploy nice -n 5 make", true, "nice -n 5 make")]
+    #[case("nohup doas make", true, "nohup make")]
+    #[case("env FOO=1 make", false, "env FOO=1 make")]
+    fn test_escalation(#[case] script: &str, #[case] escalated: bool, #[case] without: &str) {
+        let unwrapped = unwrap_script_with(script, &default_wrappers());
+        assert_eq!(unwrapped.is_escalated(), escalated);
+        assert_eq!(unwrapped.without_escalation(), without);
+    }
+
+    #[test]
+    fn test_unwrap_script_with_custom_wrappers() {
+        let mut wrappers = default_wrappers();
+        wrappers.push(Wrapper::new("firejail", &["--profile"]));
+        let unwrapped = unwrap_script_with("firejail --profile web firefox", &wrappers);
+        assert_eq!(unwrapped.prefix(), "firejail --profile web ");
+        assert_eq!(unwrapped.script, "firefox");
+        assert!(!unwrapped.is_escalated());
+    }
+
+    #[rstest]
+    #[case("firejail", "firejail --profile=web ", "firefox")]
+    #[case("firejail,proxychains", "firejail --profile=web ", "firefox")]
+    #[case("proxychains firejail", "firejail --profile=web ", "firefox")]
+    #[case("", "", "firejail --profile=web firefox")]
+    fn test_unwrap_script_with_configured_wrappers(
+        #[case] configured: &str,
+        #[case] prefix: &str,
+        #[case] inner: &str,
+    ) {
+        let context = FakeContext::new().with_env(ENV_VAR_NAME_WRAPPERS, configured);
+        let unwrapped = unwrap_script("firejail --profile=web firefox", &context);
+        assert_eq!(unwrapped.prefix(), prefix);
+        assert_eq!(unwrapped.script, inner);
+    }
+
+    #[rstest]
+    #[case("vim /etc/hosts", Some("vim"))]
+    #[case("sudo -u root vim /etc/hosts", Some("vim"))]
+    #[case("LANG=C '/usr/bin/my vim' a", Some("/usr/bin/my vim"))]
+    #[case("sudo -i", Some("sudo"))]
+    #[case("", None)]
+    fn test_get_wrapped_program(#[case] script: &str, #[case] expected: Option<&str>) {
+        assert_eq!(
+            get_wrapped_program(script, &FakeContext::new()).as_deref(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(FakeContext::new(), "sudo")]
+    #[case(FakeContext::new().with_program("doas"), "doas")]
+    #[case(FakeContext::new().with_program("doas").with_program("sudo"), "sudo")]
+    #[case(FakeContext::new().with_program("run0").with_program("pkexec"), "run0")]
+    #[case(
+        FakeContext::new().with_program("sudo").with_env(ENV_VAR_NAME_PRIVILEGE_COMMAND, "doas"),
+        "doas"
+    )]
+    #[case(
+        FakeContext::new().with_env(ENV_VAR_NAME_PRIVILEGE_COMMAND, "sudo -E"),
+        "sudo -E"
+    )]
+    fn test_get_privilege_command(#[case] context: FakeContext, #[case] expected: &str) {
+        assert_eq!(get_privilege_command(&context), expected);
+    }
+}
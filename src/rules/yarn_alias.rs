@@ -0,0 +1,75 @@
+use super::{utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, shell::Shell, utils::replace_argument};
+use regex::Regex;
+
+fn get_suggestion(output: &str) -> Option<String> {
+    let re = Regex::new(r#"Did you mean [`"](?:yarn )?([^`"]*)[`"]"#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_suggestion(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["yarn"], Some(1))
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    match command.output.as_deref().and_then(get_suggestion) {
+        Some(fix) => vec![replace_argument(
+            &command.script,
+            &command.script_parts[1],
+            &fix,
+        )],
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "yarn_alias".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const OUTPUT_REMOVE: &str = "error Command \"rm\" not found. Did you mean \"remove\"?";
+    const OUTPUT_LIST: &str = "error Command \"ls\" not found. Did you mean \"list\"?";
+
+    #[rstest]
+    #[case("yarn rm lodash", OUTPUT_REMOVE, true)]
+    #[case("yarn ls", OUTPUT_LIST, true)]
+    #[case("yarn remove lodash", "", false)]
+    #[case("npm rm lodash", OUTPUT_REMOVE, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("yarn rm lodash", OUTPUT_REMOVE, vec!["yarn remove lodash"])]
+    #[case("yarn ls", OUTPUT_LIST, vec!["yarn list"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -0,0 +1,117 @@
+use super::{
+    utils::{join_script, match_rule_with_is_app},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, shell::Shell};
+use regex::Regex;
+
+/// npm commands and the yarn commands that replace them when they are given packages.
+const NPM_COMMANDS: [(&str, &str); 5] = [
+    ("install", "add"),
+    ("i", "add"),
+    ("uninstall", "remove"),
+    ("un", "remove"),
+    ("rm", "remove"),
+];
+
+/// Returns the command yarn classic suggests, e.g.
+/// ``error `install` has been replaced with `add` [...]. Run "yarn add lodash" instead.``
+fn get_suggestion(output: &str) -> Option<String> {
+    let re = Regex::new(r#"Run "(.*)" instead"#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+/// Returns the yarn command for an npm command, if it was given packages, which is what
+/// yarn berry does not accept.
+fn get_yarn_command(command: &CrabCommand) -> Option<&'static str> {
+    let npm_command = command.script_parts.get(1)?;
+    let has_packages = command.script_parts[2..]
+        .iter()
+        .any(|part| !part.starts_with('-'));
+    NPM_COMMANDS
+        .iter()
+        .find(|(npm, _)| npm == npm_command)
+        .filter(|_| has_packages)
+        .map(|(_, yarn)| *yarn)
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_suggestion(output).is_some()
+            || (!command.succeeded()
+                && output.to_lowercase().contains("error")
+                && get_yarn_command(command).is_some())
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["yarn"], Some(1))
+}
+
+pub fn get_new_command(command: &mut CrabCommand, system_shell: Option<&dyn Shell>) -> Vec<String> {
+    if let Some(suggestion) = command.output.as_deref().and_then(get_suggestion) {
+        return vec![suggestion];
+    }
+    match get_yarn_command(command) {
+        Some(yarn_command) => {
+            let mut parts = command.script_parts.clone();
+            parts[1] = yarn_command.to_owned();
+            vec![join_script(system_shell, &parts)]
+        }
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::new(
+        "yarn_command_replaced".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use rstest::rstest;
+
+    const CLASSIC_OUTPUT: &str = "error `install` has been replaced with `add` to add new dependencies. Run \"yarn add lodash\" instead.";
+    const BERRY_OUTPUT: &str = "Usage Error: Extraneous positional argument (\"lodash\")\n\n$ yarn install [--json] [--immutable] [--mode #0]";
+
+    #[rstest]
+    #[case("yarn install lodash", CLASSIC_OUTPUT, true)]
+    #[case("yarn install lodash", BERRY_OUTPUT, true)]
+    #[case(
+        "yarn uninstall lodash",
+        "error Command \"uninstall\" not found.",
+        true
+    )]
+    #[case("yarn install", BERRY_OUTPUT, false)]
+    #[case("yarn install lodash", "", false)]
+    #[case("npm install lodash", CLASSIC_OUTPUT, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None)
+            .with_exit_status(Some(1), None);
+        assert_eq!(match_rule(&mut command, None), is_match);
+    }
+
+    #[rstest]
+    #[case("yarn install lodash", CLASSIC_OUTPUT, vec!["yarn add lodash"])]
+    #[case("yarn i -D jest ts-jest", BERRY_OUTPUT, vec!["yarn add -D jest ts-jest"])]
+    #[case("yarn uninstall lodash", "error Command \"uninstall\" not found.", vec!["yarn remove lodash"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None), expected);
+    }
+}
@@ -0,0 +1,107 @@
+use super::{
+    utils::{match_rule_with_is_app, package_json::get_scripts},
+    BuiltinRule,
+};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+/// Returns the script yarn could not find, from yarn classic (`Command "buidl" not found`)
+/// or yarn berry (`Couldn't find a script named "buidl"`).
+fn get_missing_script(output: &str) -> Option<String> {
+    let re = Regex::new(r#"(?:Command|script named) "([^"]+)""#).unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_script(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_with_is_app(auxiliary_match_rule, command, vec!["yarn"], Some(1))
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    match command.output.as_deref().and_then(get_missing_script) {
+        Some(script) => replace_command(
+            command,
+            &script,
+            get_scripts(context).iter().map(|s| s.as_str()).collect(),
+        ),
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "yarn_missing_script".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const PACKAGE_JSON: &str = r#"{
+  "name": "code-view-web",
+  "scripts": {
+    "build": "webpack --progress --colors -p --config ./webpack.production.config.js",
+    "develop": "webpack-dev-server --progress --colors",
+    "test": "jest",
+    "watch-test": "jest --verbose --watch"
+  }
+}"#;
+
+    const CLASSIC_OUTPUT: &str = "yarn run v1.22.19
+error Command \"dvelop\" not found.
+info Visit https://yarnpkg.com/en/docs/cli/run for documentation about this command.";
+    const BERRY_OUTPUT: &str = "Usage Error: Couldn't find a script named \"dvelop\".
+
+$ yarn run [--inspect] [--inspect-brk] [-T,--top-level] [-B,--binaries-only] [--require #0] <scriptName> ...";
+
+    #[rstest]
+    #[case("yarn dvelop", CLASSIC_OUTPUT, true)]
+    #[case("yarn run dvelop", BERRY_OUTPUT, true)]
+    #[case("yarn develop", "", false)]
+    #[case("pnpm dvelop", CLASSIC_OUTPUT, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("yarn dvelop", CLASSIC_OUTPUT, vec!["yarn develop", "yarn build", "yarn test"])]
+    #[case("yarn run dvelop --port 3000", BERRY_OUTPUT, vec!["yarn run develop --port 3000", "yarn run build --port 3000", "yarn run test --port 3000"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let context = FakeContext::new().with_file("package.json", PACKAGE_JSON.as_bytes());
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
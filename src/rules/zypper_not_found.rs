@@ -0,0 +1,120 @@
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{cli::command::CrabCommand, context::Context, shell::Shell, utils::replace_command};
+use regex::Regex;
+
+fn get_missing_package(output: &str) -> Option<String> {
+    let re = Regex::new(r"No provider of '([^']+)' found").unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+/// Returns the names of the packages in the table printed by `zypper search`, e.g.
+/// `   | vim | Vi IMproved | package`.
+fn get_packages(context: &dyn Context) -> Vec<String> {
+    let stdout = context
+        .run(
+            "zypper",
+            &["--no-refresh", "--quiet", "search", "--type", "package"],
+        )
+        .map(|output| output.stdout)
+        .unwrap_or_default();
+    let mut packages: Vec<String> = stdout
+        .lines()
+        .filter_map(|line| line.split('|').nth(1))
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty() && name != "Name")
+        .collect();
+    packages.dedup();
+    packages
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_missing_package(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["zypper"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    let Some(package) = command.output.as_deref().and_then(get_missing_package) else {
+        return vec![];
+    };
+    let packages = get_packages(context);
+    let packages = packages.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    replace_command(command, &package, packages)
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "zypper_not_found".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const NO_PROVIDER: &str = "Loading repository data...
+Reading installed packages...
+'htpo' not found in package names. Trying capabilities.
+No provider of 'htpo' found.
+Resolving package dependencies...
+
+Nothing to do.";
+    const SEARCH: &str = "
+S  | Name        | Summary                          | Type
+---+-------------+----------------------------------+--------
+   | btop        | Usage and stats for processor    | package
+i  | htop        | An interactive process viewer    | package
+   | htop        | An interactive process viewer    | package
+   | vim         | Vi IMproved                      | package
+";
+
+    #[rstest]
+    #[case("zypper install htpo", NO_PROVIDER, true)]
+    #[case("sudo zypper in htpo", NO_PROVIDER, true)]
+    #[case("zypper install htop", "Nothing to do.", false)]
+    #[case("dnf install htpo", NO_PROVIDER, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("zypper install htpo", vec!["zypper install htop", "zypper install btop"])]
+    #[case("sudo zypper in htpo", vec!["sudo zypper in htop", "sudo zypper in btop"])]
+    fn test_get_new_command(#[case] command: &str, #[case] expected: Vec<&str>) {
+        let context = FakeContext::new()
+            .with_command_output("zypper --no-refresh --quiet search --type package", SEARCH);
+        let mut command = CrabCommand::new(command.to_owned(), Some(NO_PROVIDER.to_owned()), None);
+        assert_eq!(get_new_command(&mut command, None, &context), expected);
+    }
+}
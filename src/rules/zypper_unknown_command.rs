@@ -0,0 +1,148 @@
+use super::{match_rule_without_sudo, utils::match_rule_with_is_app, BuiltinRule};
+use crate::{
+    cli::command::CrabCommand,
+    context::Context,
+    shell::Shell,
+    utils::{get_close_matches, replace_argument},
+};
+use regex::Regex;
+
+const COMMANDS: [&str; 42] = [
+    "addlock",
+    "addrepo",
+    "addservice",
+    "cleanlocks",
+    "clean",
+    "dist-upgrade",
+    "download",
+    "help",
+    "info",
+    "install",
+    "install-new-recommends",
+    "licenses",
+    "list-patches",
+    "list-updates",
+    "locks",
+    "modifyrepo",
+    "modifyservice",
+    "needs-rebooting",
+    "packages",
+    "patch",
+    "patch-check",
+    "patches",
+    "patterns",
+    "products",
+    "ps",
+    "purge-kernels",
+    "refresh",
+    "refresh-services",
+    "remove",
+    "removelock",
+    "removerepo",
+    "removeservice",
+    "renamerepo",
+    "repos",
+    "search",
+    "services",
+    "shell",
+    "source-download",
+    "source-install",
+    "update",
+    "verify",
+    "what-provides",
+];
+
+fn get_unknown_command(output: &str) -> Option<String> {
+    let re = Regex::new(r"Unknown command '([^']+)'").unwrap();
+    re.captures(output).map(|caps| caps[1].to_owned())
+}
+
+fn auxiliary_match_rule(command: &CrabCommand) -> bool {
+    if let Some(output) = &command.output {
+        get_unknown_command(output).is_some()
+    } else {
+        false
+    }
+}
+
+pub fn match_rule(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> bool {
+    match_rule_without_sudo(
+        |command| match_rule_with_is_app(auxiliary_match_rule, command, vec!["zypper"], None),
+        command,
+        context,
+    )
+}
+
+pub fn get_new_command(
+    command: &mut CrabCommand,
+    system_shell: Option<&dyn Shell>,
+    context: &dyn Context,
+) -> Vec<String> {
+    match command.output.as_deref().and_then(get_unknown_command) {
+        Some(unknown) => get_close_matches(&unknown, &COMMANDS, None, None)
+            .iter()
+            .map(|new| replace_argument(&command.script, &unknown, new))
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn get_rule() -> BuiltinRule {
+    BuiltinRule::with_context(
+        "zypper_unknown_command".to_owned(),
+        None,
+        None,
+        None,
+        Box::new(match_rule),
+        get_new_command,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_new_command, match_rule};
+    use crate::cli::command::CrabCommand;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    const UNKNOWN_INSTALL: &str = "Unknown command 'isntall'
+
+  Usage:
+	zypper [--global-options] <command> [--command-options] [arguments]
+	zypper <subcommand> [--command-options] [arguments]
+";
+    const UNKNOWN_REFRESH: &str = "Unknown command 'refersh'";
+
+    #[rstest]
+    #[case("zypper isntall vim", UNKNOWN_INSTALL, true)]
+    #[case("sudo zypper refersh", UNKNOWN_REFRESH, true)]
+    #[case("zypper install vim", "", false)]
+    #[case("dnf isntall vim", UNKNOWN_INSTALL, false)]
+    fn test_match(#[case] command: &str, #[case] stdout: &str, #[case] is_match: bool) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            match_rule(&mut command, None, &FakeContext::new()),
+            is_match
+        );
+    }
+
+    #[rstest]
+    #[case("zypper isntall vim", UNKNOWN_INSTALL, vec!["zypper install vim", "zypper source-install vim"])]
+    #[case("sudo zypper refersh", UNKNOWN_REFRESH, vec!["sudo zypper refresh", "sudo zypper search", "sudo zypper refresh-services"])]
+    fn test_get_new_command(
+        #[case] command: &str,
+        #[case] stdout: &str,
+        #[case] expected: Vec<&str>,
+    ) {
+        let mut command = CrabCommand::new(command.to_owned(), Some(stdout.to_owned()), None);
+        assert_eq!(
+            get_new_command(&mut command, None, &FakeContext::new()),
+            expected
+        );
+    }
+}
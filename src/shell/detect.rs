@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use crate::context::Context;
+
+/// Names of the shells ohcrab can integrate with.
+pub const SUPPORTED_SHELLS: [&str; 5] = ["bash", "zsh", "nu", "tcsh", "csh"];
+
+/// Maximum number of ancestors of the process inspected to find the shell.
+const MAX_DEPTH: usize = 8;
+
+/// Detects the shell the user is running.
+///
+/// The ancestors of the process `pid` are inspected first, since `$SHELL` only holds the
+/// login shell, which may not be the one in use. They are read from `/proc` when it is
+/// available and from `ps` otherwise, e.g. on macOS. If none of them is a supported shell,
+/// the name of the shell in `$SHELL` is returned, even if it is not supported.
+pub fn detect_shell(context: &dyn Context, pid: u32) -> Option<String> {
+    detect_shell_from_process_tree(context, pid).or_else(|| {
+        context.env_var("SHELL").and_then(|shell| {
+            Path::new(&shell)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_owned())
+        })
+    })
+}
+
+fn detect_shell_from_process_tree(context: &dyn Context, mut pid: u32) -> Option<String> {
+    for _ in 0..MAX_DEPTH {
+        let name = get_process_name(context, pid)?;
+        // Login shells are named after `argv[0]`, e.g. `-bash`
+        let name = name.trim_start_matches('-');
+        if SUPPORTED_SHELLS.contains(&name) {
+            return Some(name.to_owned());
+        }
+        pid = get_parent_pid(context, pid)?;
+        if pid <= 1 {
+            break;
+        }
+    }
+    None
+}
+
+fn get_process_name(context: &dyn Context, pid: u32) -> Option<String> {
+    if let Some(name) = context.read_to_string(Path::new(&format!("/proc/{pid}/comm"))) {
+        return Some(name.trim().to_owned());
+    }
+    // `ps` may print the path of the executable, e.g. `/bin/zsh` or `-/bin/zsh`
+    let name = get_ps_field(context, pid, "comm")?;
+    let (login, path) = match name.strip_prefix('-') {
+        Some(path) => ("-", path),
+        None => ("", name.as_str()),
+    };
+    let file_name = Path::new(path).file_name()?.to_str()?;
+    Some(format!("{login}{file_name}"))
+}
+
+fn get_parent_pid(context: &dyn Context, pid: u32) -> Option<u32> {
+    let Some(stat) = context.read_to_string(Path::new(&format!("/proc/{pid}/stat"))) else {
+        return get_ps_field(context, pid, "ppid")?.parse().ok();
+    };
+    // The format is `pid (comm) state ppid ...`, where `comm` can contain spaces
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Returns the `field` of the process `pid` printed by `ps`, for systems without `/proc`.
+fn get_ps_field(context: &dyn Context, pid: u32, field: &str) -> Option<String> {
+    let output = context.run("ps", &["-o", &format!("{field}="), "-p", &pid.to_string()])?;
+    if !output.success {
+        return None;
+    }
+    let value = output.stdout.trim();
+    (!value.is_empty()).then(|| value.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::detect_shell;
+    use crate::context::FakeContext;
+    use rstest::rstest;
+
+    fn process(context: FakeContext, pid: u32, name: &str, parent: u32) -> FakeContext {
+        context
+            .with_file(&format!("/proc/{pid}/comm"), format!("{name}\n").as_bytes())
+            .with_file(
+                &format!("/proc/{pid}/stat"),
+                format!("{pid} ({name}) S {parent} {pid} {pid} 0").as_bytes(),
+            )
+    }
+
+    #[rstest]
+    #[case(process(FakeContext::new(), 100, "zsh", 1), Some("zsh"))]
+    #[case(process(FakeContext::new(), 100, "-bash", 1), Some("bash"))]
+    #[case(process(FakeContext::new(), 100, "nu", 1), Some("nu"))]
+    #[case(process(FakeContext::new(), 100, "-tcsh", 1), Some("tcsh"))]
+    #[case(
+        process(process(FakeContext::new(), 100, "ohcrab (1)", 90), 90, "zsh", 1),
+        Some("zsh")
+    )]
+    #[case(
+        process(FakeContext::new(), 100, "sh", 1).with_env("SHELL", "/usr/bin/bash"),
+        Some("bash")
+    )]
+    #[case(
+        FakeContext::new()
+            .with_command_output("ps -o comm= -p 100", "ohcrab\n")
+            .with_command_output("ps -o ppid= -p 100", "  90\n")
+            .with_command_output("ps -o comm= -p 90", "-/bin/zsh\n")
+            .with_env("SHELL", "/bin/bash"),
+        Some("zsh")
+    )]
+    #[case(
+        FakeContext::new().with_command_output("ps -o comm= -p 100", "/opt/homebrew/bin/nu\n"),
+        Some("nu")
+    )]
+    #[case(FakeContext::new().with_env("SHELL", "/usr/local/bin/fish"), Some("fish"))]
+    #[case(FakeContext::new(), None)]
+    fn test_detect_shell(#[case] context: FakeContext, #[case] expected: Option<&str>) {
+        assert_eq!(detect_shell(&context, 100).as_deref(), expected);
+    }
+}
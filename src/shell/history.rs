@@ -0,0 +1,215 @@
+/// A command read from the history file of a shell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub command: String,
+    /// When the command was run, in seconds since the Unix epoch, if the shell saved it.
+    pub timestamp: Option<u64>,
+}
+
+impl HistoryEntry {
+    pub fn new(command: &str, timestamp: Option<u64>) -> Self {
+        Self {
+            command: command.to_owned(),
+            timestamp,
+        }
+    }
+}
+
+/// Byte used by zsh to escape the bytes that have a special meaning for it.
+const ZSH_META: u8 = 0x83;
+
+/// Reverts the "metafication" zsh applies to its history file, where some bytes (e.g. the
+/// ones of non-ASCII characters) are written as `0x83` followed by the byte XOR 32.
+pub fn unmetafy(bytes: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut bytes = bytes.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte == ZSH_META {
+            if let Some(&next) = bytes.next() {
+                result.push(next ^ 32);
+            }
+        } else {
+            result.push(byte);
+        }
+    }
+    result
+}
+
+fn split_lines(content: &[u8]) -> impl Iterator<Item = &[u8]> {
+    content
+        .split(|&byte| byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+fn decode(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim().to_owned()
+}
+
+/// Parses the history of zsh, both in the plain and in the extended format
+/// (`: <start>:<duration>;<command>`).
+///
+/// New lines inside of a command are saved as a `\` at the end of the line.
+pub fn parse_zsh_history(content: &[u8]) -> Vec<HistoryEntry> {
+    let content = unmetafy(content);
+    let mut entries = vec![];
+    let mut lines = split_lines(&content);
+
+    while let Some(line) = lines.next() {
+        let (timestamp, mut command) = match parse_zsh_extended_line(line) {
+            Some((timestamp, command)) => (Some(timestamp), command.to_vec()),
+            None => (None, line.to_vec()),
+        };
+        while command.last() == Some(&b'\\') {
+            match lines.next() {
+                Some(next_line) => {
+                    command.pop();
+                    command.push(b'\n');
+                    command.extend_from_slice(next_line);
+                }
+                None => break,
+            }
+        }
+        let command = decode(&command);
+        if !command.is_empty() {
+            entries.push(HistoryEntry { command, timestamp });
+        }
+    }
+    entries
+}
+
+/// Splits a line of the extended format into its start time and its command.
+fn parse_zsh_extended_line(line: &[u8]) -> Option<(u64, &[u8])> {
+    let rest = line.strip_prefix(b": ")?;
+    let separator = rest.iter().position(|&byte| byte == b';')?;
+    let (header, command) = (&rest[..separator], &rest[separator + 1..]);
+    let header = std::str::from_utf8(header).ok()?;
+    let (start, duration) = header.split_once(':')?;
+    duration.parse::<u64>().ok()?;
+    Some((start.parse().ok()?, command))
+}
+
+/// Parses a history where commands can be preceded by a line with their start time,
+/// `<prefix><epoch>`, like bash with `HISTTIMEFORMAT` (`#`) or tcsh (`#+`).
+///
+/// If there are timestamps, all the lines until the next one belong to the same command,
+/// which is how multi-line commands are saved. Otherwise, every line is a command.
+pub fn parse_timestamped_history(content: &[u8], prefix: &str) -> Vec<HistoryEntry> {
+    let parse_timestamp = |line: &[u8]| -> Option<u64> {
+        let digits = line.strip_prefix(prefix.as_bytes())?;
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        std::str::from_utf8(digits).ok()?.parse().ok()
+    };
+    let has_timestamps = split_lines(content).any(|line| parse_timestamp(line).is_some());
+
+    let mut entries = vec![];
+    let mut push = |lines: &mut Vec<&[u8]>, timestamp: Option<u64>| {
+        let command = decode(&lines.join(&b'\n'));
+        if !command.is_empty() {
+            entries.push(HistoryEntry { command, timestamp });
+        }
+        lines.clear();
+    };
+
+    let mut timestamp = None;
+    let mut lines: Vec<&[u8]> = vec![];
+    for line in split_lines(content) {
+        if let Some(new_timestamp) = parse_timestamp(line) {
+            push(&mut lines, timestamp);
+            timestamp = Some(new_timestamp);
+        } else {
+            lines.push(line);
+            if !has_timestamps {
+                push(&mut lines, None);
+            }
+        }
+    }
+    push(&mut lines, timestamp);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_timestamped_history, parse_zsh_history, unmetafy, HistoryEntry};
+    use rstest::rstest;
+
+    #[test]
+    fn test_unmetafy() {
+        // "é" is 0xc3 0xa9, and zsh escapes 0xa9 as 0x83 0x89
+        assert_eq!(unmetafy(b"caf\xc3\x83\x89"), "café".as_bytes());
+        assert_eq!(unmetafy(b"ls"), b"ls");
+    }
+
+    #[rstest]
+    #[case(
+        b": 1702325001:0;ls -lah\n: 1702325002:3;cd /tmp\n",
+        vec![
+            HistoryEntry::new("ls -lah", Some(1702325001)),
+            HistoryEntry::new("cd /tmp", Some(1702325002)),
+        ]
+    )]
+    #[case(
+        b": 1702325001:0;for x in a b; do\\\n  echo $x\\\ndone\n: 1702325005:0;ls\n",
+        vec![
+            HistoryEntry::new("for x in a b; do\n  echo $x\ndone", Some(1702325001)),
+            HistoryEntry::new("ls", Some(1702325005)),
+        ]
+    )]
+    #[case(
+        b"git status\necho 'a;b'\n",
+        vec![
+            HistoryEntry::new("git status", None),
+            HistoryEntry::new("echo 'a;b'", None),
+        ]
+    )]
+    #[case(
+        b": 1702325001:0;echo caf\xc3\x83\x89\n: 1702325002:0;cat \xff\n",
+        vec![
+            HistoryEntry::new("echo café", Some(1702325001)),
+            HistoryEntry::new("cat \u{FFFD}", Some(1702325002)),
+        ]
+    )]
+    #[case(b"\n\n", vec![])]
+    fn test_parse_zsh_history(#[case] content: &[u8], #[case] expected: Vec<HistoryEntry>) {
+        assert_eq!(parse_zsh_history(content), expected);
+    }
+
+    #[rstest]
+    #[case(
+        b"ls -lah\ncd /tmp\n",
+        "#",
+        vec![HistoryEntry::new("ls -lah", None), HistoryEntry::new("cd /tmp", None)]
+    )]
+    #[case(
+        b"#1702325001\nls -lah\n#1702325002\nfor x in a b; do\n  echo $x\ndone\n",
+        "#",
+        vec![
+            HistoryEntry::new("ls -lah", Some(1702325001)),
+            HistoryEntry::new("for x in a b; do\n  echo $x\ndone", Some(1702325002)),
+        ]
+    )]
+    #[case(
+        b"# a comment\n#1702325001\necho \xe9\n",
+        "#",
+        vec![
+            HistoryEntry::new("# a comment", None),
+            HistoryEntry::new("echo \u{FFFD}", Some(1702325001)),
+        ]
+    )]
+    #[case(
+        b"#+1702325001\nls -lah\n#+1702325002\ncd /tmp\n",
+        "#+",
+        vec![
+            HistoryEntry::new("ls -lah", Some(1702325001)),
+            HistoryEntry::new("cd /tmp", Some(1702325002)),
+        ]
+    )]
+    fn test_parse_timestamped_history(
+        #[case] content: &[u8],
+        #[case] prefix: &str,
+        #[case] expected: Vec<HistoryEntry>,
+    ) {
+        assert_eq!(parse_timestamped_history(content, prefix), expected);
+    }
+}
@@ -1,46 +1,123 @@
-use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
 use std::path::Path;
 
+use crate::context::Context;
 use crate::{ARGUMENT_PLACEHOLDER, ENV_VAR_NAME_ALIAS, ENV_VAR_NAME_HISTORY, ENV_VAR_NAME_SHELL};
 
+pub mod detect;
+pub mod history;
+mod nushell;
+pub mod quote;
+mod tcsh;
+
+use detect::SUPPORTED_SHELLS;
+use history::{parse_timestamped_history, parse_zsh_history, HistoryEntry};
+pub use nushell::Nushell;
+use quote::{needs_quoting, quote_posix};
+pub use tcsh::Tcsh;
+
 pub trait Shell {
     fn app_alias(&self, alias_name: &str) -> String;
+    /// Returns the function the shell calls when a command is not found, which asks
+    /// ohcrab for suggestions, or `None` if the shell has no such hook.
+    fn command_not_found_hook(&self) -> Option<String> {
+        None
+    }
+    /// Returns the code that binds `key` to a function which corrects the command being
+    /// typed without running it, or the previous one if the command line is empty, and
+    /// replaces the command line with the selected correction without running it.
+    ///
+    /// `key` uses the notation of the shell, e.g. `\e\e` for Esc Esc.
+    fn keybinding(&self, key: &str) -> Option<String> {
+        None
+    }
     fn get_shell(&self) -> String;
-    fn get_history_file_name(&self) -> String;
+    /// Returns the path of the history file, looking up the variables of the shell and
+    /// the home directory through `context`.
+    fn get_history_file_name(&self, context: &dyn Context) -> String;
+    /// Returns the path of the file the shell runs at startup, e.g. `~/.bashrc`.
+    fn get_rc_file_name(&self, context: &dyn Context) -> String;
+    /// Returns the code added to the startup file by `ohcrab init --install`, given the
+    /// arguments passed to `ohcrab init` and the integration script they produce.
+    fn init_snippet(&self, init_arguments: &str, script: &str) -> String {
+        format!(
+            "eval \"$(ohcrab init {}{init_arguments})\"",
+            self.get_shell()
+        )
+    }
     fn script_from_history(&self, command_script: &str) -> String {
         command_script.to_owned()
     }
 
-    fn get_history(&self, file_path: Option<&str>) -> Vec<String> {
+    /// Parses the content of the history file, where each line is a command by default.
+    ///
+    /// Invalid UTF-8 sequences are replaced instead of dropping the command.
+    fn parse_history(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        String::from_utf8_lossy(content)
+            .lines()
+            .map(|line| self.script_from_history(line))
+            .map(|command| command.trim().to_owned())
+            .filter(|command| !command.is_empty())
+            .map(|command| HistoryEntry {
+                command,
+                timestamp: None,
+            })
+            .collect()
+    }
+
+    /// Returns the commands in the history, oldest first, along with when they were run
+    /// if the shell saves it.
+    ///
+    /// The history file is read through `context`.
+    fn get_history_entries(
+        &self,
+        file_path: Option<&str>,
+        context: &dyn Context,
+    ) -> Vec<HistoryEntry> {
         let history_file_name = {
             if let Some(path) = file_path {
                 path.to_owned()
             } else {
-                self.get_history_file_name()
+                self.get_history_file_name(context)
             }
         };
-        let mut history: Vec<String> = Vec::new();
-        if Path::new(&history_file_name).exists() {
-            if let Ok(file) = File::open(&history_file_name) {
-                let reader = io::BufReader::new(file);
-
-                // TODO: Limit history length based on settings
-                for line in reader.lines() {
-                    let prepared = self.script_from_history(&line.unwrap_or("".to_owned()));
-                    let prepared = prepared.trim();
-                    if !prepared.is_empty() {
-                        history.push(prepared.to_owned());
-                    }
-                }
-            }
+        // TODO: Limit history length based on settings
+        match context.read(Path::new(&history_file_name)) {
+            Some(content) => self.parse_history(&content),
+            None => vec![],
         }
-        history
+    }
+
+    fn get_history(&self, file_path: Option<&str>, context: &dyn Context) -> Vec<String> {
+        self.get_history_entries(file_path, context)
+            .into_iter()
+            .map(|entry| entry.command)
+            .collect()
     }
     fn and(&self, commands: Vec<&str>) -> String {
         commands.join(" && ")
     }
+    /// Quotes `word` so that the shell reads it as a single argument, as is.
+    fn quote(&self, word: &str) -> String {
+        quote_posix(word)
+    }
+    /// Rebuilds a command line from its arguments, e.g. `script_parts`, so that splitting
+    /// it gives back the same arguments.
+    ///
+    /// Arguments are only quoted when they could not have been typed unquoted, so that
+    /// variables, globs and `~` keep being expanded.
+    fn join(&self, parts: &[&str]) -> String {
+        parts
+            .iter()
+            .map(|part| {
+                if needs_quoting(part) {
+                    self.quote(part)
+                } else {
+                    part.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 
     fn get_builtin_commands(&self) -> Vec<String> {
         vec![
@@ -104,12 +181,18 @@ pub trait Shell {
     }
 }
 
-pub fn get_bash_type(shell_type: &str) -> Box<dyn Shell> {
+/// Returns the `Shell` with the given name, or an error message if it is not supported.
+pub fn get_bash_type(shell_type: &str) -> Result<Box<dyn Shell>, String> {
     let shell_candidate = shell_type.to_lowercase();
     match shell_candidate.as_str() {
-        "zsh" => Box::new(Zsh),
-        "bash" => Box::new(Bash),
-        _ => panic!("The shell '{shell_type}' is not supported yet"),
+        "zsh" => Ok(Box::new(Zsh)),
+        "bash" => Ok(Box::new(Bash)),
+        "nu" | "nushell" => Ok(Box::new(Nushell)),
+        "tcsh" | "csh" => Ok(Box::new(Tcsh)),
+        _ => Err(format!(
+            "The shell '{shell_type}' is not supported yet (supported shells: {})",
+            SUPPORTED_SHELLS.join(", ")
+        )),
     }
 }
 
@@ -137,19 +220,69 @@ impl Shell for Zsh {
         )
     }
 
-    fn script_from_history(&self, command_script: &str) -> String {
-        if command_script.contains(';') {
-            command_script.split_once(';').unwrap().1.to_owned()
-        } else {
-            "".to_owned()
-        }
+    fn command_not_found_hook(&self) -> Option<String> {
+        Some(format!(
+            r#"
+            command_not_found_handler () {{
+                printf 'zsh: command not found: %s\n' "$1" >&2;
+                local OC_CMD;
+                OC_CMD=$(
+                    {ENV_VAR_NAME_SHELL}="zsh" ohcrab --command-not-found -- "$@"
+                );
+                if [ -n "$OC_CMD" ]; then
+                    eval "$OC_CMD";
+                    return $?;
+                fi;
+                return 127;
+            }}
+            "#,
+        ))
     }
 
-    fn get_history_file_name(&self) -> String {
-        match env::var("HISTFILE") {
-            Ok(val) => val,
-            Err(_) => dirs::home_dir()
-                .unwrap()
+    fn keybinding(&self, key: &str) -> Option<String> {
+        Some(format!(
+            r#"
+            __ohcrab_keybinding () {{
+                local OC_SCRIPT="$BUFFER";
+                local OC_MODE="--buffer";
+                if [ -z "$OC_SCRIPT" ]; then
+                    OC_SCRIPT="$(fc -ln -1)";
+                    OC_MODE="";
+                fi;
+                local OC_CMD;
+                OC_CMD=$(
+                    {ENV_VAR_NAME_SHELL}="zsh" {ENV_VAR_NAME_HISTORY}="$OC_SCRIPT" ohcrab $OC_MODE </dev/tty
+                );
+                if [ -n "$OC_CMD" ]; then
+                    BUFFER="$OC_CMD";
+                    CURSOR=${{#BUFFER}};
+                fi;
+                zle reset-prompt;
+            }}
+            zle -N __ohcrab_keybinding;
+            bindkey '{key}' __ohcrab_keybinding;
+            "#,
+        ))
+    }
+
+    fn get_rc_file_name(&self, context: &dyn Context) -> String {
+        let config_dir = match context.env_var("ZDOTDIR") {
+            Some(val) => val.into(),
+            None => context.home_dir().unwrap_or_default(),
+        };
+        config_dir.join(".zshrc").to_str().unwrap().to_string()
+    }
+
+    fn parse_history(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        parse_zsh_history(content)
+    }
+
+    fn get_history_file_name(&self, context: &dyn Context) -> String {
+        match context.env_var("HISTFILE") {
+            Some(val) => val,
+            None => context
+                .home_dir()
+                .unwrap_or_default()
                 .join(".zsh_history")
                 .to_str()
                 .unwrap()
@@ -163,6 +296,10 @@ impl Shell for Bash {
         "bash".to_owned()
     }
 
+    fn parse_history(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        parse_timestamped_history(content, "#")
+    }
+
     fn app_alias(&self, alias_name: &str) -> String {
         format!(
             r#"
@@ -179,43 +316,233 @@ impl Shell for Bash {
         )
     }
 
-    fn get_history_file_name(&self) -> String {
-        match env::var("HISTFILE") {
-            Ok(val) => val,
-            Err(_) => dirs::home_dir()
-                .unwrap()
+    fn command_not_found_hook(&self) -> Option<String> {
+        Some(format!(
+            r#"
+            function command_not_found_handle () {{
+                printf 'bash: %s: command not found\n' "$1" >&2;
+                local OC_CMD;
+                OC_CMD=$(
+                    {ENV_VAR_NAME_SHELL}="bash" ohcrab --command-not-found -- "$@"
+                );
+                if [ -n "$OC_CMD" ]; then
+                    eval "$OC_CMD";
+                    return $?;
+                fi;
+                return 127;
+            }}
+            "#,
+        ))
+    }
+
+    fn keybinding(&self, key: &str) -> Option<String> {
+        Some(format!(
+            r#"
+            function __ohcrab_keybinding () {{
+                local OC_SCRIPT="$READLINE_LINE";
+                local OC_MODE="--buffer";
+                if [ -z "$OC_SCRIPT" ]; then
+                    OC_SCRIPT="$(fc -ln -1)";
+                    OC_MODE="";
+                fi;
+                local OC_CMD;
+                OC_CMD=$(
+                    {ENV_VAR_NAME_SHELL}="bash" {ENV_VAR_NAME_HISTORY}="$OC_SCRIPT" ohcrab $OC_MODE </dev/tty
+                );
+                if [ -n "$OC_CMD" ]; then
+                    READLINE_LINE="$OC_CMD";
+                    READLINE_POINT=${{#READLINE_LINE}};
+                fi;
+            }}
+            bind -x '"{key}": __ohcrab_keybinding';
+            "#,
+        ))
+    }
+
+    fn get_history_file_name(&self, context: &dyn Context) -> String {
+        match context.env_var("HISTFILE") {
+            Some(val) => val,
+            None => context
+                .home_dir()
+                .unwrap_or_default()
                 .join(".bash_history")
                 .to_str()
                 .unwrap()
                 .to_string(),
         }
     }
+
+    fn get_rc_file_name(&self, context: &dyn Context) -> String {
+        context
+            .home_dir()
+            .unwrap_or_default()
+            .join(".bashrc")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_bash_type;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("bash", "bash")]
+    #[case("ZSH", "zsh")]
+    #[case("nu", "nu")]
+    #[case("nushell", "nu")]
+    #[case("tcsh", "tcsh")]
+    #[case("csh", "tcsh")]
+    fn test_get_bash_type(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(get_bash_type(name).unwrap().get_shell(), expected);
+    }
+
+    #[test]
+    fn test_get_bash_type_unsupported() {
+        assert_eq!(
+            get_bash_type("fish").err().unwrap(),
+            "The shell 'fish' is not supported yet (supported shells: bash, zsh, nu, tcsh, csh)"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_bash {
+    use crate::context::FakeContext;
+    use crate::shell::{history::HistoryEntry, Shell};
+
+    use super::Bash;
+
+    #[test]
+    fn test_get_history_entries() {
+        let context = FakeContext::new().with_file(
+            "/home/crab/.bash_history",
+            b"#1702325001\nls -lah\n#1702325002\necho \xe9\n",
+        );
+
+        assert_eq!(
+            Bash {}.get_history_entries(Some("/home/crab/.bash_history"), &context),
+            vec![
+                HistoryEntry::new("ls -lah", Some(1702325001)),
+                HistoryEntry::new("echo \u{FFFD}", Some(1702325002)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_history_file_name() {
+        let context = FakeContext::new();
+        assert_eq!(
+            Bash {}.get_history_file_name(&context),
+            "/home/crab/.bash_history"
+        );
+        assert_eq!(Bash {}.get_rc_file_name(&context), "/home/crab/.bashrc");
+
+        let context = context.with_env("HISTFILE", "/tmp/history");
+        assert_eq!(Bash {}.get_history_file_name(&context), "/tmp/history");
+    }
+
+    #[test]
+    fn test_init_snippet() {
+        assert_eq!(
+            Bash {}.init_snippet(" --alias shinycrab", "function shinycrab () {}"),
+            r#"eval "$(ohcrab init bash --alias shinycrab)""#
+        );
+    }
+
+    #[test]
+    fn test_keybinding() {
+        let keybinding = Bash {}.keybinding("\\e\\e").unwrap();
+        assert!(keybinding.contains(r#"local OC_SCRIPT="$READLINE_LINE";"#));
+        assert!(keybinding.contains(r#"READLINE_LINE="$OC_CMD";"#));
+        assert!(keybinding.contains("ohcrab $OC_MODE </dev/tty"));
+        assert!(keybinding.contains(r#"bind -x '"\e\e": __ohcrab_keybinding';"#));
+    }
+
+    #[test]
+    fn test_command_not_found_hook() {
+        let hook = Bash {}.command_not_found_hook().unwrap();
+        assert!(hook.contains("function command_not_found_handle ()"));
+        assert!(hook.contains(r#"OHCRAB_SHELL="bash" ohcrab --command-not-found -- "$@""#));
+        assert!(hook.contains("return 127;"));
+    }
 }
 
 #[cfg(test)]
 mod test_zsh {
-    use crate::shell::Shell;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
+    use crate::context::FakeContext;
+    use crate::shell::{history::HistoryEntry, Shell};
 
     use super::Zsh;
 
     #[test]
     fn test_get_history() {
-        // Create a file inside of `std::env::temp_dir()`.
-        let mut file = NamedTempFile::new().unwrap();
-
-        writeln!(
-            file,
-            ": 1702325001:0;ls -lah\n: 1702325001:0;cd /tmp\n: 1702325001:0;nvim"
-        )
-        .unwrap();
-        let path = file.path().to_str().unwrap();
+        let context = FakeContext::new().with_file(
+            "/home/crab/.zsh_history",
+            b": 1702325001:0;ls -lah\n: 1702325001:0;cd /tmp\n: 1702325001:0;nvim\n",
+        );
 
         let system_shell = Zsh {};
         assert_eq!(
-            system_shell.get_history(Some(path)),
+            system_shell.get_history(Some("/home/crab/.zsh_history"), &context),
             vec!["ls -lah", "cd /tmp", "nvim"]
         );
     }
+
+    #[test]
+    fn test_get_history_entries() {
+        let context = FakeContext::new().with_file(
+            "/home/crab/.zsh_history",
+            b": 1702325001:0;echo caf\xc3\x83\x89\\\ndone\n: 1702325009:0;ls\n",
+        );
+
+        assert_eq!(
+            Zsh {}.get_history_entries(Some("/home/crab/.zsh_history"), &context),
+            vec![
+                HistoryEntry::new("echo caf\u{e9}\ndone", Some(1702325001)),
+                HistoryEntry::new("ls", Some(1702325009)),
+            ]
+        );
+        assert!(Zsh {}
+            .get_history_entries(Some("/nonexistent"), &context)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_history_file_name() {
+        let context = FakeContext::new();
+        assert_eq!(
+            Zsh {}.get_history_file_name(&context),
+            "/home/crab/.zsh_history"
+        );
+        assert_eq!(Zsh {}.get_rc_file_name(&context), "/home/crab/.zshrc");
+
+        let context = context
+            .with_env("HISTFILE", "/tmp/history")
+            .with_env("ZDOTDIR", "/home/crab/.config/zsh");
+        assert_eq!(Zsh {}.get_history_file_name(&context), "/tmp/history");
+        assert_eq!(
+            Zsh {}.get_rc_file_name(&context),
+            "/home/crab/.config/zsh/.zshrc"
+        );
+    }
+
+    #[test]
+    fn test_keybinding() {
+        let keybinding = Zsh {}.keybinding("^[^[").unwrap();
+        assert!(keybinding.contains(r#"local OC_SCRIPT="$BUFFER";"#));
+        assert!(keybinding.contains(r#"BUFFER="$OC_CMD";"#));
+        assert!(keybinding.contains("zle -N __ohcrab_keybinding;"));
+        assert!(keybinding.contains("bindkey '^[^[' __ohcrab_keybinding;"));
+    }
+
+    #[test]
+    fn test_command_not_found_hook() {
+        let hook = Zsh {}.command_not_found_hook().unwrap();
+        assert!(hook.contains("command_not_found_handler ()"));
+        assert!(hook.contains(r#"OHCRAB_SHELL="zsh" ohcrab --command-not-found -- "$@""#));
+        assert!(hook.contains("return 127;"));
+    }
 }
@@ -0,0 +1,384 @@
+use std::path::{Path, PathBuf};
+#[cfg(feature = "nushell-sqlite")]
+use std::ptr::{self, NonNull};
+
+#[cfg(feature = "nushell-sqlite")]
+use rusqlite::{ffi, serialize::OwnedData, Connection, DatabaseName};
+
+use super::history::HistoryEntry;
+use super::quote::quote_nushell;
+use super::Shell;
+use crate::context::Context;
+#[cfg(not(feature = "nushell-sqlite"))]
+use crate::utils::debug_log;
+use crate::{ARGUMENT_PLACEHOLDER, ENV_VAR_NAME_ALIAS, ENV_VAR_NAME_HISTORY, ENV_VAR_NAME_SHELL};
+
+/// Escape used by the plaintext history of Nushell for the new lines inside of an entry.
+const NEWLINE_ESCAPE: &str = "<\\n>";
+
+pub struct Nushell;
+
+impl Nushell {
+    fn get_config_dir(&self, context: &dyn Context) -> PathBuf {
+        context.config_dir().unwrap_or_default().join("nushell")
+    }
+
+    /// Parses the database of the SQLite history backend, oldest entries first.
+    #[cfg(feature = "nushell-sqlite")]
+    fn parse_sqlite_history(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        let query = |connection: Connection| -> rusqlite::Result<Vec<HistoryEntry>> {
+            let mut statement = connection
+                .prepare("SELECT command_line, start_timestamp FROM history ORDER BY id")?;
+            let rows = statement.query_map([], |row| {
+                // Start times are saved in milliseconds
+                let start_timestamp: Option<i64> = row.get(1)?;
+                Ok(HistoryEntry {
+                    command: row.get::<_, String>(0)?.trim().to_owned(),
+                    timestamp: start_timestamp.map(|timestamp| (timestamp / 1000) as u64),
+                })
+            })?;
+            rows.filter(|entry| {
+                entry
+                    .as_ref()
+                    .map_or(true, |entry| !entry.command.is_empty())
+            })
+            .collect()
+        };
+        let Ok(mut connection) = Connection::open_in_memory() else {
+            return vec![];
+        };
+        // SQLite takes ownership of the database, which must be allocated by it
+        let data = unsafe {
+            let Some(buffer) = NonNull::new(ffi::sqlite3_malloc64(content.len() as u64) as *mut u8)
+            else {
+                return vec![];
+            };
+            ptr::copy_nonoverlapping(content.as_ptr(), buffer.as_ptr(), content.len());
+            OwnedData::from_raw_nonnull(buffer, content.len())
+        };
+        connection
+            .deserialize(DatabaseName::Main, data, true)
+            .and_then(|_| query(connection))
+            .unwrap_or_default()
+    }
+
+    /// The SQLite history backend can only be read when built with the `nushell-sqlite`
+    /// feature.
+    #[cfg(not(feature = "nushell-sqlite"))]
+    fn parse_sqlite_history(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        debug_log("The SQLite history of Nushell needs the `nushell-sqlite` feature");
+        vec![]
+    }
+}
+
+impl Shell for Nushell {
+    fn get_shell(&self) -> String {
+        "nu".to_owned()
+    }
+
+    fn app_alias(&self, alias_name: &str) -> String {
+        // Nushell has no `fc` and cannot evaluate a string in the current scope, so the
+        // previous entry is taken from `history` (the last one is the call to the alias
+        // itself) and the correction runs in a new instance of the shell.
+        format!(
+            r#"
+            def --env {alias_name} [...args] {{
+                let oc_history = (history | last 2 | first | get command);
+                let oc_cmd = (with-env {{
+                    {ENV_VAR_NAME_SHELL}: "nu",
+                    {ENV_VAR_NAME_ALIAS}: "{alias_name}",
+                    {ENV_VAR_NAME_HISTORY}: $oc_history
+                }} {{
+                    ^ohcrab {ARGUMENT_PLACEHOLDER} ...$args
+                }});
+                if ($oc_cmd | is-not-empty) {{
+                    ^$nu.current-exe --commands $oc_cmd
+                }}
+            }}
+            "#,
+        )
+    }
+
+    /// The SQLite history is preferred when it exists, unless ohcrab was built without the
+    /// `nushell-sqlite` feature and cannot read it, in which case the plaintext history,
+    /// if any, is used.
+    fn get_history_file_name(&self, context: &dyn Context) -> String {
+        let config_dir = self.get_config_dir(context);
+        let sqlite_history = config_dir.join("history.sqlite3");
+        let history = if cfg!(feature = "nushell-sqlite") && context.exists(&sqlite_history) {
+            sqlite_history
+        } else {
+            config_dir.join("history.txt")
+        };
+        history.to_str().unwrap().to_owned()
+    }
+
+    /// Nushell cannot source the output of a command at startup, so the integration
+    /// script itself is installed, in a file that Nushell loads automatically.
+    fn get_rc_file_name(&self, context: &dyn Context) -> String {
+        self.get_config_dir(context)
+            .join("autoload")
+            .join("ohcrab.nu")
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    fn init_snippet(&self, init_arguments: &str, script: &str) -> String {
+        script.to_owned()
+    }
+
+    fn script_from_history(&self, command_script: &str) -> String {
+        command_script.replace(NEWLINE_ESCAPE, "\n")
+    }
+
+    fn get_history_entries(
+        &self,
+        file_path: Option<&str>,
+        context: &dyn Context,
+    ) -> Vec<HistoryEntry> {
+        let history_file_name = match file_path {
+            Some(path) => path.to_owned(),
+            None => self.get_history_file_name(context),
+        };
+        let Some(content) = context.read(Path::new(&history_file_name)) else {
+            return vec![];
+        };
+        if history_file_name.ends_with(".sqlite3") {
+            self.parse_sqlite_history(&content)
+        } else {
+            self.parse_history(&content)
+        }
+    }
+
+    /// Nushell has no `&&`: a sequence stops at the first command that fails, since the
+    /// failure of an external command is an error.
+    fn and(&self, commands: Vec<&str>) -> String {
+        commands.join("; ")
+    }
+
+    fn quote(&self, word: &str) -> String {
+        quote_nushell(word)
+    }
+
+    fn get_builtin_commands(&self) -> Vec<String> {
+        [
+            "alias",
+            "all",
+            "any",
+            "append",
+            "cd",
+            "clear",
+            "collect",
+            "compact",
+            "complete",
+            "config",
+            "cp",
+            "date",
+            "def",
+            "describe",
+            "do",
+            "drop",
+            "du",
+            "each",
+            "echo",
+            "enumerate",
+            "error",
+            "exec",
+            "exit",
+            "export",
+            "filter",
+            "find",
+            "first",
+            "flatten",
+            "for",
+            "format",
+            "from",
+            "get",
+            "glob",
+            "group-by",
+            "help",
+            "hide",
+            "history",
+            "http",
+            "if",
+            "ignore",
+            "input",
+            "insert",
+            "is-empty",
+            "is-not-empty",
+            "join",
+            "keybindings",
+            "kill",
+            "last",
+            "length",
+            "let",
+            "lines",
+            "load-env",
+            "loop",
+            "ls",
+            "match",
+            "merge",
+            "mkdir",
+            "module",
+            "move",
+            "mut",
+            "mv",
+            "open",
+            "overlay",
+            "par-each",
+            "parse",
+            "path",
+            "print",
+            "ps",
+            "reduce",
+            "reject",
+            "rename",
+            "return",
+            "reverse",
+            "rm",
+            "save",
+            "select",
+            "seq",
+            "skip",
+            "sleep",
+            "sort",
+            "sort-by",
+            "source",
+            "split",
+            "start",
+            "str",
+            "sys",
+            "table",
+            "take",
+            "to",
+            "touch",
+            "transpose",
+            "try",
+            "uniq",
+            "update",
+            "upsert",
+            "use",
+            "version",
+            "where",
+            "which",
+            "while",
+            "with-env",
+            "wrap",
+            "zip",
+        ]
+        .iter()
+        .map(|command| command.to_string())
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Nushell;
+    use crate::context::FakeContext;
+    #[cfg(feature = "nushell-sqlite")]
+    use crate::shell::history::HistoryEntry;
+    use crate::shell::Shell;
+    use rstest::rstest;
+    #[cfg(feature = "nushell-sqlite")]
+    use rusqlite::{Connection, DatabaseName};
+
+    #[test]
+    fn test_app_alias() {
+        let alias = Nushell {}.app_alias("shinycrab");
+        assert!(alias.contains("def --env shinycrab [...args] {"));
+        assert!(alias.contains("history | last 2 | first | get command"));
+        assert!(alias.contains("^ohcrab OHCRAB_ARGUMENT_PLACEHOLDER ...$args"));
+    }
+
+    #[test]
+    fn test_get_history_plaintext() {
+        let context = FakeContext::new().with_file(
+            "/home/crab/.config/nushell/history.txt",
+            b"ls -la\nfor x in [1 2] {<\\n>  print $x<\\n>}\n\ncd /tmp\n",
+        );
+
+        assert_eq!(
+            Nushell {}.get_history(Some("/home/crab/.config/nushell/history.txt"), &context),
+            vec!["ls -la", "for x in [1 2] {\n  print $x\n}", "cd /tmp"]
+        );
+    }
+
+    #[cfg(feature = "nushell-sqlite")]
+    #[test]
+    fn test_get_history_sqlite() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE history (
+                     id INTEGER PRIMARY KEY,
+                     command_line TEXT NOT NULL,
+                     start_timestamp INTEGER
+                 );
+                 INSERT INTO history VALUES (2, 'git stauts', 1702325009123);
+                 INSERT INTO history VALUES (1, 'ls -la', NULL);",
+            )
+            .unwrap();
+        let database = connection.serialize(DatabaseName::Main).unwrap();
+        let path = "/home/crab/.config/nushell/history.sqlite3";
+        let context = FakeContext::new().with_file(path, &database);
+
+        assert_eq!(
+            Nushell {}.get_history(Some(path), &context),
+            vec!["ls -la", "git stauts"]
+        );
+        assert_eq!(
+            Nushell {}.get_history_entries(Some(path), &context),
+            vec![
+                HistoryEntry::new("ls -la", None),
+                HistoryEntry::new("git stauts", Some(1702325009)),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(b"")]
+    #[case(b"not a database")]
+    fn test_get_history_invalid_sqlite(#[case] content: &[u8]) {
+        let path = "/home/crab/.config/nushell/history.sqlite3";
+        let context = FakeContext::new().with_file(path, content);
+        assert!(Nushell {}.get_history(Some(path), &context).is_empty());
+    }
+
+    #[test]
+    fn test_get_history_file_name() {
+        let context = FakeContext::new();
+        assert_eq!(
+            Nushell {}.get_history_file_name(&context),
+            "/home/crab/.config/nushell/history.txt"
+        );
+
+        let context = context.with_file("/home/crab/.config/nushell/history.sqlite3", b"");
+        let expected = if cfg!(feature = "nushell-sqlite") {
+            "/home/crab/.config/nushell/history.sqlite3"
+        } else {
+            "/home/crab/.config/nushell/history.txt"
+        };
+        assert_eq!(Nushell {}.get_history_file_name(&context), expected);
+    }
+
+    #[test]
+    fn test_get_history_missing_file() {
+        assert!(Nushell {}
+            .get_history(Some("/nonexistent/history.sqlite3"), &FakeContext::new())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_and() {
+        assert_eq!(Nushell {}.and(vec!["cd foo", "ls"]), "cd foo; ls");
+    }
+
+    #[test]
+    fn test_get_builtin_commands() {
+        let builtins = Nushell {}.get_builtin_commands();
+        assert!(builtins.contains(&"cd".to_owned()));
+        assert!(builtins.contains(&"with-env".to_owned()));
+    }
+}
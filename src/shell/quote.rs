@@ -0,0 +1,136 @@
+/// Returns `true` if `word` can be written as is, without any special meaning.
+fn is_safe(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c))
+}
+
+/// Returns `true` if `word` could not have been typed unquoted, i.e. it contains blanks,
+/// quotes, backslashes, or characters that separate commands or start comments.
+///
+/// Other special characters, such as `$`, `*` or `~`, are most likely meant to be expanded
+/// by the shell when they are part of an argument that was split from a command line.
+pub fn needs_quoting(word: &str) -> bool {
+    word.is_empty()
+        || word
+            .chars()
+            .any(|c| c.is_whitespace() || "'\"\\;&|<>()!#".contains(c))
+}
+
+/// Quotes `word` for POSIX shells, using single quotes, inside of which nothing is special.
+pub fn quote_posix(word: &str) -> String {
+    if is_safe(word) {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Quotes `word` for (t)csh, where `!` is special even inside of single quotes.
+pub fn quote_tcsh(word: &str) -> String {
+    if is_safe(word) {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''").replace('!', r"'\!'"))
+    }
+}
+
+/// Quotes `word` for Nushell, where single quotes cannot be escaped inside of single
+/// quoted strings, so double quotes are used when `word` contains one.
+pub fn quote_nushell(word: &str) -> String {
+    if is_safe(word) {
+        word.to_owned()
+    } else if !word.contains('\'') {
+        format!("'{word}'")
+    } else {
+        format!("\"{}\"", word.replace('\\', r"\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{needs_quoting, quote_nushell, quote_posix, quote_tcsh};
+    use crate::cli::command::shlex_split;
+    use crate::shell::{Bash, Nushell, Shell, Tcsh, Zsh};
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("status", "status")]
+    #[case("--force-with-lease=origin/main", "--force-with-lease=origin/main")]
+    #[case("", "''")]
+    #[case("my file.txt", "'my file.txt'")]
+    #[case("$HOME", "'$HOME'")]
+    #[case("it's", r"'it'\''s'")]
+    #[case("fix!", "'fix!'")]
+    fn test_quote_posix(#[case] word: &str, #[case] expected: &str) {
+        assert_eq!(quote_posix(word), expected);
+    }
+
+    #[rstest]
+    #[case("status", "status")]
+    #[case("fix!", r"'fix'\!''")]
+    #[case("it's", r"'it'\''s'")]
+    fn test_quote_tcsh(#[case] word: &str, #[case] expected: &str) {
+        assert_eq!(quote_tcsh(word), expected);
+    }
+
+    #[rstest]
+    #[case("status", "status")]
+    #[case("my file.txt", "'my file.txt'")]
+    #[case(r#"it's "a\b""#, r#""it's \"a\\b\"""#)]
+    fn test_quote_nushell(#[case] word: &str, #[case] expected: &str) {
+        assert_eq!(quote_nushell(word), expected);
+    }
+
+    #[rstest]
+    #[case("*.txt", false)]
+    #[case("$HOME", false)]
+    #[case("~/src", false)]
+    #[case("my file", true)]
+    #[case("a;b", true)]
+    #[case("", true)]
+    fn test_needs_quoting(#[case] word: &str, #[case] expected: bool) {
+        assert_eq!(needs_quoting(word), expected);
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            Bash {}.join(&["git", "commit", "-m", "fix the $PATH, again!", "*.rs"]),
+            "git commit -m 'fix the $PATH, again!' *.rs"
+        );
+    }
+
+    fn shells() -> Vec<Box<dyn Shell>> {
+        vec![
+            Box::new(Bash {}),
+            Box::new(Zsh {}),
+            Box::new(Nushell {}),
+            Box::new(Tcsh {}),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_quote_round_trip(word in "[a-z0-9 '\"$!*\\\\;&|~#\t\n\u{e9}-]{0,12}") {
+            for system_shell in shells() {
+                // tcsh cannot quote new lines in a way other shells read back
+                if system_shell.get_shell() == "tcsh" && word.contains('\n') {
+                    continue;
+                }
+                prop_assert_eq!(shlex_split(&system_shell.quote(&word)), vec![word.to_owned()]);
+            }
+        }
+
+        #[test]
+        fn test_split_join_split(script in "[a-z0-9 '\"$!*\\\\;&|~#-]{0,24}") {
+            let parts = shlex_split(&script);
+            let parts = parts.iter().map(|part| part.as_str()).collect::<Vec<_>>();
+            for system_shell in shells() {
+                prop_assert_eq!(shlex_split(&system_shell.join(&parts)), parts.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use super::history::{parse_timestamped_history, HistoryEntry};
+use super::quote::quote_tcsh;
+use super::Shell;
+use crate::context::Context;
+use crate::{ARGUMENT_PLACEHOLDER, ENV_VAR_NAME_ALIAS, ENV_VAR_NAME_HISTORY, ENV_VAR_NAME_SHELL};
+
+/// `tcsh`, also used for `csh`, which is `tcsh` on most systems.
+pub struct Tcsh;
+
+impl Shell for Tcsh {
+    fn get_shell(&self) -> String {
+        "tcsh".to_owned()
+    }
+
+    fn app_alias(&self, alias_name: &str) -> String {
+        // `history -h 2` prints the previous command and the call to the alias itself
+        format!(
+            "alias {alias_name} 'setenv {ENV_VAR_NAME_SHELL} tcsh && \
+             setenv {ENV_VAR_NAME_ALIAS} {alias_name} && \
+             setenv {ENV_VAR_NAME_HISTORY} \"`history -h 2 | head -n 1`\" && \
+             eval `ohcrab {ARGUMENT_PLACEHOLDER} \\!*`; \
+             unsetenv {ENV_VAR_NAME_HISTORY}'\n"
+        )
+    }
+
+    fn get_history_file_name(&self, context: &dyn Context) -> String {
+        match context.env_var("HISTFILE") {
+            Some(val) => val,
+            None => context
+                .home_dir()
+                .unwrap_or_default()
+                .join(".history")
+                .to_str()
+                .unwrap()
+                .to_string(),
+        }
+    }
+
+    fn get_rc_file_name(&self, context: &dyn Context) -> String {
+        context
+            .home_dir()
+            .unwrap_or_default()
+            .join(".tcshrc")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn init_snippet(&self, init_arguments: &str, script: &str) -> String {
+        format!("eval \"`ohcrab init tcsh{init_arguments}`\"")
+    }
+
+    /// The history file has a `#+<timestamp>` line before each command.
+    fn parse_history(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        parse_timestamped_history(content, "#+")
+    }
+
+    fn quote(&self, word: &str) -> String {
+        quote_tcsh(word)
+    }
+
+    fn get_builtin_commands(&self) -> Vec<String> {
+        vec![
+            "alias".to_owned(),
+            "alloc".to_owned(),
+            "bg".to_owned(),
+            "bindkey".to_owned(),
+            "break".to_owned(),
+            "builtins".to_owned(),
+            "case".to_owned(),
+            "cd".to_owned(),
+            "chdir".to_owned(),
+            "complete".to_owned(),
+            "continue".to_owned(),
+            "dirs".to_owned(),
+            "echo".to_owned(),
+            "echotc".to_owned(),
+            "else".to_owned(),
+            "end".to_owned(),
+            "endif".to_owned(),
+            "endsw".to_owned(),
+            "eval".to_owned(),
+            "exec".to_owned(),
+            "exit".to_owned(),
+            "fg".to_owned(),
+            "filetest".to_owned(),
+            "foreach".to_owned(),
+            "glob".to_owned(),
+            "goto".to_owned(),
+            "hashstat".to_owned(),
+            "history".to_owned(),
+            "hup".to_owned(),
+            "if".to_owned(),
+            "jobs".to_owned(),
+            "kill".to_owned(),
+            "limit".to_owned(),
+            "log".to_owned(),
+            "login".to_owned(),
+            "logout".to_owned(),
+            "ls-F".to_owned(),
+            "nice".to_owned(),
+            "nohup".to_owned(),
+            "notify".to_owned(),
+            "onintr".to_owned(),
+            "popd".to_owned(),
+            "printenv".to_owned(),
+            "pushd".to_owned(),
+            "rehash".to_owned(),
+            "repeat".to_owned(),
+            "sched".to_owned(),
+            "set".to_owned(),
+            "setenv".to_owned(),
+            "settc".to_owned(),
+            "setty".to_owned(),
+            "shift".to_owned(),
+            "source".to_owned(),
+            "stop".to_owned(),
+            "suspend".to_owned(),
+            "switch".to_owned(),
+            "telltc".to_owned(),
+            "time".to_owned(),
+            "umask".to_owned(),
+            "unalias".to_owned(),
+            "uncomplete".to_owned(),
+            "unhash".to_owned(),
+            "unlimit".to_owned(),
+            "unset".to_owned(),
+            "unsetenv".to_owned(),
+            "wait".to_owned(),
+            "where".to_owned(),
+            "which".to_owned(),
+            "while".to_owned(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tcsh;
+    use crate::context::FakeContext;
+    use crate::shell::{history::HistoryEntry, Shell};
+
+    #[test]
+    fn test_app_alias() {
+        let alias = Tcsh {}.app_alias("shinycrab");
+        assert!(alias.starts_with("alias shinycrab 'setenv OHCRAB_SHELL tcsh && "));
+        assert!(alias.contains("setenv OHCRAB_ALIAS shinycrab && "));
+        assert!(alias.contains(r#"setenv OHCRAB_COMMAND_HISTORY "`history -h 2 | head -n 1`""#));
+        assert!(alias.contains(r"eval `ohcrab OHCRAB_ARGUMENT_PLACEHOLDER \!*`"));
+    }
+
+    #[test]
+    fn test_get_history() {
+        let context = FakeContext::new().with_file(
+            "/home/crab/.history",
+            b"#+1702325001\nls -lah\n#+1702325002\ncd /tmp\n#+1702325003\nvim\n",
+        );
+
+        assert_eq!(
+            Tcsh {}.get_history(Some("/home/crab/.history"), &context),
+            vec!["ls -lah", "cd /tmp", "vim"]
+        );
+        assert_eq!(
+            Tcsh {}.get_history_entries(Some("/home/crab/.history"), &context)[2],
+            HistoryEntry::new("vim", Some(1702325003))
+        );
+    }
+
+    #[test]
+    fn test_init_snippet() {
+        assert_eq!(
+            Tcsh {}.init_snippet(" --hook", ""),
+            "eval \"`ohcrab init tcsh --hook`\""
+        );
+    }
+}
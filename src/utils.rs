@@ -1,10 +1,12 @@
 use fuzzt::{algorithms::SequenceMatcher, get_top_n};
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::env;
 
 use std::path::Path;
 
 use crate::cli::command::CrabCommand;
+use crate::context::Context;
 use crate::shell::Shell;
 
 use regex::Regex;
@@ -233,12 +235,14 @@ fn not_corrected(history: &Vec<String>, oc_alias: &String) -> Vec<String> {
 /// Returns a vector of valid history commands excluding the current command.
 ///
 /// The method compares the command with executables and shell builtins and
-/// ignores commands performed just after the alias is called ("crab").
+/// ignores commands performed just after the alias is called ("crab"). The most recent
+/// commands come first, so that they win when several of them are equally close.
 ///
 /// # Arguments
 ///
 /// * `command` - A reference to the current CrabCommand.
 /// * `system_shell` - A reference to the system shell.
+/// * `context` - The context used to list the available executables.
 ///
 /// # Returns
 ///
@@ -246,14 +250,19 @@ fn not_corrected(history: &Vec<String>, oc_alias: &String) -> Vec<String> {
 pub fn get_valid_history_without_current(
     command: &CrabCommand,
     system_shell: &dyn Shell,
+    context: &dyn Context,
 ) -> Vec<String> {
     let mut corrected: Vec<String> = Vec::new();
     let mut valid_history: Vec<String> = Vec::new();
 
-    let history = system_shell.get_history(None);
+    let history_entries = system_shell.get_history_entries(None, context);
+    let history: Vec<String> = history_entries
+        .iter()
+        .map(|entry| entry.command.to_owned())
+        .collect();
     let mut executables = history.clone();
     executables.extend(system_shell.get_builtin_commands());
-    executables.extend(get_all_executable());
+    executables.extend(context.executables());
     let executables: HashSet<_> = executables.into_iter().collect();
 
     for line in not_corrected(&history, &get_alias()) {
@@ -266,6 +275,14 @@ pub fn get_valid_history_without_current(
         }
     }
 
+    // Rank by the last time each command was run, or by its position in the history if
+    // the shell does not save timestamps
+    let mut last_run: HashMap<&str, (Option<u64>, usize)> = HashMap::new();
+    for (position, entry) in history_entries.iter().enumerate() {
+        last_run.insert(&entry.command, (entry.timestamp, position));
+    }
+    valid_history.sort_by_key(|line| Reverse(last_run.get(line.as_str()).copied()));
+    valid_history.dedup();
     valid_history
 }
 
@@ -316,7 +333,12 @@ pub fn get_all_matched_commands(stderr: &str, separator_option: Option<Vec<&str>
 mod tests {
     use mockall::mock;
 
-    use crate::{cli::command::CrabCommand, shell::Shell, utils::get_alias};
+    use crate::{
+        cli::command::CrabCommand,
+        context::{Context, FakeContext},
+        shell::{history::HistoryEntry, Shell},
+        utils::get_alias,
+    };
 
     use super::get_all_matched_commands;
     use super::get_valid_history_without_current;
@@ -327,9 +349,10 @@ mod tests {
         impl Shell for MyShell {
             fn app_alias(&self, alias_name: &str) -> String;
             fn get_shell(&self) -> String;
-            fn get_history_file_name(&self) -> String;
+            fn get_history_file_name(&self, context: &dyn Context) -> String;
+            fn get_rc_file_name(&self, context: &dyn Context) -> String;
             fn script_from_history(&self, command_script: &str) -> String;
-            fn get_history<'a>(&self, file_path: Option<&'a str>) -> Vec<String> ;
+            fn get_history_entries<'a>(&self, file_path: Option<&'a str>, context: &dyn Context) -> Vec<HistoryEntry>;
             fn get_builtin_commands(&self) -> Vec<String>;
         }
     }
@@ -342,36 +365,59 @@ mod tests {
         mock_shell
             .expect_get_builtin_commands()
             .returning(|| vec!["command1".to_string(), "command2".to_string()]);
-        mock_shell.expect_get_history().returning(|_| {
+        mock_shell.expect_get_history_entries().returning(|_, _| {
             vec![
-                "ls -l".to_string(),
-                "command1".to_string(),
-                "cmp a.txt b.txt".to_string(),
+                HistoryEntry::new("ls -l", None),
+                HistoryEntry::new("command1", None),
+                HistoryEntry::new("cmp a.txt b.txt", None),
             ]
         });
         let system_shell: Box<dyn Shell> = Box::new(mock_shell);
+        let context = FakeContext::new().with_program("ls").with_program("cmp");
 
         assert_eq!(
-            vec!["command1", "cmp a.txt b.txt"],
-            get_valid_history_without_current(&command, &*system_shell)
+            vec!["cmp a.txt b.txt", "command1"],
+            get_valid_history_without_current(&command, &*system_shell, &context)
         );
 
         let mut mock_shell = MockMyShell::new();
         mock_shell
             .expect_get_builtin_commands()
             .returning(|| vec!["command1".to_string(), "command2".to_string()]);
-        mock_shell.expect_get_history().returning(|_| {
+        mock_shell.expect_get_history_entries().returning(|_, _| {
             vec![
-                "ls -l".to_string(),
-                "cmp a.txt b.txt".to_string(),
-                get_alias(),
+                HistoryEntry::new("ls -l", None),
+                HistoryEntry::new("cmp a.txt b.txt", None),
+                HistoryEntry::new(&get_alias(), None),
             ]
         });
         let system_shell: Box<dyn Shell> = Box::new(mock_shell);
         // Skip "cmp a.txt b.txt" because it comes before "crab" (alias)
         assert_eq!(
             Vec::<String>::new(),
-            get_valid_history_without_current(&command, &*system_shell)
+            get_valid_history_without_current(&command, &*system_shell, &context)
+        );
+    }
+
+    #[test]
+    fn test_get_valid_history_ranked_by_recency() {
+        let command = CrabCommand::new("gti".to_owned(), Some("".to_owned()), None);
+        let mut mock_shell = MockMyShell::new();
+        mock_shell.expect_get_builtin_commands().returning(Vec::new);
+        mock_shell.expect_get_history_entries().returning(|_, _| {
+            vec![
+                HistoryEntry::new("git status", Some(1702325001)),
+                HistoryEntry::new("git log", Some(1702325005)),
+                HistoryEntry::new("git status", Some(1702325009)),
+                HistoryEntry::new("git diff", Some(1702325003)),
+            ]
+        });
+        let system_shell: Box<dyn Shell> = Box::new(mock_shell);
+        let context = FakeContext::new().with_program("git");
+
+        assert_eq!(
+            vec!["git status", "git log", "git diff"],
+            get_valid_history_without_current(&command, &*system_shell, &context)
         );
     }
 
@@ -113,3 +113,166 @@ fn test_piped_command() {
         .success()
         .stdout(predicate::str::contains("git status | grep foo"));
 }
+
+// Localized Environment
+/// Tests that a command failing with a localized message is still corrected, since it is
+/// re-run with the C locale. The script mimics a program translated to German.
+#[cfg(target_family = "unix")]
+#[test]
+fn test_localized_environment() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp_dir.path().join("localized.sh"),
+        r#"if [ "$LC_ALL" = C ]; then echo "Permission denied" >&2; else echo "Keine Berechtigung" >&2; fi; exit 1"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("LC_ALL", "de_DE.UTF-8")
+        .env("LANG", "de_DE.UTF-8")
+        .env("LANGUAGE", "de")
+        .arg("--select-first")
+        .arg("--")
+        .arg("sh localized.sh")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sudo sh localized.sh"));
+
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("LC_ALL", "de_DE.UTF-8")
+        .env("LANG", "de_DE.UTF-8")
+        .arg("--select-first")
+        .arg("--keep-locale")
+        .arg("--")
+        .arg("sh localized.sh")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sudo").not());
+}
+
+// Command Not Found Hook
+/// Tests the mode used by the shell "command not found" hook, which only suggests
+/// corrections by default and prints the selected one when offering to fix it.
+#[test]
+fn test_command_not_found() {
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("--command-not-found")
+        .arg("--")
+        .arg("gti")
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git status").not())
+        .stderr(
+            predicate::str::contains("Did you mean").and(predicate::str::contains("git status")),
+        );
+
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("--command-not-found")
+        .arg("--offer-fix")
+        .arg("--select-first")
+        .arg("--")
+        .arg("gti")
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git status"));
+}
+
+#[test]
+fn test_hook_generation() {
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("--shell=zsh")
+        .arg("--hook")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("command_not_found_handler"));
+}
+
+// Init Subcommand
+#[test]
+fn test_init() {
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("init")
+        .arg("bash")
+        .arg("--alias=shinycrab")
+        .arg("--hook")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("function shinycrab ()")
+                .and(predicate::str::contains("command_not_found_handle")),
+        );
+}
+
+#[test]
+fn test_init_unsupported_shell() {
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("init")
+        .arg("fish")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "The shell 'fish' is not supported yet",
+        ));
+}
+
+#[test]
+fn test_init_install() {
+    let home = tempfile::tempdir().unwrap();
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+        cmd.env("HOME", home.path())
+            .env_remove("ZDOTDIR")
+            .arg("init")
+            .arg("zsh")
+            .arg("--install")
+            .assert()
+            .success();
+    }
+    let rc_file = std::fs::read_to_string(home.path().join(".zshrc")).unwrap();
+    assert_eq!(rc_file.matches("eval \"$(ohcrab init zsh)\"").count(), 1);
+}
+
+#[test]
+fn test_init_keybinding() {
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("init")
+        .arg("bash")
+        .arg("--keybinding")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"bind -x '"\e\e": __ohcrab_keybinding';"#,
+        ));
+
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.arg("init")
+        .arg("zsh")
+        .arg("--keybinding=^X^X")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "bindkey '^X^X' __ohcrab_keybinding;",
+        ));
+}
+
+// Command Line Buffer
+#[test]
+fn test_buffer() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("ohcrab").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("--buffer")
+        .arg("--select-first")
+        .arg("--")
+        .arg("mkdir")
+        .arg("foo/bar")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("mkdir -p foo/bar"));
+    // The command was not run
+    assert!(!dir.path().join("foo").exists());
+}